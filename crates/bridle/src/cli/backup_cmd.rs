@@ -0,0 +1,132 @@
+//! `bridle backup` subcommands for live-config snapshots.
+
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::profile::{get_manager, resolve_harness};
+use crate::config::BackupEntry;
+use crate::error::{Error, Result};
+
+#[derive(Serialize)]
+struct BackupListEntry {
+    taken_at: String,
+    path: String,
+}
+
+/// Parses a `--since` value: a duration back from `now` (`30m`, `24h`, `7d`,
+/// `2w`), an ISO date (`2026-10-01`), or an ISO date and time.
+fn parse_since(input: &str, now: NaiveDateTime) -> Result<NaiveDateTime> {
+    let invalid = || {
+        Error::InvalidValue(format!(
+            "'{input}' is not a duration (e.g. 7d, 24h) or ISO date"
+        ))
+    };
+    let input = input.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).expect("midnight is valid"));
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(at);
+    }
+    let (unit_at, _) = input.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = input.split_at(unit_at);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let span = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    now.checked_sub_signed(span).ok_or_else(invalid)
+}
+
+/// Backups taken at or after `since`, keeping their order.
+fn backups_since(backups: Vec<BackupEntry>, since: Option<NaiveDateTime>) -> Vec<BackupEntry> {
+    match since {
+        Some(since) => backups
+            .into_iter()
+            .filter(|b| b.taken_at >= since)
+            .collect(),
+        None => backups,
+    }
+}
+
+/// Lists a harness's live-config backups, oldest first.
+pub fn list_backups(harness_name: &str, since: Option<&str>, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let since = since
+        .map(|s| parse_since(s, Local::now().naive_local()))
+        .transpose()?;
+    let entries: Vec<BackupListEntry> =
+        backups_since(get_manager()?.list_backups(&harness)?, since)
+            .into_iter()
+            .map(|b| BackupListEntry {
+                taken_at: b.taken_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                path: b.path.display().to_string(),
+            })
+            .collect();
+
+    output_list(&entries, format, |entries| {
+        if entries.is_empty() {
+            println!("No backups for {}", harness_name);
+        }
+        for entry in entries {
+            println!("  {}  {}", entry.taken_at, entry.path);
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileManager;
+    use crate::test_env::setup_test_env;
+    use tempfile::TempDir;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y%m%d_%H%M%S").unwrap()
+    }
+
+    #[test]
+    fn since_seven_days_keeps_recent_backups() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let harness = resolve_harness("claude-code").unwrap();
+        let dir = manager.backups_dir().join("claude-code");
+        for name in [
+            "20261001_120000",
+            "20261010_080000",
+            "20261014_090000",
+            "extra",
+            "not-a-date",
+        ] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+        }
+
+        let since = parse_since("7d", at("20261014_100000")).unwrap();
+        assert_eq!(since, at("20261007_100000"));
+        let kept: Vec<NaiveDateTime> =
+            backups_since(manager.list_backups(&harness).unwrap(), Some(since))
+                .into_iter()
+                .map(|b| b.taken_at)
+                .collect();
+        assert_eq!(kept, [at("20261010_080000"), at("20261014_090000")]);
+    }
+
+    #[test]
+    fn since_accepts_dates_and_rejects_garbage() {
+        let now = at("20261014_100000");
+        assert_eq!(
+            parse_since("2026-10-01", now).unwrap(),
+            at("20261001_000000")
+        );
+        assert_eq!(parse_since("24h", now).unwrap(), at("20261013_100000"));
+        assert!(parse_since("7y", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+}
@@ -1,12 +1,29 @@
 //! CLI subcommand definitions.
 
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+
+use crate::config::{ProfileName, ProfileTemplate};
+use crate::display::ProfileSection;
+
+/// Which live harness config a command reads or replaces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ScopeArg {
+    /// The user's global config (e.g. `~/.claude`).
+    #[default]
+    Global,
+    /// The project config under the current directory (e.g. `./.claude`).
+    Project,
+}
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Show status of all harnesses.
     Status,
 
+    /// Check bridle's config and profiles for problems; exits non-zero if a
+    /// check fails.
+    Doctor,
+
     /// Initialize bridle configuration.
     Init,
 
@@ -21,6 +38,18 @@ pub enum Commands {
     #[command(subcommand)]
     Config(ConfigCommands),
 
+    /// Inspect a profile's MCP servers.
+    #[command(subcommand)]
+    Mcp(McpCommands),
+
+    /// Inspect live-config backups taken before switches.
+    #[command(subcommand)]
+    Backup(BackupCommands),
+
+    /// Manage custom harness definitions.
+    #[command(subcommand)]
+    Harness(HarnessCommands),
+
     /// Install skills from a GitHub repository.
     Install {
         /// GitHub repository URL or owner/repo shorthand.
@@ -28,6 +57,18 @@ pub enum Commands {
         /// Force overwrite existing skills.
         #[arg(long, short)]
         force: bool,
+        /// Harness to install into, skipping target selection (with --profile).
+        #[arg(long, requires = "profile")]
+        harness: Option<String>,
+        /// Profile to install into, active or not (with --harness).
+        #[arg(long, requires = "harness")]
+        profile: Option<String>,
+        /// Create the --profile if it doesn't exist.
+        #[arg(long, requires = "profile")]
+        create: bool,
+        /// Install without confirming the summary of what will be written.
+        #[arg(long, short)]
+        yes: bool,
     },
 
     /// Uninstall components from a profile.
@@ -39,14 +80,63 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum McpCommands {
+    /// List MCP servers with their enabled state and transport.
+    List {
+        /// Harness name.
+        harness: String,
+        /// Profile to inspect instead of the active one.
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Show one MCP server's command, args, URL, and environment variable names.
+    Show {
+        /// Harness name.
+        harness: String,
+        /// MCP server name.
+        name: String,
+        /// Profile to inspect instead of the active one.
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupCommands {
+    /// List backups for a harness, oldest first.
+    List {
+        /// Harness name.
+        harness: String,
+        /// Only backups newer than a duration (7d, 24h) or ISO date.
+        #[arg(long)]
+        since: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HarnessCommands {
+    /// Register a custom harness from a TOML descriptor.
+    Add {
+        /// Path to the descriptor file.
+        file: std::path::PathBuf,
+    },
+
+    /// List custom harnesses.
+    List,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommands {
     /// Set a configuration value.
     Set {
-        /// Setting name (e.g., profile_marker).
+        /// Setting name (e.g., profile_marker), or a dotted key with --harness.
         key: String,
         /// Value to set (true/false for booleans).
         value: String,
+        /// Set the key in this harness's active profile config instead of bridle's.
+        #[arg(long)]
+        harness: Option<String>,
     },
 
     /// Get a configuration value.
@@ -54,14 +144,25 @@ pub enum ConfigCommands {
         /// Setting name.
         key: String,
     },
+
+    /// Open the bridle config file in your editor.
+    Edit,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum ProfileCommands {
     /// List profiles for a harness.
+    #[command(visible_alias = "ls")]
     List {
         /// Harness name (claude-code, opencode, goose, amp-code, crush, copilot-cli).
         harness: String,
+        /// One tab-separated `name model mcp_count active` line per profile.
+        #[arg(long)]
+        compact: bool,
+        /// With --compact, stop at the first profile that fails to load
+        /// instead of listing it with its error.
+        #[arg(long, requires = "compact")]
+        fail_fast: bool,
     },
 
     /// Show details of a specific profile.
@@ -70,6 +171,18 @@ pub enum ProfileCommands {
         harness: String,
         /// Profile name.
         name: String,
+        /// Only show these sections (repeatable).
+        #[arg(long = "resource", value_enum)]
+        resources: Vec<ProfileSection>,
+        /// Print one `key: value` line per entry instead of grouped sections.
+        #[arg(long)]
+        plain: bool,
+        /// Print only the profile directory's absolute path.
+        #[arg(long, conflicts_with_all = ["resources", "plain"])]
+        print_path: bool,
+        /// Live config an active profile is read from.
+        #[arg(long, value_enum, default_value_t)]
+        scope: ScopeArg,
     },
 
     /// Create a new profile.
@@ -77,10 +190,70 @@ pub enum ProfileCommands {
         /// Harness name.
         harness: String,
         /// Profile name.
-        name: String,
+        name: ProfileName,
         /// Copy current harness config to the new profile.
         #[arg(long)]
         from_current: bool,
+        /// Create an empty profile without reading the live config.
+        #[arg(long, conflicts_with = "from_current")]
+        empty: bool,
+        /// Start from a built-in starter profile instead of the live config.
+        #[arg(long, value_enum, conflicts_with_all = ["from_current", "empty", "force"])]
+        template: Option<ProfileTemplate>,
+        /// Replace an existing profile with the current config (old one is backed up).
+        #[arg(long, conflicts_with = "empty")]
+        force: bool,
+        /// Live config to capture.
+        #[arg(long, value_enum, default_value_t)]
+        scope: ScopeArg,
+        /// Also leave out paths matching this glob when capturing (repeatable).
+        #[arg(long, value_name = "PATTERN", conflicts_with = "empty")]
+        exclude: Vec<String>,
+        /// Capture paths matching this glob even if otherwise excluded (repeatable).
+        #[arg(long, value_name = "PATTERN", conflicts_with = "empty")]
+        include: Vec<String>,
+        /// Capture only config files, leaving out skills, agents, commands, and plugins.
+        #[arg(long, conflicts_with_all = ["empty", "template"])]
+        no_resources: bool,
+        /// Print only the new profile directory's absolute path.
+        #[arg(long)]
+        print_path: bool,
+    },
+
+    /// Create a profile from another harness's profile, copying only skills,
+    /// agents, and commands.
+    Copy {
+        /// Source harness name.
+        #[arg(long)]
+        from_harness: String,
+        /// Source profile name.
+        #[arg(long)]
+        from: String,
+        /// Target harness name.
+        #[arg(long)]
+        to_harness: String,
+        /// New profile name.
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Pack an inactive profile into a .tar.gz; switching to it unpacks it.
+    Compress {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+    },
+
+    /// Move a profile to another harness, keeping only its skills, agents,
+    /// and commands.
+    Move {
+        /// Harness the profile is under now.
+        harness: String,
+        /// Profile name.
+        name: String,
+        /// Harness to move it to.
+        new_harness: String,
     },
 
     /// Delete a profile.
@@ -88,7 +261,17 @@ pub enum ProfileCommands {
         /// Harness name.
         harness: String,
         /// Profile name.
-        name: String,
+        #[arg(required_unless_present = "glob", conflicts_with = "glob")]
+        name: Option<String>,
+        /// Delete every inactive profile matching a pattern such as `temp-*`.
+        #[arg(long)]
+        glob: Option<String>,
+        /// Skip the confirmation prompt for `--glob`.
+        #[arg(long, short)]
+        yes: bool,
+        /// Show what would be removed without deleting anything.
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Switch to a profile (set as active).
@@ -97,6 +280,33 @@ pub enum ProfileCommands {
         harness: String,
         /// Profile name.
         name: String,
+        /// Live config to replace.
+        #[arg(long, value_enum, default_value_t)]
+        scope: ScopeArg,
+        /// Switch only config files; live skills, agents, commands, and plugins stay as they are.
+        #[arg(long)]
+        no_resources: bool,
+        /// Switch even if the active profile is locked.
+        #[arg(long)]
+        force: bool,
+        /// Print only the absolute path of the live config dir written to.
+        #[arg(long)]
+        print_path: bool,
+    },
+
+    /// Discard edits to the live config and re-apply the active profile as
+    /// stored (the live config is backed up first).
+    Reset {
+        /// Harness name.
+        harness: String,
+    },
+
+    /// Show when a profile was created, switched to, edited, and synced.
+    Log {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
     },
 
     /// Edit a profile with $EDITOR.
@@ -105,15 +315,137 @@ pub enum ProfileCommands {
         harness: String,
         /// Profile name.
         name: String,
+        /// Open a single file, relative to the profile directory.
+        #[arg(long)]
+        file: Option<String>,
+    },
+
+    /// Edit the live config of the active profile, then offer to save it back.
+    OpenActive {
+        /// Harness name.
+        harness: String,
     },
 
     /// Compare two profiles or profile vs current config.
     Diff {
         /// Harness name.
         harness: String,
-        /// First profile name.
-        name: String,
+        /// First profile name (with --against-backup, the profile to compare
+        /// instead of the current config).
+        #[arg(required_unless_present = "against_backup")]
+        name: Option<String>,
         /// Second profile name (optional, defaults to current config).
+        #[arg(conflicts_with = "against_backup")]
         other: Option<String>,
+        /// Compare against a backup instead: its timestamp from `backup list`, or `latest`.
+        #[arg(long, value_name = "TIMESTAMP")]
+        against_backup: Option<String>,
+        /// Treat line-ending and trailing-whitespace changes as differences.
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Compare two stored profiles' settings and resources, never reading the live config.
+    Compare {
+        /// Harness name.
+        harness: String,
+        /// Profile to compare from.
+        from: String,
+        /// Profile to compare to.
+        to: String,
+    },
+
+    /// Restore the live config from the backup taken before the last switch.
+    Rollback {
+        /// Harness name.
+        harness: String,
+        /// Live config to restore.
+        #[arg(long, value_enum, default_value_t)]
+        scope: ScopeArg,
+        /// Roll back even if the active profile is locked.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check a profile's config files, MCP servers, skills, and manifest.
+    Verify {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+    },
+
+    /// Check a profile's main config file against a bundled JSON Schema
+    /// (opencode, claude-code, and amp-code).
+    ValidateConfig {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+    },
+
+    /// Print a file from a profile (the live copy if the profile is active).
+    Cat {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+        /// File path relative to the profile directory.
+        file: String,
+        /// Read the stored copy even when the profile is active.
+        #[arg(long)]
+        stored: bool,
+    },
+
+    /// Print a content hash of a profile, for checking two setups match.
+    Fingerprint {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+    },
+
+    /// Delete profiles stored for harnesses that are no longer installed or defined.
+    PruneOrphans {
+        /// List what would be deleted without deleting it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete without asking for confirmation.
+        #[arg(long, short)]
+        yes: bool,
+    },
+
+    /// Show aggregate resource counts across all profiles of a harness.
+    Stats {
+        /// Harness name.
+        harness: String,
+        /// Stop at the first profile that fails to load instead of counting
+        /// the rest.
+        #[arg(long)]
+        fail_fast: bool,
+    },
+
+    /// Set a profile's description and tags.
+    SetMeta {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+        /// Free-form description.
+        #[arg(long)]
+        description: Option<String>,
+        /// Tag to apply (repeatable; replaces existing tags).
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Lock the profile (`true`) or unlock it (`false`). While locked and
+        /// active, switching away needs `--force`; it can't be deleted.
+        #[arg(long)]
+        locked: Option<bool>,
+    },
+
+    /// Print the active profile name for a harness.
+    Which {
+        /// Harness name.
+        harness: String,
     },
 }
@@ -1,6 +1,11 @@
+use std::path::{Path, PathBuf};
+
 use harness_locate::{Harness, HarnessKind};
 
-use crate::config::BridleConfig;
+use crate::cli::output::write_atomically;
+use crate::cli::profile::{get_manager, open_in_editor, resolve_harness};
+use crate::config::jsonc::set_jsonc_value;
+use crate::config::{BridleConfig, ProfileName, is_probably_binary};
 use crate::error::{Error, Result};
 use crate::harness::HarnessConfig;
 
@@ -11,6 +16,116 @@ pub fn set_config(key: &str, value: &str) -> Result<()> {
     }
 }
 
+/// Names the main settings file of each built-in harness may have, relative
+/// to its config dir, preferred first.
+fn harness_settings_files(harness_id: &str) -> Option<&'static [&'static str]> {
+    match harness_id {
+        "opencode" => Some(&["opencode.jsonc", "opencode.json"]),
+        "claude-code" | "amp-code" => Some(&["settings.json"]),
+        "goose" => Some(&["config.yaml"]),
+        "crush" => Some(&["crush.json"]),
+        "copilot-cli" => Some(&["config.json"]),
+        _ => None,
+    }
+}
+
+/// The settings file in `dir`: the first of `names` that exists, or the
+/// preferred one when none does yet.
+fn settings_path(dir: &Path, names: &[&str]) -> PathBuf {
+    names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(names[0]))
+}
+
+/// Sets `key` (dotted for nested tables, e.g. `agent.general.model`) in the
+/// settings file of the harness's active profile, both in the live config and
+/// in the stored profile.
+///
+/// `value` is taken as JSON when it parses (`true`, `3`, `["a"]`) and as a
+/// string otherwise. JSON and JSONC files keep their comments; YAML files are
+/// rewritten from the parsed document.
+pub fn set_harness_config(harness_name: &str, key: &str, value: &str) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let files = harness_settings_files(harness.id()).ok_or_else(|| {
+        Error::InvalidValue(format!(
+            "{} has no settings file bridle can edit",
+            harness.id()
+        ))
+    })?;
+    let active = BridleConfig::load()?
+        .active_profile_for(&harness.active_key())
+        .map(String::from)
+        .ok_or(Error::NoActiveProfile)?;
+    let name = ProfileName::new(&active).map_err(|_| Error::InvalidProfileName(active.clone()))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| value.into());
+
+    let manager = get_manager()?;
+    let profile_dir = manager.profile_path(&harness, &name);
+    let profile_file = settings_path(&profile_dir, files);
+    manager.detach_hardlinks(&profile_file)?;
+    for path in [settings_path(&harness.config_dir()?, files), profile_file] {
+        write_setting(&path, key, &value)?;
+        println!("Set {} in {}", key, path.display());
+    }
+    Ok(())
+}
+
+/// Writes `value` at the dotted `key` in the JSON, JSONC or YAML file at
+/// `path`, creating the file if needed. The file is replaced in one rename,
+/// so a failed write never leaves it truncated.
+fn write_setting(path: &Path, key: &str, value: &serde_json::Value) -> Result<()> {
+    let keys: Vec<&str> = key.split('.').collect();
+    if keys.iter().any(|k| k.is_empty()) {
+        return Err(Error::InvalidValue(format!("'{}' is not a valid key", key)));
+    }
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if is_probably_binary(&bytes) {
+        return Err(Error::InvalidValue(format!(
+            "{} looks like a binary file; not editing it",
+            path.display()
+        )));
+    }
+    let content = String::from_utf8_lossy(&bytes);
+    let is_yaml = path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+    let updated = if is_yaml {
+        let mut doc: serde_yaml::Value = if content.trim().is_empty() {
+            serde_yaml::Value::Mapping(Default::default())
+        } else {
+            serde_yaml::from_str(&content)?
+        };
+        set_yaml_value(&mut doc, &keys, serde_yaml::to_value(value)?);
+        serde_yaml::to_string(&doc)?
+    } else if content.trim().is_empty() {
+        set_jsonc_value("{}\n", &keys, value)?
+    } else {
+        set_jsonc_value(&content, &keys, value)?
+    };
+    write_atomically(path, &updated)
+}
+
+/// Sets `keys` in `doc`, replacing anything in the way with mappings.
+fn set_yaml_value(doc: &mut serde_yaml::Value, keys: &[&str], value: serde_yaml::Value) {
+    let Some((first, rest)) = keys.split_first() else {
+        *doc = value;
+        return;
+    };
+    if !doc.is_mapping() {
+        *doc = serde_yaml::Value::Mapping(Default::default());
+    }
+    let map = doc.as_mapping_mut().expect("just made a mapping");
+    let key = serde_yaml::Value::String(first.to_string());
+    let entry = map.entry(key).or_insert(serde_yaml::Value::Null);
+    set_yaml_value(entry, rest, value);
+}
+
 pub fn get_config(key: &str) -> Result<()> {
     let config = BridleConfig::load()?;
 
@@ -21,6 +136,45 @@ pub fn get_config(key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Opens bridle's `config.toml` in the editor, writing the defaults first if
+/// it doesn't exist.
+///
+/// The editor works on a copy. If the copy no longer parses, the config is
+/// left unchanged and the copy kept so the edit isn't lost.
+pub fn edit_config() -> Result<()> {
+    let path = BridleConfig::config_path()?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        BridleConfig::default().save()?;
+    }
+
+    let draft = path.with_extension("toml.edit");
+    std::fs::copy(&path, &draft)?;
+    if let Err(e) = open_in_editor(&draft) {
+        let _ = std::fs::remove_file(&draft);
+        return Err(e);
+    }
+
+    match apply_edited_config(&draft, &path) {
+        Ok(()) => println!("Saved {}", path.display()),
+        Err(e) => eprintln!(
+            "Warning: edited config does not parse ({e}); {} is unchanged, edit kept at {}",
+            path.display(),
+            draft.display()
+        ),
+    }
+    Ok(())
+}
+
+/// Moves `draft` over `target` if it parses as a bridle config.
+fn apply_edited_config(draft: &Path, target: &Path) -> Result<()> {
+    toml::from_str::<BridleConfig>(&std::fs::read_to_string(draft)?)?;
+    std::fs::rename(draft, target)?;
+    Ok(())
+}
+
 fn set_profile_marker(value: &str) -> Result<()> {
     let enabled = match value.to_lowercase().as_str() {
         "true" | "1" | "yes" | "on" => true,
@@ -60,3 +214,88 @@ fn cleanup_all_marker_files() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileManager;
+    use tempfile::TempDir;
+
+    #[test]
+    fn setting_opencode_theme_keeps_jsonc_comments() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("opencode.jsonc");
+        std::fs::write(&path, "{\n  // my model\n  \"model\": \"gpt-4\"\n}\n").unwrap();
+
+        write_setting(&path, "theme", &"tokyonight".into()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\n  // my model\n  \"model\": \"gpt-4\",\n  \"theme\": \"tokyonight\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn setting_goose_value_writes_nested_yaml() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.yaml");
+        std::fs::write(&path, "GOOSE_MODEL: gpt-4o\nextensions: {}\n").unwrap();
+
+        write_setting(&path, "GOOSE_MODEL", &"claude-sonnet-4".into()).unwrap();
+        write_setting(&path, "extensions.developer.enabled", &true.into()).unwrap();
+
+        let doc: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["GOOSE_MODEL"], "claude-sonnet-4");
+        assert_eq!(doc["extensions"]["developer"]["enabled"], true);
+    }
+
+    #[test]
+    fn setting_targets_the_existing_opencode_file_and_detaches_hardlinks() {
+        let temp = TempDir::new().unwrap();
+        let names = harness_settings_files("opencode").unwrap();
+        assert_eq!(
+            settings_path(temp.path(), names),
+            temp.path().join("opencode.jsonc")
+        );
+
+        let path = temp.path().join("opencode.json");
+        let shared = temp.path().join("other-profile.json");
+        std::fs::write(&shared, "{}\n").unwrap();
+        std::fs::hard_link(&shared, &path).unwrap();
+        assert_eq!(settings_path(temp.path(), names), path);
+
+        ProfileManager::new(temp.path().join("profiles"))
+            .detach_hardlinks(&path)
+            .unwrap();
+        write_setting(&path, "theme", &"nord".into()).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&shared).unwrap(), "{}\n");
+        assert!(std::fs::read_to_string(&path).unwrap().contains("\"nord\""));
+        assert!(!temp.path().join("opencode.jsonc").exists());
+    }
+
+    #[test]
+    fn broken_edit_leaves_config_untouched() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("config.toml");
+        let draft = temp.path().join("config.toml.edit");
+        std::fs::write(&target, "profile_marker = true\n").unwrap();
+
+        std::fs::write(&draft, "profile_marker = [unclosed\n").unwrap();
+        assert!(apply_edited_config(&draft, &target).is_err());
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "profile_marker = true\n"
+        );
+        assert!(draft.exists());
+
+        std::fs::write(&draft, "profile_marker = false\n").unwrap();
+        apply_edited_config(&draft, &target).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "profile_marker = false\n"
+        );
+        assert!(!draft.exists());
+    }
+}
@@ -0,0 +1,223 @@
+//! `bridle doctor`: checks of bridle's own setup, with a result CI can gate on.
+
+use harness_locate::{Harness, HarnessKind};
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::profile::get_manager;
+use crate::config::{BridleConfig, ProfileManager, ProfileName};
+use crate::display::{CHECK_FAIL_SYMBOL, CHECK_MISSING_SYMBOL, CHECK_OK_SYMBOL, WARNING_SYMBOL};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    /// What the check looks at doesn't exist yet, which is not a failure.
+    Missing,
+}
+
+impl CheckStatus {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Ok => CHECK_OK_SYMBOL,
+            Self::Warn => WARNING_SYMBOL,
+            Self::Fail => CHECK_FAIL_SYMBOL,
+            Self::Missing => CHECK_MISSING_SYMBOL,
+        }
+    }
+}
+
+/// The outcome of one check.
+#[derive(Debug, Serialize)]
+pub struct DoctorCheck {
+    /// Stable identifier, e.g. `profiles-dir`.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub status: CheckStatus,
+    /// What was found; empty when there is nothing to add.
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    fn new(id: &'static str, title: &'static str, problems: Vec<String>, bad: CheckStatus) -> Self {
+        Self {
+            id,
+            title,
+            status: if problems.is_empty() {
+                CheckStatus::Ok
+            } else {
+                bad
+            },
+            detail: problems.join("; "),
+        }
+    }
+}
+
+fn run_checks() -> Vec<DoctorCheck> {
+    let config = BridleConfig::load();
+    let config_problems = config.as_ref().err().map(ToString::to_string);
+    let config = config.unwrap_or_default();
+
+    let manager = get_manager();
+    let profiles_dir = match &manager {
+        Ok(manager) => profiles_dir_check(manager),
+        Err(e) => profiles_dir_result(vec![e.to_string()]),
+    };
+
+    let mut missing = Vec::new();
+    if let Ok(manager) = &manager {
+        for kind in HarnessKind::ALL {
+            let harness = Harness::new(*kind);
+            if let Some(active) = config.active_profile_for(&harness.active_key())
+                && !ProfileName::new(active).is_ok_and(|n| manager.profile_exists(&harness, &n))
+            {
+                missing.push(format!(
+                    "{}: active profile '{}' does not exist",
+                    harness.id(),
+                    active
+                ));
+            }
+        }
+    }
+
+    vec![
+        DoctorCheck::new(
+            "config",
+            "bridle config loads",
+            config_problems.into_iter().collect(),
+            CheckStatus::Fail,
+        ),
+        profiles_dir,
+        DoctorCheck::new(
+            "active-profiles",
+            "Active profiles exist",
+            missing,
+            CheckStatus::Warn,
+        ),
+        DoctorCheck::new(
+            "shared-config-dirs",
+            "Harnesses have their own config dirs",
+            crate::harness::shared_config_dir_warnings(),
+            CheckStatus::Warn,
+        ),
+    ]
+}
+
+fn profiles_dir_result(problems: Vec<String>) -> DoctorCheck {
+    DoctorCheck::new(
+        "profiles-dir",
+        "Profiles directory is writable",
+        problems,
+        CheckStatus::Fail,
+    )
+}
+
+/// Probes the profiles dir for writes only when it exists, so running the
+/// check never creates it.
+fn profiles_dir_check(manager: &ProfileManager) -> DoctorCheck {
+    let dir = manager.profiles_dir();
+    if !dir.exists() {
+        return DoctorCheck {
+            status: CheckStatus::Missing,
+            detail: format!(
+                "{} does not exist yet; the first profile creates it",
+                dir.display()
+            ),
+            ..profiles_dir_result(Vec::new())
+        };
+    }
+    profiles_dir_result(
+        manager
+            .ensure_writable()
+            .err()
+            .map(|e| e.to_string())
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// Prints `checks` and fails with [`Error::DoctorFailed`] if any failed.
+fn report(checks: &[DoctorCheck], format: ResolvedFormat) -> Result<()> {
+    output_list(checks, format, |checks| {
+        for check in checks {
+            println!("{} {}", check.status.symbol(), check.title);
+            if !check.detail.is_empty() {
+                println!("    {}", check.detail);
+            }
+        }
+    })?;
+    match checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count()
+    {
+        0 => Ok(()),
+        failed => Err(Error::DoctorFailed(failed)),
+    }
+}
+
+pub fn run_doctor(format: ResolvedFormat) -> Result<()> {
+    report(&run_checks(), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_env::setup_test_env;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn failing_check_is_reported_as_fail_and_exits_non_zero() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let checks = [
+            DoctorCheck::new(
+                "config",
+                "bridle config loads",
+                Vec::new(),
+                CheckStatus::Fail,
+            ),
+            DoctorCheck::new(
+                "profiles-dir",
+                "Profiles directory is writable",
+                vec!["read-only file system".to_string()],
+                CheckStatus::Fail,
+            ),
+        ];
+
+        let path = temp.path().join("doctor.json");
+        crate::cli::output::set_output_file(Some(path.clone()));
+        let result = report(&checks, ResolvedFormat::Json);
+        crate::cli::output::set_output_file(None);
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::DoctorFailed(1)), "{err}");
+        assert_ne!(err.exit_code(), 0);
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json[0]["status"], "ok");
+        assert_eq!(json[1]["status"], "fail");
+        assert_eq!(json[1]["detail"], "read-only file system");
+    }
+
+    #[test]
+    fn missing_profiles_dir_is_reported_without_creating_it() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let dir = temp.path().join("profiles");
+        let manager = ProfileManager::new(dir.clone());
+
+        let check = profiles_dir_check(&manager);
+        assert_eq!(check.status, CheckStatus::Missing);
+        assert!(!dir.exists());
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(profiles_dir_check(&manager).status, CheckStatus::Ok);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+}
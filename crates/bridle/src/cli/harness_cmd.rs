@@ -0,0 +1,75 @@
+//! `bridle harness` subcommands for custom harness descriptors.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::error::{Error, Result};
+use crate::harness::{CustomHarness, HarnessConfig};
+
+#[derive(Serialize)]
+struct CustomHarnessEntry {
+    id: String,
+    config_dir: String,
+}
+
+/// Validates the descriptor at `file` and installs it as `harnesses/<id>.toml`.
+pub fn add_harness(file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(file)?;
+    let harness = CustomHarness::from_toml(&content)?;
+    let dir = CustomHarness::descriptors_dir()?;
+    let dest = dir.join(format!("{}.toml", harness.id()));
+    if dest.exists() {
+        return Err(Error::Config(format!(
+            "custom harness '{}' already exists at {}",
+            harness.id(),
+            dest.display()
+        )));
+    }
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(&dest, content)?;
+    println!("Added harness '{}' ({})", harness.id(), dest.display());
+    Ok(())
+}
+
+/// Lists the installed custom harnesses.
+pub fn list_harnesses(format: ResolvedFormat) -> Result<()> {
+    let entries: Vec<CustomHarnessEntry> =
+        CustomHarness::load_dir(&CustomHarness::descriptors_dir()?)?
+            .iter()
+            .map(|h| CustomHarnessEntry {
+                id: h.id().to_string(),
+                config_dir: h.descriptor().config_dir.display().to_string(),
+            })
+            .collect();
+
+    output_list(&entries, format, |entries| {
+        if entries.is_empty() {
+            println!("No custom harnesses defined");
+        }
+        for entry in entries {
+            println!("  {}  {}", entry.id, entry.config_dir);
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_env::setup_test_env;
+    use tempfile::TempDir;
+
+    #[test]
+    fn add_installs_descriptor_once() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let file = temp.path().join("acme.toml");
+        std::fs::write(&file, "id = \"acme\"\nconfig_dir = \"/opt/acme\"\n").unwrap();
+
+        add_harness(&file).unwrap();
+        assert_eq!(CustomHarness::find("acme").unwrap().unwrap().id(), "acme");
+        assert!(add_harness(&file).is_err());
+    }
+}
@@ -9,9 +9,10 @@ use dialoguer_multiselect::{GroupMultiSelect, ItemState};
 
 use harness_locate::{Harness, HarnessKind, Scope, Severity, validate_agent_for_harness};
 
-use crate::config::{BridleConfig, ProfileManager};
+use crate::cli::profile::{confirm, get_manager, resolve_harness};
+use crate::config::{BridleConfig, ProfileManager, ProfileName};
 use crate::harness::HarnessConfig;
-use crate::install::discovery::{DiscoveryError, discover_skills};
+use crate::install::discovery::{DiscoveryError, discover_skills, github_fetch_options};
 use crate::install::installer::{install_agent, install_command, install_skills};
 use crate::install::mcp_installer::{McpInstallOutcome, install_mcp};
 use crate::install::{
@@ -57,6 +58,27 @@ fn harness_supports_mcp(harness_id: &str) -> bool {
         .is_some()
 }
 
+/// Which kinds of component a harness can take.
+#[derive(Debug, Clone, Copy, Default)]
+struct HarnessSupport {
+    skills: bool,
+    agents: bool,
+    commands: bool,
+    mcp: bool,
+}
+
+impl HarnessSupport {
+    /// Asks the installed harness `harness_id`.
+    fn of(harness_id: &str) -> Self {
+        Self {
+            skills: harness_supports_skills(harness_id),
+            agents: harness_supports_agents(harness_id),
+            commands: harness_supports_commands(harness_id),
+            mcp: harness_supports_mcp(harness_id),
+        }
+    }
+}
+
 fn count_incompatible_agents(agents: &[AgentInfo], kind: HarnessKind) -> usize {
     agents
         .iter()
@@ -104,21 +126,190 @@ impl SelectedComponents {
     }
 }
 
-pub fn run(source: &str, force: bool) -> Result<()> {
+/// Resolves `--harness`/`--profile` to an install target. A missing profile
+/// is an error unless `create` is set; [`prepare_target`] then creates it,
+/// once the install is confirmed.
+fn explicit_target(harness_name: &str, profile_name: &str, create: bool) -> Result<InstallTarget> {
+    let harness = resolve_harness(harness_name)?;
+    let profile = ProfileName::new(profile_name)?;
+    if !create && !get_manager()?.profile_exists(&harness, &profile) {
+        return Err(eyre!(
+            "Profile '{}' not found for {} (pass --create to create it)",
+            profile,
+            harness.id()
+        ));
+    }
+    Ok(InstallTarget {
+        harness: harness.id().to_string(),
+        profile,
+    })
+}
+
+/// Creates `target` if it doesn't exist yet (`--create`), and unpacks it if
+/// it is a compressed profile, so the install writes into it instead of
+/// into a new directory that would shadow the archive.
+fn prepare_target(manager: &ProfileManager, target: &InstallTarget) -> Result<()> {
+    let harness = resolve_harness(&target.harness)?;
+    if !manager.profile_exists(&harness, &target.profile) {
+        manager.create_profile(&harness, &target.profile)?;
+    }
+    manager.expand_profile(&harness, &target.profile)?;
+    Ok(())
+}
+
+/// What an install will write into one target, leaving out what its harness
+/// can't take.
+#[derive(Debug, Default)]
+struct TargetPlan {
+    target: String,
+    skills: Vec<String>,
+    agents: Vec<String>,
+    commands: Vec<String>,
+    mcp_servers: Vec<String>,
+    warnings: Vec<String>,
+}
+
+fn plan_install(
+    selected: &SelectedComponents,
+    targets: &[InstallTarget],
+    support_of: &dyn Fn(&str) -> HarnessSupport,
+) -> Vec<TargetPlan> {
+    targets
+        .iter()
+        .map(|target| {
+            let harness = target.harness.as_str();
+            let kind = parse_harness_kind(harness);
+            let support = support_of(harness);
+            let mut plan = TargetPlan {
+                target: format!("{}/{}", harness, target.profile),
+                skills: selected.skills.iter().map(|s| s.name.clone()).collect(),
+                ..TargetPlan::default()
+            };
+
+            if selected.agents.is_empty() || support.agents {
+                plan.agents = selected.agents.iter().map(|a| a.name.clone()).collect();
+                let incompatible =
+                    kind.map_or(0, |k| count_incompatible_agents(&selected.agents, k));
+                if incompatible > 0 {
+                    plan.warnings
+                        .push(format!("{} agent(s) incompatible", incompatible));
+                }
+            } else {
+                plan.warnings
+                    .push(format!("{} agent(s) not supported", selected.agents.len()));
+            }
+
+            if selected.commands.is_empty() || support.commands {
+                plan.commands = selected.commands.iter().map(|c| c.name.clone()).collect();
+            } else {
+                plan.warnings.push(format!(
+                    "{} command(s) not supported",
+                    selected.commands.len()
+                ));
+            }
+
+            if !selected.mcp_servers.is_empty() && !support.mcp {
+                plan.warnings.push("MCP servers not supported".to_string());
+            } else {
+                let mut servers: Vec<String> = selected
+                    .mcp_servers
+                    .iter()
+                    .filter(|(_, server)| kind.is_none_or(|k| is_mcp_compatible(server, k)))
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                servers.sort();
+                plan.mcp_servers = servers;
+                let incompatible = kind.map_or_else(Vec::new, |k| {
+                    get_incompatible_mcp_names(&selected.mcp_servers, k)
+                });
+                if !incompatible.is_empty() {
+                    plan.warnings
+                        .push(format!("{} incompatible", incompatible.join(", ")));
+                }
+            }
+            plan
+        })
+        .collect()
+}
+
+/// Prints what is about to be installed where and asks to go ahead with
+/// `ask`, unless `yes` is set.
+fn confirm_install(
+    selected: &SelectedComponents,
+    plans: &[TargetPlan],
+    yes: bool,
+    ask: &dyn Fn(&str) -> crate::error::Result<bool>,
+) -> Result<bool> {
+    let mut totals = Vec::new();
+    for (count, what) in [
+        (selected.skills.len(), "skill(s)"),
+        (selected.agents.len(), "agent(s)"),
+        (selected.commands.len(), "command(s)"),
+        (selected.mcp_servers.len(), "MCP server(s)"),
+    ] {
+        if count > 0 {
+            totals.push(format!("{} {}", count, what));
+        }
+    }
+    let targets: Vec<&str> = plans.iter().map(|p| p.target.as_str()).collect();
+    eprintln!(
+        "\nAbout to install {} into {}:",
+        totals.join(", "),
+        targets.join(", ")
+    );
+    for plan in plans {
+        eprintln!("  {}", plan.target);
+        for (label, items) in [
+            ("skills", &plan.skills),
+            ("agents", &plan.agents),
+            ("commands", &plan.commands),
+            ("MCP servers", &plan.mcp_servers),
+        ] {
+            if !items.is_empty() {
+                eprintln!("    {}: {}", label, items.join(", "));
+            }
+        }
+        for warning in &plan.warnings {
+            eprintln!("    {}", format!("⚠ {}", warning).yellow());
+        }
+    }
+
+    if yes {
+        return Ok(true);
+    }
+    Ok(ask("Proceed?")?)
+}
+
+/// Installs components from `source`. With `target` as `(harness, profile)`,
+/// installs into exactly that profile instead of asking for targets. The
+/// summary of what will be written is confirmed first unless `yes` is set.
+pub fn run(
+    source: &str,
+    force: bool,
+    target: Option<(&str, &str)>,
+    create: bool,
+    yes: bool,
+) -> Result<()> {
     if !std::io::stdin().is_terminal() {
         return Err(eyre!(
             "Interactive mode requires a terminal. Use --help for non-interactive options."
         ));
     }
 
+    let explicit = target
+        .map(|(harness, profile)| explicit_target(harness, profile, create))
+        .transpose()?;
+
     let url = normalize_source(source);
 
     eprintln!("Discovering components from {}...", url);
 
-    let discovery = discover_skills(&url).map_err(|e| match e {
+    let timeout = BridleConfig::load().unwrap_or_default().fetch_timeout();
+    let discovery = discover_skills(&url, &github_fetch_options(timeout)).map_err(|e| match e {
         DiscoveryError::InvalidUrl(msg) => eyre!("Invalid URL: {}", msg),
         DiscoveryError::FetchError(e) => eyre!("Failed to fetch repository: {}", e),
         DiscoveryError::NoSkillsFound => eyre!("No installable components found in repository"),
+        e @ DiscoveryError::RateLimited { .. } => eyre!("{}", e),
     })?;
 
     // Build summary of what was found
@@ -155,17 +346,28 @@ pub fn run(source: &str, force: bool) -> Result<()> {
         return Ok(());
     }
 
-    let targets = select_targets(&selected)?;
+    let targets = match explicit {
+        Some(target) => vec![target],
+        None => select_targets(&selected)?,
+    };
 
     if targets.is_empty() {
         eprintln!("No targets selected");
         return Ok(());
     }
 
+    let plans = plan_install(&selected, &targets, &HarnessSupport::of);
+    if !confirm_install(&selected, &plans, yes, &confirm)? {
+        eprintln!("Nothing installed (pass --yes to skip the prompt)");
+        return Ok(());
+    }
+
     let options = InstallOptions { force };
+    let manager = get_manager()?;
 
     for target in &targets {
         eprintln!("\nInstalling to {}/{}...", target.harness, target.profile);
+        prepare_target(&manager, target)?;
 
         // Install skills
         if !selected.skills.is_empty() {
@@ -385,6 +587,83 @@ fn normalize_source(source: &str) -> String {
     }
 }
 
+/// How the profiles of harness `kind` are offered as targets for `selected`:
+/// disabled, with the reason, when the harness can take none of it, and with
+/// a warning when it can take only part of it.
+fn target_state(
+    selected: &SelectedComponents,
+    kind: HarnessKind,
+    support: HarnessSupport,
+) -> ItemState {
+    let supports_skills = support.skills;
+    let supports_agents = support.agents;
+    let supports_commands = support.commands;
+
+    let can_install_skills = supports_skills && !selected.skills.is_empty();
+    let can_install_agents = supports_agents && !selected.agents.is_empty();
+    let can_install_commands = supports_commands && !selected.commands.is_empty();
+    let incompatible_mcp_count = count_incompatible_mcps(&selected.mcp_servers, kind);
+    let can_install_mcp = selected.mcp_servers.len() > incompatible_mcp_count;
+
+    // Claude Code MCP support is in development (no global MCP config support)
+    let claude_mcp_in_dev = kind == HarnessKind::ClaudeCode && !selected.mcp_servers.is_empty();
+
+    let can_install_anything = can_install_skills
+        || can_install_agents
+        || can_install_commands
+        || (can_install_mcp && !claude_mcp_in_dev);
+
+    let mut skipped: Vec<&str> = Vec::new();
+    if !selected.skills.is_empty() && !supports_skills {
+        skipped.push("skills");
+    }
+    if !selected.agents.is_empty() && !supports_agents {
+        skipped.push("agents");
+    }
+    if !selected.commands.is_empty() && !supports_commands {
+        skipped.push("commands");
+    }
+
+    if claude_mcp_in_dev {
+        return ItemState::Disabled {
+            reason: "MCP: in development".into(),
+        };
+    }
+    if !can_install_anything {
+        let reason = if skipped.is_empty() {
+            "no selected components supported".to_string()
+        } else {
+            format!("{} not supported", skipped.join(", "))
+        };
+        return ItemState::Disabled { reason };
+    }
+
+    let mut warnings: Vec<String> = Vec::new();
+    if !skipped.is_empty() {
+        warnings.push(format!("{} not supported", skipped.join(", ")));
+    }
+    if can_install_agents {
+        let incompatible_agent_count = count_incompatible_agents(&selected.agents, kind);
+        if incompatible_agent_count > 0 {
+            warnings.push(format!(
+                "{} agent(s) incompatible",
+                incompatible_agent_count
+            ));
+        }
+    }
+    if incompatible_mcp_count > 0 {
+        let names = get_incompatible_mcp_names(&selected.mcp_servers, kind);
+        warnings.push(format!("{} incompatible", names.join(", ")));
+    }
+    if warnings.is_empty() {
+        ItemState::Normal
+    } else {
+        ItemState::Warning {
+            message: warnings.join("; "),
+        }
+    }
+}
+
 fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
     let config = BridleConfig::load()?;
     let profiles_dir = BridleConfig::profiles_dir()?;
@@ -416,39 +695,7 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
         }
 
         let active_profile = config.active_profile_for(harness_id);
-        let supports_skills = harness_supports_skills(harness_id);
-        let supports_agents = harness_supports_agents(harness_id);
-        let supports_commands = harness_supports_commands(harness_id);
-
-        let can_install_skills = supports_skills && !selected.skills.is_empty();
-        let can_install_agents = supports_agents && !selected.agents.is_empty();
-        let can_install_commands = supports_commands && !selected.commands.is_empty();
-        let incompatible_mcp_count = count_incompatible_mcps(&selected.mcp_servers, *kind);
-        let compatible_mcp_count = selected.mcp_servers.len() - incompatible_mcp_count;
-        let can_install_mcp = compatible_mcp_count > 0;
-
-        // Claude Code MCP support is in development (no global MCP config support)
-        let claude_mcp_in_dev =
-            *kind == HarnessKind::ClaudeCode && !selected.mcp_servers.is_empty();
-
-        let can_install_anything = can_install_skills
-            || can_install_agents
-            || can_install_commands
-            || (can_install_mcp && !claude_mcp_in_dev);
-
-        let mut skipped: Vec<&str> = Vec::new();
-        if !selected.agents.is_empty() && !supports_agents {
-            skipped.push("agents");
-        }
-        if !selected.commands.is_empty() && !supports_commands {
-            skipped.push("commands");
-        }
-
-        let incompatible_agent_count = if supports_agents && !selected.agents.is_empty() {
-            count_incompatible_agents(&selected.agents, *kind)
-        } else {
-            0
-        };
+        let state = target_state(selected, *kind, HarnessSupport::of(harness_id));
 
         let mut items_with_states = Vec::new();
         let mut targets = Vec::new();
@@ -462,42 +709,9 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
                 profile.to_string()
             };
 
-            let state = if claude_mcp_in_dev {
-                ItemState::Disabled {
-                    reason: "MCP: in development".into(),
-                }
-            } else if !can_install_anything {
-                ItemState::Disabled {
-                    reason: "no selected components supported".into(),
-                }
-            } else if !skipped.is_empty()
-                || incompatible_agent_count > 0
-                || incompatible_mcp_count > 0
-            {
-                let mut warnings: Vec<String> = Vec::new();
-                if !skipped.is_empty() {
-                    warnings.push(format!("{} not supported", skipped.join(", ")));
-                }
-                if incompatible_agent_count > 0 {
-                    warnings.push(format!(
-                        "{} agent(s) incompatible",
-                        incompatible_agent_count
-                    ));
-                }
-                if incompatible_mcp_count > 0 {
-                    let names = get_incompatible_mcp_names(&selected.mcp_servers, *kind);
-                    warnings.push(format!("{} incompatible", names.join(", ")));
-                }
-                ItemState::Warning {
-                    message: warnings.join("; "),
-                }
-            } else {
-                ItemState::Normal
-            };
-
             let default_selected = is_active && !matches!(state, ItemState::Disabled { .. });
 
-            items_with_states.push((label, state));
+            items_with_states.push((label, state.clone()));
             targets.push(InstallTarget {
                 harness: harness_id.to_string(),
                 profile,
@@ -505,7 +719,9 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
             defaults.push(default_selected);
         }
 
-        let harness_warning = if incompatible_mcp_count > 0 {
+        let harness_warning = if let ItemState::Disabled { reason } = &state {
+            Some(reason.clone())
+        } else if count_incompatible_mcps(&selected.mcp_servers, *kind) > 0 {
             let names = get_incompatible_mcp_names(&selected.mcp_servers, *kind);
             Some(format!("{} incompatible", names.join(", ")))
         } else {
@@ -561,6 +777,158 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::install::installer::install_skills;
+    use crate::test_env::setup_test_env;
+    use tempfile::TempDir;
+
+    #[test]
+    fn agents_only_selection_disables_harnesses_without_agents() {
+        let selected = SelectedComponents {
+            skills: Vec::new(),
+            mcp_servers: HashMap::new(),
+            agents: vec![AgentInfo {
+                name: "reviewer".to_string(),
+                description: Some("Reviews code".to_string()),
+                path: "agents/reviewer.md".to_string(),
+                content: "---\ndescription: Reviews code\n---\nReview the diff.\n".to_string(),
+            }],
+            commands: Vec::new(),
+        };
+
+        let without_agents = HarnessSupport {
+            skills: true,
+            ..HarnessSupport::default()
+        };
+        let with_agents = HarnessSupport {
+            agents: true,
+            ..without_agents
+        };
+        assert_eq!(
+            target_state(&selected, HarnessKind::Goose, without_agents),
+            ItemState::Disabled {
+                reason: "agents not supported".into()
+            }
+        );
+        assert!(!matches!(
+            target_state(&selected, HarnessKind::ClaudeCode, with_agents),
+            ItemState::Disabled { .. }
+        ));
+    }
+
+    #[test]
+    fn explicit_inactive_profile_gets_install_but_live_config_does_not() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let mut config = BridleConfig::load().unwrap_or_default();
+        config.set_active_profile("opencode", "home");
+        config.save().unwrap();
+
+        assert!(explicit_target("opencode", "work", false).is_err());
+        let target = explicit_target("oc", "work", true).unwrap();
+        assert_eq!(target.harness, "opencode");
+        // Nothing is created until the install goes ahead.
+        let manager = get_manager().unwrap();
+        let harness = resolve_harness("opencode").unwrap();
+        assert!(!manager.profile_exists(&harness, &target.profile));
+        prepare_target(&manager, &target).unwrap();
+
+        let skill = SkillInfo {
+            name: "explicit-target-skill".to_string(),
+            description: None,
+            path: "skills/explicit-target-skill/SKILL.md".to_string(),
+            content: "# Skill".to_string(),
+        };
+        let report = install_skills(&[skill], &target, &InstallOptions::default());
+        assert_eq!(report.installed.len(), 1, "{:?}", report.errors);
+        let installed = &report.installed[0];
+        assert!(installed.profile_path.starts_with(temp.path()));
+        assert!(installed.profile_path.exists());
+        assert!(installed.harness_path.is_none());
+    }
+
+    #[test]
+    fn install_into_a_compressed_profile_expands_it() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("opencode").unwrap();
+        let work = ProfileName::new("work").unwrap();
+        let manager = get_manager().unwrap();
+        let path = manager.create_profile(&harness, &work).unwrap();
+        std::fs::write(path.join("opencode.jsonc"), "{}").unwrap();
+        let archive = manager.compress_profile(&harness, &work).unwrap();
+
+        let target = explicit_target("opencode", "work", true).unwrap();
+        prepare_target(&manager, &target).unwrap();
+        let skill = SkillInfo {
+            name: "compressed-target-skill".to_string(),
+            description: None,
+            path: "skills/compressed-target-skill/SKILL.md".to_string(),
+            content: "# Skill".to_string(),
+        };
+        let report = install_skills(&[skill], &target, &InstallOptions::default());
+
+        assert_eq!(report.installed.len(), 1, "{:?}", report.errors);
+        assert!(!archive.exists());
+        assert!(path.join("opencode.jsonc").is_file());
+        assert!(report.installed[0].profile_path.starts_with(&path));
+    }
+
+    #[test]
+    fn summary_counts_match_selection_and_yes_skips_the_prompt() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let skill = |name: &str| SkillInfo {
+            name: name.to_string(),
+            description: None,
+            path: format!("skills/{name}/SKILL.md"),
+            content: "# Skill".to_string(),
+        };
+        let server = McpServer::Stdio(harness_locate::StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let selected = SelectedComponents {
+            skills: vec![skill("review"), skill("plan")],
+            mcp_servers: HashMap::from([("files".to_string(), server)]),
+            agents: vec![],
+            commands: vec![],
+        };
+        let targets: Vec<InstallTarget> = ["work", "home"]
+            .into_iter()
+            .map(|profile| InstallTarget {
+                harness: "claude-code".to_string(),
+                profile: ProfileName::new(profile).unwrap(),
+            })
+            .collect();
+
+        let claude = |_: &str| HarnessSupport {
+            skills: true,
+            agents: true,
+            commands: true,
+            mcp: true,
+        };
+        let plans = plan_install(&selected, &targets, &claude);
+
+        assert_eq!(plans.len(), 2);
+        assert_eq!(plans[0].target, "claude-code/work");
+        assert_eq!(plans[0].skills, ["review", "plan"]);
+        assert_eq!(plans[0].mcp_servers, ["files"]);
+        assert!(plans[0].warnings.is_empty(), "{:?}", plans[0].warnings);
+        let asked = std::cell::Cell::new(false);
+        let decline = |prompt: &str| {
+            assert_eq!(prompt, "Proceed?");
+            asked.set(true);
+            Ok(false)
+        };
+        assert!(!confirm_install(&selected, &plans, false, &decline).unwrap());
+        assert!(asked.get());
+        let never = |_: &str| panic!("--yes must not ask");
+        assert!(confirm_install(&selected, &plans, true, &never).unwrap());
+    }
 
     #[test]
     fn normalize_source_handles_shorthand() {
@@ -0,0 +1,173 @@
+//! `bridle mcp` subcommands for inspecting a profile's MCP servers.
+
+use harness_locate::Harness;
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output, output_list};
+use crate::cli::profile::{get_manager, resolve_harness};
+use crate::config::{BridleConfig, McpServerInfo, ProfileName};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+#[derive(Serialize)]
+struct McpListEntry {
+    name: String,
+    enabled: bool,
+    transport: &'static str,
+}
+
+#[derive(Serialize)]
+struct McpShowEntry<'a> {
+    #[serde(flatten)]
+    server: &'a McpServerInfo,
+    transport: &'static str,
+}
+
+/// The servers of `profile`, or of the active profile when it's `None`.
+fn profile_servers(
+    harness: &Harness,
+    profile: Option<&str>,
+) -> Result<(ProfileName, Vec<McpServerInfo>)> {
+    let profile = match profile {
+        Some(name) => name.to_string(),
+        None => BridleConfig::load()?
+            .active_profile_for(&harness.active_key())
+            .map(String::from)
+            .ok_or(Error::NoActiveProfile)?,
+    };
+    let name = ProfileName::new(&profile).map_err(|_| Error::InvalidProfileName(profile))?;
+    let (servers, warnings) = get_manager()?.profile_mcp_servers(harness, &name)?;
+    for warning in warnings {
+        crate::diagnostics::warn(format!("Warning: {warning}"));
+    }
+    Ok((name, servers))
+}
+
+/// Lists the MCP servers of a profile with their enabled state and transport.
+pub fn list_mcp_servers(
+    harness_name: &str,
+    profile: Option<&str>,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let (name, servers) = profile_servers(&harness, profile)?;
+    let entries: Vec<McpListEntry> = servers
+        .iter()
+        .map(|s| McpListEntry {
+            name: s.name.clone(),
+            enabled: s.enabled,
+            transport: s.transport(),
+        })
+        .collect();
+
+    output_list(&entries, format, |entries| {
+        if entries.is_empty() {
+            println!("No MCP servers in {}/{}", harness_name, name);
+        }
+        for entry in entries {
+            let state = if entry.enabled { "" } else { " (disabled)" };
+            println!("  {} [{}]{}", entry.name, entry.transport, state);
+        }
+    })
+}
+
+/// Shows one MCP server of a profile in full. Only the names of its
+/// environment variables are shown, never their values.
+pub fn show_mcp_server(
+    harness_name: &str,
+    server_name: &str,
+    profile: Option<&str>,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let (name, servers) = profile_servers(&harness, profile)?;
+    let server = servers
+        .iter()
+        .find(|s| s.name == server_name)
+        .ok_or_else(|| Error::McpServerNotFound(format!("{} in profile {}", server_name, name)))?;
+    let entry = McpShowEntry {
+        server,
+        transport: server.transport(),
+    };
+
+    output(&entry, format, |entry| {
+        let server = entry.server;
+        println!("{}", server.name);
+        println!("  enabled: {}", if server.enabled { "yes" } else { "no" });
+        println!("  transport: {}", entry.transport);
+        if let Some(command) = &server.command {
+            println!("  command: {}", command);
+        }
+        if let Some(args) = server.args.as_ref().filter(|a| !a.is_empty()) {
+            println!("  args: {}", args.join(" "));
+        }
+        if let Some(url) = &server.url {
+            println!("  url: {}", url);
+        }
+        if !server.env_keys.is_empty() {
+            println!("  env: {}", server.env_keys.join(", "));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_env::setup_test_env;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn work_profile() {
+        let harness = resolve_harness("claude-code").unwrap();
+        let name = ProfileName::new("work").unwrap();
+        let manager = get_manager().unwrap();
+        manager.create_profile(&harness, &name).unwrap();
+        fs::write(
+            manager.profile_path(&harness, &name).join(".mcp.json"),
+            r#"{"mcpServers": {
+                "search": {"type": "http", "url": "https://example.com/mcp"},
+                "files": {"command": "npx", "args": ["files"], "env": {"ROOT": "/"}, "disabled": true}
+            }}"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn list_reports_name_enabled_and_transport() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        work_profile();
+
+        let path = temp.path().join("mcp.json");
+        crate::cli::output::set_output_file(Some(path.clone()));
+        let listed = list_mcp_servers("claude-code", Some("work"), ResolvedFormat::Json);
+        crate::cli::output::set_output_file(None);
+        listed.unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"name": "files", "enabled": false, "transport": "stdio"},
+                {"name": "search", "enabled": true, "transport": "http"},
+            ])
+        );
+    }
+
+    #[test]
+    fn show_unknown_server_is_not_found() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        work_profile();
+
+        let err =
+            show_mcp_server("claude-code", "nope", Some("work"), ResolvedFormat::Json).unwrap_err();
+        assert!(matches!(err, Error::McpServerNotFound(_)), "{err}");
+        assert!(err.to_string().contains("nope in profile work"), "{err}");
+        assert_eq!(
+            err.exit_code(),
+            crate::error::exit_code::MCP_SERVER_NOT_FOUND
+        );
+    }
+}
@@ -1,13 +1,20 @@
 //! CLI module for bridle.
 
+pub mod backup_cmd;
 mod commands;
 pub mod config_cmd;
+pub mod doctor;
+pub mod harness_cmd;
 pub mod init;
 pub mod install;
+pub mod mcp_cmd;
 pub mod output;
 pub mod profile;
 pub mod status;
 pub mod tui;
 pub mod uninstall;
 
-pub use commands::{Commands, ConfigCommands, ProfileCommands};
+pub use commands::{
+    BackupCommands, Commands, ConfigCommands, HarnessCommands, McpCommands, ProfileCommands,
+    ScopeArg,
+};
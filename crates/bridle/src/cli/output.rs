@@ -1,10 +1,26 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
 use clap::ValueEnum;
 use serde::Serialize;
 
+use crate::error::{Error, Result};
+
+thread_local! {
+    static OUTPUT_FILE: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Sends json/yaml results to `path` instead of stdout (`--output-file`).
+/// `None` restores stdout.
+pub fn set_output_file(path: Option<PathBuf>) {
+    OUTPUT_FILE.with(|f| *f.borrow_mut() = path);
+}
+
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum OutputFormat {
     Text,
     Json,
+    Yaml,
     #[default]
     Auto,
 }
@@ -13,6 +29,7 @@ pub enum OutputFormat {
 pub enum ResolvedFormat {
     Text,
     Json,
+    Yaml,
 }
 
 impl OutputFormat {
@@ -20,42 +37,99 @@ impl OutputFormat {
         match self {
             Self::Text | Self::Auto => ResolvedFormat::Text,
             Self::Json => ResolvedFormat::Json,
+            Self::Yaml => ResolvedFormat::Yaml,
+        }
+    }
+}
+
+/// Serializes `data` for a machine-readable `format`, or `None` for text.
+pub(crate) fn serialize<T: Serialize + ?Sized>(data: &T, format: ResolvedFormat) -> Option<String> {
+    match format {
+        ResolvedFormat::Json => {
+            Some(serde_json::to_string(data).expect("serialization should not fail"))
+        }
+        ResolvedFormat::Yaml => Some(
+            serde_yaml::to_string(data)
+                .expect("serialization should not fail")
+                .trim_end()
+                .to_string(),
+        ),
+        ResolvedFormat::Text => None,
+    }
+}
+
+/// Prints `serialized`, or writes it to the `--output-file` if one is set.
+fn emit(serialized: &str) -> Result<()> {
+    match OUTPUT_FILE.with(|f| f.borrow().clone()) {
+        Some(path) => write_atomically(&path, &format!("{serialized}\n")),
+        None => {
+            println!("{serialized}");
+            Ok(())
         }
     }
 }
 
-pub fn output<T, F>(data: &T, format: ResolvedFormat, text_fn: F)
+/// Writes `contents` to a temporary sibling of `path` and renames it into
+/// place, so readers never see a half-written file.
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::InvalidValue(format!("not a file path: {}", path.display())))?;
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(format!(".tmp-{}", std::process::id()));
+    let tmp = parent.join(tmp_name);
+    std::fs::write(&tmp, contents)?;
+    if let Err(e) = std::fs::rename(&tmp, path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Prints nothing but the absolute form of `path` (`--print-path`), for
+/// shell use such as `cd "$(bridle profile show opencode work --print-path)"`.
+pub fn print_path(path: &Path) -> Result<()> {
+    emit(&std::path::absolute(path)?.display().to_string())
+}
+
+/// Prints `lines` as plain text regardless of `--format`, honouring
+/// `--output-file`.
+pub fn print_lines(lines: &[String]) -> Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    emit(&lines.join("\n"))
+}
+
+pub fn output<T, F>(data: &T, format: ResolvedFormat, text_fn: F) -> Result<()>
 where
     T: Serialize,
     F: FnOnce(&T),
 {
-    match format {
-        ResolvedFormat::Json => {
-            println!(
-                "{}",
-                serde_json::to_string(data).expect("serialization should not fail")
-            );
-        }
-        ResolvedFormat::Text => {
+    match serialize(data, format) {
+        Some(serialized) => emit(&serialized),
+        None => {
             text_fn(data);
+            Ok(())
         }
     }
 }
 
-pub fn output_list<T, F>(items: &[T], format: ResolvedFormat, text_fn: F)
+pub fn output_list<T, F>(items: &[T], format: ResolvedFormat, text_fn: F) -> Result<()>
 where
     T: Serialize,
     F: FnOnce(&[T]),
 {
-    match format {
-        ResolvedFormat::Json => {
-            println!(
-                "{}",
-                serde_json::to_string(items).expect("serialization should not fail")
-            );
-        }
-        ResolvedFormat::Text => {
+    match serialize(items, format) {
+        Some(serialized) => emit(&serialized),
+        None => {
             text_fn(items);
+            Ok(())
         }
     }
 }
@@ -1,11 +1,25 @@
-use harness_locate::{Harness, HarnessKind, InstallationStatus};
+use std::io::{IsTerminal, Write};
+use std::path::{Component, Path, PathBuf};
+
+use dialoguer_multiselect::Confirm;
+use dialoguer_multiselect::theme::ColorfulTheme;
+use harness_locate::{Harness, HarnessKind, InstallationStatus, Scope};
 use serde::Serialize;
 
-use crate::cli::output::{ResolvedFormat, output, output_list};
-use crate::config::{BridleConfig, ProfileManager, ProfileName};
-use crate::display::{ProfileNode, SectionKind, nodes_to_text, profile_to_nodes};
+use crate::cli::ScopeArg;
+use crate::cli::output::{self, ResolvedFormat, output, output_list};
+use crate::config::profile_history::{self, HistoryEvent};
+use crate::config::{
+    BridleConfig, CaptureFilter, EditSnapshot, ProfileInfo, ProfileManager, ProfileMeta,
+    ProfileName, ProfileTemplate, Severity,
+};
+use crate::display::{
+    ProfileNode, ProfileSection, SectionKind, TextStyle, filter_nodes, nodes_to_text,
+    profile_changes_to_nodes, profile_summary_line, profile_to_nodes,
+};
 use crate::error::{Error, Result};
-use crate::harness::HarnessConfig;
+use crate::harness::{CustomHarness, HarnessConfig, ScopedHarness};
+use crate::install::installer::validate_component_name;
 
 #[derive(Serialize)]
 struct ProfileListEntry {
@@ -14,6 +28,55 @@ struct ProfileListEntry {
     is_active: bool,
 }
 
+#[derive(Debug, PartialEq, Serialize)]
+struct ActiveProfileEntry {
+    harness: String,
+    active: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ResourceCounts {
+    mcp_servers: usize,
+    skills: usize,
+    agents: usize,
+    commands: usize,
+}
+
+impl ResourceCounts {
+    fn from_info(info: &ProfileInfo) -> Self {
+        Self {
+            mcp_servers: info.mcp_servers.len(),
+            skills: info.skills.items.len(),
+            agents: info.agents.as_ref().map_or(0, |a| a.items.len()),
+            commands: info.commands.items.len(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ProfileCountsEntry {
+    name: String,
+    #[serde(flatten)]
+    counts: ResourceCounts,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MostEntries {
+    mcp_servers: Option<String>,
+    skills: Option<String>,
+    agents: Option<String>,
+    commands: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProfileStats {
+    harness_id: String,
+    profile_count: usize,
+    profiles: Vec<ProfileCountsEntry>,
+    totals: ResourceCounts,
+    most: MostEntries,
+}
+
 pub(crate) fn resolve_harness(name: &str) -> Result<Harness> {
     let kind = match name {
         "claude-code" | "claude" | "cc" => HarnessKind::ClaudeCode,
@@ -28,29 +91,112 @@ pub(crate) fn resolve_harness(name: &str) -> Result<Harness> {
     Ok(Harness::new(kind))
 }
 
-fn get_manager() -> Result<ProfileManager> {
-    let profiles_dir = BridleConfig::profiles_dir()?;
-    Ok(ProfileManager::new(profiles_dir))
+/// Resolves a built-in harness whose live config is looked up for `scope`.
+fn resolve_scoped_harness(name: &str, scope: ScopeArg) -> Result<ScopedHarness> {
+    let kind = resolve_harness(name)?.kind();
+    let scope = match scope {
+        ScopeArg::Global => Scope::Global,
+        ScopeArg::Project => Scope::Project(std::env::current_dir()?),
+    };
+    Ok(ScopedHarness::new(kind, scope))
 }
 
-pub fn list_profiles(harness_name: &str, format: ResolvedFormat) -> Result<()> {
-    let harness = resolve_harness(harness_name)?;
+/// A built-in harness, or one defined by a descriptor in `harnesses/`.
+enum AnyHarness {
+    Builtin(Harness),
+    Custom(Box<CustomHarness>),
+}
+
+impl AnyHarness {
+    fn config(&self) -> &dyn HarnessConfig {
+        match self {
+            Self::Builtin(h) => h,
+            Self::Custom(h) => h.as_ref(),
+        }
+    }
+}
+
+/// Like [`resolve_harness`], falling back to custom harness descriptors.
+fn resolve_any_harness(name: &str) -> Result<AnyHarness> {
+    match resolve_harness(name) {
+        Err(Error::UnknownHarness(_)) => CustomHarness::find(name)?
+            .map(|h| AnyHarness::Custom(Box::new(h)))
+            .ok_or_else(|| Error::UnknownHarness(name.to_string())),
+        other => other.map(AnyHarness::Builtin),
+    }
+}
+
+/// Every profile of `harness` with its details; see
+/// [`ProfileManager::show_profiles`].
+fn show_all_profiles(harness: &AnyHarness, keep_going: bool) -> Result<Vec<ProfileInfo>> {
     let manager = get_manager()?;
+    match harness {
+        AnyHarness::Builtin(h) => manager.show_profiles(h, keep_going),
+        AnyHarness::Custom(h) => manager.show_custom_profiles(h, keep_going),
+    }
+}
 
-    let active_profile: Option<String> = BridleConfig::load()
-        .ok()
-        .and_then(|c| c.active_profile_for(harness.id()).map(|s| s.to_string()));
+/// The profile named `input`, or else the one profile whose name starts
+/// with it, so `work` can be typed as `wo`.
+///
+/// # Errors
+/// [`Error::AmbiguousProfile`] if several profiles share the prefix, and
+/// [`Error::ProfileNotFound`] if none does.
+fn resolve_profile_name(
+    manager: &ProfileManager,
+    harness: &dyn HarnessConfig,
+    input: &str,
+) -> Result<ProfileName> {
+    let name = ProfileName::new(input).map_err(|_| Error::InvalidProfileName(input.to_string()))?;
+    if manager.profile_exists(harness, &name) {
+        return Ok(name);
+    }
+    let mut matches: Vec<ProfileName> = manager
+        .list_profiles(harness)?
+        .into_iter()
+        .filter(|p| p.as_str().starts_with(input))
+        .collect();
+    match matches.len() {
+        0 => Err(Error::ProfileNotFound(input.to_string())),
+        1 => Ok(matches.remove(0)),
+        _ => Err(Error::AmbiguousProfile {
+            prefix: input.to_string(),
+            matches: matches.iter().map(ToString::to_string).collect(),
+        }),
+    }
+}
 
-    let profiles = manager.list_profiles(&harness)?;
-    let entries: Vec<ProfileListEntry> = profiles
-        .iter()
-        .map(|p| ProfileListEntry {
-            name: p.to_string(),
+pub(crate) fn get_manager() -> Result<ProfileManager> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let manager = ProfileManager::new(profiles_dir);
+    Ok(match BridleConfig::load() {
+        Ok(config) => manager.with_fs_retries(config.profile.fs_retries),
+        Err(_) => manager,
+    })
+}
+
+/// Settles a profile switch that an earlier run didn't finish, so the live
+/// config isn't left half-replaced. Failures are reported, not fatal.
+pub fn recover_interrupted_switches() {
+    if let Err(e) = get_manager().and_then(|m| m.recover_interrupted_switch()) {
+        eprintln!("Warning: could not recover an interrupted profile switch: {e}");
+    }
+}
+
+/// Names and the active flag only, so nothing in a profile is read;
+/// `--compact` loads each profile for its model and servers.
+pub fn list_profiles(harness_name: &str, format: ResolvedFormat) -> Result<()> {
+    let resolved = resolve_any_harness(harness_name)?;
+    let harness = resolved.config();
+    let config = BridleConfig::load().unwrap_or_default();
+    let active = config.active_profile_for(&harness.active_key());
+    let entries: Vec<ProfileListEntry> = get_manager()?
+        .list_profiles(harness)?
+        .into_iter()
+        .map(|name| ProfileListEntry {
+            is_active: active == Some(name.as_str()),
+            name: name.to_string(),
             harness_id: harness.id().to_string(),
-            is_active: active_profile
-                .as_ref()
-                .map(|a| a == &p.to_string())
-                .unwrap_or(false),
         })
         .collect();
 
@@ -64,23 +210,124 @@ pub fn list_profiles(harness_name: &str, format: ResolvedFormat) -> Result<()> {
                 println!("  {}{}", entry.name, active);
             }
         }
-    });
+    })?;
     Ok(())
 }
 
-pub fn show_profile(harness_name: &str, profile_name: &str, format: ResolvedFormat) -> Result<()> {
-    let harness = resolve_harness(harness_name)?;
-    let name = ProfileName::new(profile_name)
-        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+/// `profile list --compact`: one `name\tmodel\tmcp_count\tactive` line per
+/// profile for `cut` and `awk`. An unset model is `-`, and the last field is
+/// `active` or `-`.
+pub fn list_profiles_compact(harness_name: &str, keep_going: bool) -> Result<()> {
+    let resolved = resolve_any_harness(harness_name)?;
+    let lines: Vec<String> = show_all_profiles(&resolved, keep_going)?
+        .iter()
+        .map(|info| {
+            format!(
+                "{}\t{}\t{}\t{}",
+                info.name,
+                info.model.as_deref().unwrap_or("-"),
+                info.mcp_servers.len(),
+                if info.is_active { "active" } else { "-" }
+            )
+        })
+        .collect();
+    output::print_lines(&lines)
+}
+
+pub fn show_profile(
+    harness_name: &str,
+    profile_name: &str,
+    resources: &[ProfileSection],
+    scope: ScopeArg,
+    style: TextStyle,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let resolved = resolve_any_harness(harness_name)?;
     let manager = get_manager()?;
+    let name = resolve_profile_name(&manager, resolved.config(), profile_name)?;
 
-    let info = manager.show_profile(&harness, &name)?;
-    output(&info, format, |info| print_profile_text(info, &harness));
+    let mut info = match &resolved {
+        AnyHarness::Builtin(_) => {
+            let scoped = resolve_scoped_harness(harness_name, scope)?;
+            manager.show_scoped_profile(&scoped, &name)?
+        }
+        AnyHarness::Custom(_) if scope == ScopeArg::Project => {
+            return Err(Error::InvalidValue(format!(
+                "{harness_name} has no project-scoped config"
+            )));
+        }
+        AnyHarness::Custom(h) => manager.show_custom_profile(h, &name)?,
+    };
+    let harness = resolved.config();
+    // Only probe the binary when there is a recorded version to compare.
+    if info.captured_with.is_some()
+        && let Some(warning) = info.version_mismatch(harness.version().as_deref())
+    {
+        info.extraction_warnings.push(warning);
+    }
+    if resources.is_empty() {
+        output(&info, format, |info| {
+            print_profile_text(info, harness, resources, style)
+        })?;
+    } else {
+        let json = filter_profile_json(&info, resources)?;
+        output(&json, format, |_| {
+            print_profile_text(&info, harness, resources, style)
+        })?;
+    }
+
+    if !info.extraction_errors.is_empty() {
+        return Err(Error::ExtractionErrors(info.name));
+    }
     Ok(())
 }
 
-fn print_profile_text(info: &crate::config::ProfileInfo, harness: &harness_locate::Harness) {
-    let mut nodes = profile_to_nodes(info);
+/// `profile show --print-path`: the profile's directory, or its archive if
+/// it is compressed, without extracting anything from it.
+pub fn print_profile_path(harness_name: &str, profile_name: &str) -> Result<()> {
+    let resolved = resolve_any_harness(harness_name)?;
+    let harness = resolved.config();
+    let manager = get_manager()?;
+    let name = resolve_profile_name(&manager, harness, profile_name)?;
+
+    if manager.is_compressed(harness, &name) {
+        output::print_path(&manager.compressed_path(harness, &name))
+    } else {
+        output::print_path(&manager.profile_path(harness, &name))
+    }
+}
+
+/// Serialized profile fields that are kept regardless of `--resource`.
+const PROFILE_JSON_HEADER: &[&str] = &[
+    "name",
+    "harness_id",
+    "is_active",
+    "path",
+    "extraction_errors",
+    "extraction_warnings",
+];
+
+fn filter_profile_json(
+    info: &ProfileInfo,
+    resources: &[ProfileSection],
+) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(info)?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.retain(|key, _| {
+            PROFILE_JSON_HEADER.contains(&key.as_str())
+                || resources.iter().any(|r| r.json_key() == key)
+        });
+    }
+    Ok(value)
+}
+
+fn print_profile_text(
+    info: &crate::config::ProfileInfo,
+    harness: &dyn HarnessConfig,
+    resources: &[ProfileSection],
+    style: TextStyle,
+) {
+    let mut nodes = filter_nodes(profile_to_nodes(info), resources);
 
     if info.is_active {
         let marker_exists = harness
@@ -96,77 +343,259 @@ fn print_profile_text(info: &crate::config::ProfileInfo, harness: &harness_locat
         }
     }
 
-    print!("{}", nodes_to_text(&nodes));
+    if style == TextStyle::Tree {
+        println!("{}", profile_summary_line(info));
+    }
+    print!("{}", nodes_to_text(&nodes, style));
 }
 
-pub fn create_profile(harness_name: &str, profile_name: &str) -> Result<()> {
-    let harness = resolve_harness(harness_name)?;
-
+/// Fails with [`Error::HarnessNotInstalled`] unless both binary and config exist.
+fn require_installed(harness: &Harness) -> Result<()> {
     let status = harness
         .installation_status()
         .unwrap_or(InstallationStatus::NotInstalled);
     match status {
-        InstallationStatus::FullyInstalled { .. } => {}
+        InstallationStatus::FullyInstalled { .. } => Ok(()),
         _ => {
             eprintln!("Harness is not installed/configured:\n");
             let lines = crate::harness::get_empty_state_message(harness.kind(), status, false);
             for line in lines {
                 eprintln!("{}", line);
             }
-            return Err(Error::HarnessNotInstalled);
+            Err(Error::HarnessNotInstalled)
         }
     }
+}
+
+pub fn create_profile(harness_name: &str, name: &ProfileName, print_path: bool) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    require_installed(&harness)?;
 
-    let name = ProfileName::new(profile_name)
-        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
     let manager = get_manager()?;
 
-    let path = manager.create_profile(&harness, &name)?;
-    println!("Created profile: {}", name.as_str());
+    let path = manager.create_profile(&harness, name)?;
+    if print_path {
+        return output::print_path(&path);
+    }
+    println!("Created profile: {name}");
     println!("Path: {}", path.display());
     Ok(())
 }
 
-pub fn create_profile_from_current(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn create_profile_from_template(
+    harness_name: &str,
+    name: &ProfileName,
+    template: ProfileTemplate,
+    print_path: bool,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
+    require_installed(&harness)?;
 
-    let status = harness
-        .installation_status()
-        .unwrap_or(InstallationStatus::NotInstalled);
-    match status {
-        InstallationStatus::FullyInstalled { .. } => {}
-        _ => {
-            eprintln!("Harness is not installed/configured:\n");
-            let lines = crate::harness::get_empty_state_message(harness.kind(), status, false);
-            for line in lines {
-                eprintln!("{}", line);
-            }
-            return Err(Error::HarnessNotInstalled);
-        }
+    let path = get_manager()?.create_from_template(&harness, name, template)?;
+    if print_path {
+        return output::print_path(&path);
     }
+    println!(
+        "Created profile from {} template: {}",
+        template.as_str(),
+        name
+    );
+    println!("Path: {}", path.display());
+    Ok(())
+}
 
-    let name = ProfileName::new(profile_name)
-        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
-    let manager = get_manager()?;
+/// How `profile create --from-current` and `--force` read the live config.
+pub struct CaptureArgs {
+    pub scope: ScopeArg,
+    pub filter: CaptureFilter,
+    /// `--no-resources`: capture config files only.
+    pub no_resources: bool,
+}
+
+impl CaptureArgs {
+    fn manager(self) -> Result<ProfileManager> {
+        let manager = get_manager()?.with_capture_filter(self.filter);
+        Ok(if self.no_resources {
+            manager.without_resources()
+        } else {
+            manager
+        })
+    }
+}
+
+pub fn create_profile_from_current(
+    harness_name: &str,
+    name: &ProfileName,
+    capture: CaptureArgs,
+    print_path: bool,
+) -> Result<()> {
+    let harness = resolve_scoped_harness(harness_name, capture.scope)?;
+    require_installed(harness.harness())?;
 
-    let path = manager.create_from_current_with_resources(&harness, Some(&harness), &name)?;
-    println!("Created profile from current config: {}", name.as_str());
+    let manager = capture.manager()?;
+
+    let path =
+        manager.create_from_current_with_resources(&harness, Some(harness.harness()), name)?;
+    if print_path {
+        return output::print_path(&path);
+    }
+    println!("Created profile from current config: {name}");
     println!("Path: {}", path.display());
     Ok(())
 }
 
-pub fn delete_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn recreate_profile_from_current(
+    harness_name: &str,
+    name: &ProfileName,
+    capture: CaptureArgs,
+    print_path: bool,
+) -> Result<()> {
+    let harness = resolve_scoped_harness(harness_name, capture.scope)?;
+    require_installed(harness.harness())?;
+
+    let manager = capture.manager()?;
+
+    let (path, backup) = manager.recreate_from_current(&harness, Some(harness.harness()), name)?;
+    if print_path {
+        return output::print_path(&path);
+    }
+    if let Some(backup) = backup {
+        println!("Backed up previous profile to: {}", backup.display());
+    }
+    println!("Created profile from current config: {name}");
+    println!("Path: {}", path.display());
+    Ok(())
+}
+
+pub fn copy_profile(
+    from_harness: &str,
+    from_profile: &str,
+    to_harness: &str,
+    to_profile: &str,
+) -> Result<()> {
+    let source_harness = resolve_harness(from_harness)?;
+    let target_harness = resolve_harness(to_harness)?;
+    let from = ProfileName::new(from_profile)
+        .map_err(|_| Error::InvalidProfileName(from_profile.to_string()))?;
+    let to = ProfileName::new(to_profile)
+        .map_err(|_| Error::InvalidProfileName(to_profile.to_string()))?;
+
+    let path = get_manager()?.copy_resources_to_new_profile(
+        &source_harness,
+        &from,
+        &target_harness,
+        &to,
+    )?;
+    println!(
+        "Created {} profile {} from {}/{}: {}",
+        target_harness.id(),
+        to.as_str(),
+        source_harness.id(),
+        from.as_str(),
+        path.display()
+    );
+    Ok(())
+}
+
+pub fn move_profile(harness_name: &str, profile_name: &str, new_harness: &str) -> Result<()> {
+    let source_harness = resolve_harness(harness_name)?;
+    let target_harness = resolve_harness(new_harness)?;
+    let manager = get_manager()?;
+    let name = resolve_profile_name(&manager, &source_harness, profile_name)?;
+
+    let path = manager.move_profile(&source_harness, &name, &target_harness)?;
+    println!(
+        "Moved {}/{} to {}: {}",
+        source_harness.id(),
+        name,
+        target_harness.id(),
+        path.display()
+    );
+    Ok(())
+}
+
+pub fn delete_profile(harness_name: &str, profile_name: &str, dry_run: bool) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
-    let name = ProfileName::new(profile_name)
-        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
     let manager = get_manager()?;
+    let name = resolve_profile_name(&manager, &harness, profile_name)?;
 
+    if dry_run {
+        println!("{}", deletion_preview(&manager, &harness, &name)?);
+        return Ok(());
+    }
     manager.delete_profile(&harness, &name)?;
     println!("Deleted profile: {}", name.as_str());
     Ok(())
 }
 
-pub fn edit_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+/// A line saying where profile `name` lives and how much deleting it frees.
+fn deletion_preview(
+    manager: &ProfileManager,
+    harness: &Harness,
+    name: &ProfileName,
+) -> Result<String> {
+    manager.ensure_deletable(harness, name)?;
+    let (path, files, bytes) = manager.profile_usage(harness, name)?;
+    Ok(format!(
+        "Would delete {}: {} ({} file{}, {})",
+        name,
+        path.display(),
+        files,
+        if files == 1 { "" } else { "s" },
+        format_size(bytes)
+    ))
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+pub fn delete_profiles_matching(
+    harness_name: &str,
+    pattern: &str,
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let manager = get_manager()?;
+    let targets = manager.inactive_profiles_matching(&harness, pattern)?;
+    if targets.is_empty() {
+        println!("No inactive profiles match '{pattern}'");
+        return Ok(());
+    }
+
+    if dry_run {
+        for name in &targets {
+            println!("{}", deletion_preview(&manager, &harness, name)?);
+        }
+        return Ok(());
+    }
+    println!("Profiles matching '{pattern}':");
+    for name in &targets {
+        println!("  {name}");
+    }
+    if !yes && !confirm(&format!("Delete {} profile(s)?", targets.len()))? {
+        println!("Nothing deleted (pass --yes to skip the prompt)");
+        return Ok(());
+    }
+    for name in &targets {
+        manager.delete_profile(&harness, name)?;
+        println!("Deleted profile: {name}");
+    }
+    Ok(())
+}
+
+pub fn edit_profile(harness_name: &str, profile_name: &str, file: Option<&str>) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
@@ -177,7 +606,85 @@ pub fn edit_profile(harness_name: &str, profile_name: &str) -> Result<()> {
         return Err(Error::ProfileNotFound(profile_name.to_string()));
     }
 
-    let config = crate::config::BridleConfig::load().unwrap_or_default();
+    let target = match file {
+        Some(relative) => {
+            let path = profile_file_path(&profile_path, relative)?;
+            if !path.exists() {
+                if !confirm(&format!("{relative} does not exist. Create it?"))? {
+                    return Err(Error::Command(format!("File not found: {relative}")));
+                }
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::File::create(&path)?;
+            }
+            path
+        }
+        None => profile_path.clone(),
+    };
+
+    manager.detach_hardlinks(&target)?;
+    let snapshot = EditSnapshot::take(&target)?;
+    if let Err(e) = open_in_editor(&target) {
+        snapshot.discard()?;
+        return Err(e);
+    }
+    profile_history::note(&profile_path, HistoryEvent::Edited);
+
+    let invalid = snapshot.invalid_files();
+    if invalid.is_empty() {
+        snapshot.discard()?;
+    } else {
+        for (path, err) in &invalid {
+            eprintln!("{} no longer parses: {err}", path.display());
+        }
+        let revert = match confirm("Revert the broken files to their pre-edit contents?") {
+            Ok(revert) => revert,
+            Err(e) => {
+                snapshot.discard()?;
+                return Err(e);
+            }
+        };
+        if revert {
+            for path in snapshot.revert_invalid()? {
+                println!("Reverted: {}", path.display());
+            }
+            return Ok(());
+        }
+        snapshot.discard()?;
+    }
+
+    println!("Edited profile: {profile_name}");
+    Ok(())
+}
+
+/// Opens the live config of the harness's active profile in the editor, then
+/// offers to save the edits back into that profile.
+///
+/// # Errors
+/// Returns [`Error::NoActiveProfile`] if the harness has no active profile.
+pub fn open_active_profile(harness_name: &str) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let active = BridleConfig::load()?
+        .active_profile_for(harness.id())
+        .map(String::from)
+        .ok_or(Error::NoActiveProfile)?;
+    let name = ProfileName::new(&active).map_err(|_| Error::InvalidProfileName(active.clone()))?;
+
+    open_in_editor(&harness.config_dir()?)?;
+
+    if confirm(&format!("Save live config back to profile '{active}'?"))? {
+        get_manager()?.save_to_profile(&harness, Some(&harness), &name)?;
+        println!("Saved live config to '{}'", active);
+    } else {
+        println!("Profile '{}' not updated", active);
+    }
+    Ok(())
+}
+
+/// Opens `target` in the configured editor and waits for it to exit.
+pub(crate) fn open_in_editor(target: &Path) -> Result<()> {
+    let config = BridleConfig::load().unwrap_or_default();
     let (program, args) = config.editor_command();
 
     // On Windows, use cmd /c to invoke the editor so that .cmd/.bat wrappers
@@ -187,29 +694,116 @@ pub fn edit_profile(harness_name: &str, profile_name: &str) -> Result<()> {
         .arg("/c")
         .arg(&program)
         .args(&args)
-        .arg(&profile_path)
+        .arg(target)
         .status()?;
 
     #[cfg(not(windows))]
     let status = std::process::Command::new(&program)
         .args(&args)
-        .arg(&profile_path)
+        .arg(target)
         .status()?;
 
-    if status.success() {
-        println!("Edited profile: {profile_name}");
-        Ok(())
-    } else {
-        Err(Error::Command(format!(
+    if !status.success() {
+        return Err(Error::Command(format!(
             "Editor exited with status: {status}"
-        )))
+        )));
     }
+    Ok(())
 }
 
-pub fn diff_profiles(
+/// Path of `file` to read for `profile cat`: the live copy when the profile is
+/// active, as `show` does, or the stored copy when `stored` is set.
+fn profile_file_to_read(
     harness_name: &str,
     profile_name: &str,
-    other_name: Option<&str>,
+    file: &str,
+    stored: bool,
+) -> Result<PathBuf> {
+    let resolved = resolve_any_harness(harness_name)?;
+    let harness = resolved.config();
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let profile_path = get_manager()?.profile_path(harness, &name);
+    if !profile_path.exists() {
+        return Err(Error::ProfileNotFound(profile_name.to_string()));
+    }
+
+    let is_active = BridleConfig::load()
+        .map(|c| c.active_profile_for(&harness.active_key()) == Some(name.as_str()))
+        .unwrap_or(false);
+    let root = if is_active && !stored {
+        harness.config_dir()?
+    } else {
+        profile_path
+    };
+    let path = profile_file_path(&root, file)?;
+    if !path.is_file() {
+        return Err(Error::Command(format!("File not found: {file}")));
+    }
+    Ok(path)
+}
+
+pub fn cat_profile_file(
+    harness_name: &str,
+    profile_name: &str,
+    file: &str,
+    stored: bool,
+) -> Result<()> {
+    let path = profile_file_to_read(harness_name, profile_name, file, stored)?;
+    std::io::stdout().write_all(&std::fs::read(path)?)?;
+    Ok(())
+}
+
+/// Join `relative` onto the profile directory, rejecting paths that escape it.
+fn profile_file_path(profile_path: &Path, relative: &str) -> Result<PathBuf> {
+    let invalid = || Error::InvalidValue(format!("path escapes the profile directory: {relative}"));
+
+    let mut path = profile_path.to_path_buf();
+    let mut has_component = false;
+    for component in Path::new(relative).components() {
+        let Component::Normal(part) = component else {
+            return Err(invalid());
+        };
+        let part = part.to_str().ok_or_else(invalid)?;
+        validate_component_name(part).map_err(|_| invalid())?;
+        path.push(part);
+        has_component = true;
+    }
+
+    if !has_component {
+        return Err(invalid());
+    }
+    Ok(path)
+}
+
+/// Asks a yes/no question, answering no when stdin is not a terminal.
+pub(crate) fn confirm(prompt: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(true)
+        .interact()
+        .map_err(|e| Error::Command(e.to_string()))
+}
+
+pub fn compress_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+
+    let archive = get_manager()?.compress_profile(&harness, &name)?;
+    println!("Compressed '{}' into {}", profile_name, archive.display());
+    Ok(())
+}
+
+pub fn set_profile_meta(
+    harness_name: &str,
+    profile_name: &str,
+    description: Option<String>,
+    tags: Vec<String>,
+    locked: Option<bool>,
 ) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
@@ -221,55 +815,1145 @@ pub fn diff_profiles(
         return Err(Error::ProfileNotFound(profile_name.to_string()));
     }
 
-    let other_path = if let Some(other) = other_name {
-        let other_name =
-            ProfileName::new(other).map_err(|_| Error::InvalidProfileName(other.to_string()))?;
-        let path = manager.profile_path(&harness, &other_name);
-        if !path.exists() {
-            return Err(Error::ProfileNotFound(other.to_string()));
+    let mut meta = ProfileMeta::load(&profile_path)?;
+    if description.is_some() {
+        meta.description = description;
+    }
+    if !tags.is_empty() {
+        meta.tags = tags;
+    }
+    if let Some(locked) = locked {
+        meta.locked = locked;
+    }
+    meta.save(&profile_path)?;
+    println!("Updated metadata for profile: {}", name.as_str());
+    Ok(())
+}
+
+pub fn which_profile(harness_name: &str, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let config = BridleConfig::load()?;
+    let entry = active_profile_entry(&config, harness.id());
+
+    output(&entry, format, |entry| {
+        if let Some(active) = &entry.active {
+            println!("{active}");
         }
-        path
-    } else {
-        harness.config(&harness_locate::Scope::Global)?
+    })?;
+
+    match entry.active {
+        Some(_) => Ok(()),
+        None => Err(Error::NoActiveProfile),
+    }
+}
+
+fn active_profile_entry(config: &BridleConfig, harness_id: &str) -> ActiveProfileEntry {
+    ActiveProfileEntry {
+        harness: harness_id.to_string(),
+        active: config.active_profile_for(harness_id).map(String::from),
+    }
+}
+
+pub fn rollback_profile(harness_name: &str, scope: ScopeArg, force: bool) -> Result<()> {
+    let harness = resolve_scoped_harness(harness_name, scope)?;
+    let mut manager = get_manager()?;
+    if force {
+        manager = manager.ignoring_locks();
+    }
+    let (backup, active) = manager.rollback(&harness, Some(harness.harness()))?;
+    println!("Restored config from backup: {}", backup.display());
+    match active {
+        Some(name) => println!("Active profile: {name}"),
+        None => println!("No active profile"),
+    }
+    Ok(())
+}
+
+pub fn verify_profile(
+    harness_name: &str,
+    profile_name: &str,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let report = get_manager()?.verify_profile(&harness, &name)?;
+
+    output(&report, format, |report| {
+        if report.issues.is_empty() {
+            println!("{}: OK", report.name);
+        }
+        for issue in &report.issues {
+            let label = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            println!("{}: {}", label, issue.message);
+        }
+    })?;
+
+    if report.has_errors() {
+        return Err(Error::VerificationFailed(report.name));
+    }
+    Ok(())
+}
+
+pub fn validate_profile_config(
+    harness_name: &str,
+    profile_name: &str,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let validation = get_manager()?.validate_profile_config(&harness, &name)?;
+
+    output(&validation, format, |v| {
+        let Some(file) = &v.file else {
+            return println!("{}: no config file to validate", v.name);
+        };
+        if v.violations.is_empty() {
+            println!("{}: {} matches schema v{}", v.name, file, v.schema_version);
+        }
+        for violation in &v.violations {
+            let path = if violation.path.is_empty() {
+                "/"
+            } else {
+                &violation.path
+            };
+            println!("error: {}{}: {}", file, path, violation.message);
+        }
+    })?;
+
+    if !validation.violations.is_empty() {
+        return Err(Error::VerificationFailed(validation.name));
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct FingerprintOutput {
+    name: String,
+    harness_id: String,
+    fingerprint: String,
+}
+
+pub fn fingerprint_profile(
+    harness_name: &str,
+    profile_name: &str,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let fingerprint = get_manager()?.fingerprint_profile(&harness, &name)?;
+
+    let result = FingerprintOutput {
+        name: name.as_str().to_string(),
+        harness_id: harness.id().to_string(),
+        fingerprint,
     };
+    output(&result, format, |r| println!("{}", r.fingerprint))?;
+    Ok(())
+}
 
-    let status = std::process::Command::new("diff")
-        .arg("-u")
-        .arg(&profile_path)
-        .arg(&other_path)
-        .status()?;
+#[derive(Serialize)]
+struct OrphanedHarness {
+    harness_id: String,
+    profiles: Vec<String>,
+    deleted: bool,
+}
 
-    match status.code() {
-        Some(0) => println!("No differences"),
-        Some(1) => {}
-        _ => return Err(Error::Command(format!("diff exited with status: {status}"))),
+pub fn prune_orphans(dry_run: bool, yes: bool, format: ResolvedFormat) -> Result<()> {
+    prune_orphans_with(dry_run, yes, format, &confirm)
+}
+
+/// [`prune_orphans`], asking with `ask` before deleting unless `yes` is set.
+fn prune_orphans_with(
+    dry_run: bool,
+    yes: bool,
+    format: ResolvedFormat,
+    ask: &dyn Fn(&str) -> Result<bool>,
+) -> Result<()> {
+    let manager = get_manager()?;
+    let found = manager.orphaned_profiles()?;
+    let mut declined = false;
+    if !dry_run && !yes && !found.is_empty() {
+        for (harness_id, profiles) in &found {
+            let names: Vec<&str> = profiles.iter().map(|p| p.as_str()).collect();
+            eprintln!("  {harness_id}: {}", names.join(", "));
+        }
+        declined = !ask(&format!(
+            "Delete the profiles of {} orphaned harness(es)?",
+            found.len()
+        ))?;
+    }
+    let delete = !dry_run && !declined;
+
+    let mut orphans = Vec::new();
+    for (harness_id, profiles) in found {
+        if delete {
+            manager.delete_harness_profiles(&harness_id)?;
+        }
+        orphans.push(OrphanedHarness {
+            harness_id,
+            profiles: profiles.iter().map(|p| p.to_string()).collect(),
+            deleted: delete,
+        });
     }
+
+    output_list(&orphans, format, |orphans| {
+        if orphans.is_empty() {
+            println!("No orphaned profiles");
+        } else if declined {
+            println!("Nothing deleted (pass --yes to skip the prompt)");
+            return;
+        }
+        for orphan in orphans {
+            let verb = if orphan.deleted {
+                "Deleted"
+            } else {
+                "Would delete"
+            };
+            println!(
+                "{} {}: {}",
+                verb,
+                orphan.harness_id,
+                orphan.profiles.join(", ")
+            );
+        }
+    })?;
     Ok(())
 }
 
-pub fn switch_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn profile_stats(harness_name: &str, keep_going: bool, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let infos = get_manager()?.show_profiles(&harness, keep_going)?;
+    let stats = compute_stats(harness.id(), &infos);
+
+    output(&stats, format, print_stats_text)?;
+    Ok(())
+}
+
+fn compute_stats(harness_id: &str, infos: &[ProfileInfo]) -> ProfileStats {
+    let profiles: Vec<ProfileCountsEntry> = infos
+        .iter()
+        .map(|info| ProfileCountsEntry {
+            name: info.name.clone(),
+            counts: ResourceCounts::from_info(info),
+        })
+        .collect();
+
+    let total = |f: fn(&ResourceCounts) -> usize| profiles.iter().map(|p| f(&p.counts)).sum();
+    // Ties go to the first profile in list order; profiles with zero don't count.
+    let most = |f: fn(&ResourceCounts) -> usize| {
+        profiles
+            .iter()
+            .filter(|p| f(&p.counts) > 0)
+            .fold(None::<&ProfileCountsEntry>, |best, p| match best {
+                Some(b) if f(&b.counts) >= f(&p.counts) => Some(b),
+                _ => Some(p),
+            })
+            .map(|p| p.name.clone())
+    };
+
+    ProfileStats {
+        harness_id: harness_id.to_string(),
+        profile_count: profiles.len(),
+        totals: ResourceCounts {
+            mcp_servers: total(|c| c.mcp_servers),
+            skills: total(|c| c.skills),
+            agents: total(|c| c.agents),
+            commands: total(|c| c.commands),
+        },
+        most: MostEntries {
+            mcp_servers: most(|c| c.mcp_servers),
+            skills: most(|c| c.skills),
+            agents: most(|c| c.agents),
+            commands: most(|c| c.commands),
+        },
+        profiles,
+    }
+}
+
+fn print_stats_text(stats: &ProfileStats) {
+    println!("Profiles for {}: {}", stats.harness_id, stats.profile_count);
+    if stats.profiles.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "  {:<24} {:>5} {:>7} {:>7} {:>9}",
+        "PROFILE", "MCP", "SKILLS", "AGENTS", "COMMANDS"
+    );
+    for p in &stats.profiles {
+        println!(
+            "  {:<24} {:>5} {:>7} {:>7} {:>9}",
+            p.name, p.counts.mcp_servers, p.counts.skills, p.counts.agents, p.counts.commands
+        );
+    }
+    println!(
+        "  {:<24} {:>5} {:>7} {:>7} {:>9}",
+        "total",
+        stats.totals.mcp_servers,
+        stats.totals.skills,
+        stats.totals.agents,
+        stats.totals.commands
+    );
+
+    println!();
+    let rows = [
+        ("MCP servers", &stats.most.mcp_servers),
+        ("Skills", &stats.most.skills),
+        ("Agents", &stats.most.agents),
+        ("Commands", &stats.most.commands),
+    ];
+    for (label, name) in rows {
+        if let Some(name) = name {
+            println!("Most {}: {}", label.to_lowercase(), name);
+        }
+    }
+}
+
+pub fn diff_profiles(
+    harness_name: &str,
+    profile_name: &str,
+    other_name: Option<&str>,
+    strict: bool,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let other = other_name
+        .map(|other| {
+            ProfileName::new(other).map_err(|_| Error::InvalidProfileName(other.to_string()))
+        })
+        .transpose()?;
     let manager = get_manager()?;
+    let changed = manager.changed_files(&harness, &name, other.as_ref(), strict)?;
+    if changed.is_empty() {
+        println!("No differences");
+        return Ok(());
+    }
 
-    if !manager.profile_exists(&harness, &name) {
-        return Err(Error::ProfileNotFound(profile_name.to_string()));
+    let profile_path = manager.profile_path(&harness, &name);
+    let other_path = match &other {
+        Some(other) => manager.profile_path(&harness, other),
+        None => harness.config(&harness_locate::Scope::Global)?,
+    };
+    diff_changed_files(&changed, &profile_path, &other_path, strict)
+}
+
+#[derive(Serialize)]
+struct ProfileChange {
+    section: &'static str,
+    change: String,
+}
+
+/// Compares the stored copies of two profiles setting by setting and resource
+/// by resource. The live config is never read, so an active profile is
+/// compared as it was last saved.
+pub fn compare_profiles(
+    harness_name: &str,
+    from_name: &str,
+    to_name: &str,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_any_harness(harness_name)?;
+    let manager = get_manager()?;
+    let stored = |name: &str| {
+        let name =
+            ProfileName::new(name).map_err(|_| Error::InvalidProfileName(name.to_string()))?;
+        manager.show_stored_profile(harness.config(), &name)
+    };
+    let nodes = profile_changes_to_nodes(&stored(from_name)?, &stored(to_name)?);
+
+    let mut changes = Vec::new();
+    for node in &nodes {
+        let section = node.label;
+        if node.children.is_empty() {
+            changes.push(ProfileChange {
+                section,
+                change: node.text.clone().unwrap_or_default(),
+            });
+        }
+        for child in &node.children {
+            changes.push(ProfileChange {
+                section,
+                change: child.text.clone().unwrap_or_default(),
+            });
+        }
+    }
+    output_list(&changes, format, |_| {
+        if nodes.is_empty() {
+            println!("No differences");
+        } else {
+            print!("{}", nodes_to_text(&nodes, TextStyle::Tree));
+        }
+    })
+}
+
+/// Shows a unified diff of each of `changed` between `left` and `right`.
+/// Unless `strict`, line endings and trailing whitespace are ignored, as they
+/// are when picking the changed files.
+fn diff_changed_files(changed: &[String], left: &Path, right: &Path, strict: bool) -> Result<()> {
+    let args = diff_args(strict);
+    for rel in changed {
+        run_diff(&args, &left.join(rel), &right.join(rel))?;
+    }
+    Ok(())
+}
+
+fn diff_args(strict: bool) -> Vec<&'static str> {
+    // -N so files present on only one side diff against nothing.
+    let mut args = vec!["-u", "-N"];
+    if !strict {
+        args.extend(["--strip-trailing-cr", "-Z"]);
     }
+    args
+}
+
+/// Runs `diff` on two paths, returning whether it found differences.
+fn run_diff(args: &[&str], left: &Path, right: &Path) -> Result<bool> {
+    let status = std::process::Command::new("diff")
+        .args(args)
+        .arg(left)
+        .arg(right)
+        .status()?;
+    match status.code() {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        _ => Err(Error::Command(format!("diff exited with status: {status}"))),
+    }
+}
+
+/// Diffs a backup (by timestamp, or `latest`) against profile `profile_name`,
+/// or the live config when it's `None`, file by file.
+pub fn diff_against_backup(
+    harness_name: &str,
+    timestamp: &str,
+    profile_name: Option<&str>,
+    strict: bool,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = profile_name
+        .map(|name| ProfileName::new(name).map_err(|_| Error::InvalidProfileName(name.to_string())))
+        .transpose()?;
+    let manager = get_manager()?;
+    let backup = manager.find_backup(&harness, timestamp)?;
+    let changed = manager.changed_files_since_backup(&harness, &backup, name.as_ref(), strict)?;
+    if changed.is_empty() {
+        println!("No differences");
+        return Ok(());
+    }
+
+    let current = match &name {
+        Some(name) => manager.profile_path(&harness, name),
+        None => harness.config(&harness_locate::Scope::Global)?,
+    };
+    diff_changed_files(&changed, &backup, &current, strict)
+}
+
+pub fn switch_profile(
+    harness_name: &str,
+    profile_name: &str,
+    scope: ScopeArg,
+    no_resources: bool,
+    force: bool,
+    print_path: bool,
+) -> Result<()> {
+    let harness = resolve_scoped_harness(harness_name, scope)?;
+    let mut manager = get_manager()?;
+    if no_resources {
+        manager = manager.without_resources();
+    }
+    if force {
+        manager = manager.ignoring_locks();
+    }
+    let name = resolve_profile_name(&manager, &harness, profile_name)?;
 
     let harness_id = harness.id();
+    // Resources are left alone, so there is nothing of theirs to back up.
+    let backup_resources = Some(harness.harness()).filter(|_| !no_resources);
 
-    match manager.backup_current(&harness) {
+    match manager.backup_current(&harness, backup_resources) {
+        Ok(_) if print_path => {}
         Ok(backup_path) => {
             println!("Backed up current config to: {}", backup_path.display());
         }
+        // Keep stdout to the path alone.
+        Err(e) if print_path => eprintln!("Warning: Could not backup current config: {e}"),
         Err(e) => {
             println!("Warning: Could not backup current config: {e}");
         }
     }
 
-    manager.switch_profile_with_resources(&harness, Some(&harness), &name)?;
+    let profile_path = manager.profile_path(&harness, &name);
+    let show_progress = std::io::stderr().is_terminal();
+    let mut report = |path: &Path| {
+        if show_progress {
+            let shown = path.strip_prefix(&profile_path).unwrap_or(path);
+            eprint!("\r\x1b[2Kcopying {}", shown.display());
+        }
+    };
+    let result =
+        manager.switch_profile_reporting(&harness, Some(harness.harness()), &name, &mut report);
+    if show_progress {
+        eprint!("\r\x1b[2K");
+    }
+    let report = result?;
+    if print_path {
+        return output::print_path(&harness.config_dir()?);
+    }
+    if let Some(outgoing) = report.saved_outgoing {
+        println!("Saved live config to: {outgoing}");
+    }
     println!("Switched to profile: {}", name.as_str());
     println!("Harness: {harness_id}");
     Ok(())
 }
+
+pub fn reset_profile(harness_name: &str) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let (name, backup) = get_manager()?.reset_to_stored(&harness, Some(&harness))?;
+    if let Some(backup) = backup {
+        println!("Backed up live config to: {}", backup.display());
+    }
+    println!("Reset {} to stored profile: {}", harness.id(), name);
+    Ok(())
+}
+
+/// `profile log`: the profile's history, oldest first.
+pub fn log_profile(harness_name: &str, profile_name: &str, format: ResolvedFormat) -> Result<()> {
+    let resolved = resolve_any_harness(harness_name)?;
+    let manager = get_manager()?;
+    let name = resolve_profile_name(&manager, resolved.config(), profile_name)?;
+    let history = manager.profile_history(resolved.config(), &name)?;
+
+    output_list(&history, format, |history| {
+        if history.is_empty() {
+            println!("No history for {}/{}", resolved.config().id(), name);
+        }
+        for entry in history {
+            println!("  {}  {}", entry.at, entry.event.as_str());
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::exit_code;
+    use crate::test_env::setup_test_env;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn show_profile_missing_profile_propagates_not_found() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+
+        let err = show_profile(
+            "opencode",
+            "missing",
+            &[],
+            ScopeArg::Global,
+            TextStyle::Tree,
+            ResolvedFormat::Json,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, Error::ProfileNotFound(ref name) if name == "missing"));
+        assert_eq!(err.exit_code(), exit_code::PROFILE_NOT_FOUND);
+    }
+
+    #[test]
+    fn filter_profile_json_keeps_header_and_selected_sections() {
+        let info = info_with_skills("work", &["a"]);
+        let json = filter_profile_json(&info, &[ProfileSection::Mcp]).unwrap();
+
+        assert_eq!(json["name"], "work");
+        assert!(json.get("mcp_servers").is_some());
+        assert!(json.get("skills").is_none());
+        assert!(json.get("model").is_none());
+    }
+
+    #[test]
+    fn which_reports_active_profile() {
+        let mut config = BridleConfig::default();
+        config.set_active_profile("opencode", "work");
+
+        let entry = active_profile_entry(&config, "opencode");
+        assert_eq!(entry.active.as_deref(), Some("work"));
+        assert_eq!(
+            serde_json::to_string(&entry).unwrap(),
+            r#"{"harness":"opencode","active":"work"}"#
+        );
+    }
+
+    #[test]
+    fn which_without_active_profile_exits_nonzero() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+
+        let entry = active_profile_entry(&BridleConfig::default(), "opencode");
+        assert_eq!(entry.active, None);
+
+        let err = which_profile("opencode", ResolvedFormat::Json).unwrap_err();
+        assert!(matches!(err, Error::NoActiveProfile));
+        assert_eq!(err.exit_code(), exit_code::NO_ACTIVE_PROFILE);
+    }
+
+    #[test]
+    fn open_active_without_active_profile_errors() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+
+        let err = open_active_profile("opencode").unwrap_err();
+        assert!(matches!(err, Error::NoActiveProfile));
+    }
+
+    fn info_with_skills(name: &str, skills: &[&str]) -> ProfileInfo {
+        ProfileInfo {
+            name: name.to_string(),
+            skills: crate::config::ResourceSummary {
+                items: skills.iter().map(|s| s.to_string()).collect(),
+                directory_exists: true,
+                linked: Vec::new(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn yaml_output_round_trips_mcp_servers() {
+        let mut info = info_with_skills("work", &["a"]);
+        info.mcp_servers = vec![
+            crate::config::McpServerInfo {
+                name: "fs".to_string(),
+                enabled: true,
+                server_type: Some("stdio".to_string()),
+                command: Some("npx".to_string()),
+                args: Some(vec!["-y".to_string(), "server-fs".to_string()]),
+                url: None,
+                env_keys: Vec::new(),
+            },
+            crate::config::McpServerInfo {
+                name: "search".to_string(),
+                url: Some("https://example.com/mcp".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let yaml = crate::cli::output::serialize(&info, ResolvedFormat::Yaml).unwrap();
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        let servers: Vec<crate::config::McpServerInfo> =
+            serde_yaml::from_value(parsed["mcp_servers"].clone()).unwrap();
+        assert_eq!(servers, info.mcp_servers);
+        assert_eq!(parsed["name"].as_str(), Some("work"));
+    }
+
+    #[test]
+    fn stats_picks_profile_with_most_skills() {
+        let mut work = info_with_skills("work", &["a", "b", "c"]);
+        work.mcp_servers.push(crate::config::McpServerInfo {
+            name: "fs".to_string(),
+            ..Default::default()
+        });
+        let personal = info_with_skills("personal", &["a"]);
+
+        let stats = compute_stats("opencode", &[personal, work]);
+
+        assert_eq!(stats.profile_count, 2);
+        assert_eq!(stats.totals.skills, 4);
+        assert_eq!(stats.totals.mcp_servers, 1);
+        assert_eq!(stats.most.skills.as_deref(), Some("work"));
+        assert_eq!(stats.most.mcp_servers.as_deref(), Some("work"));
+        assert_eq!(stats.most.agents, None);
+    }
+
+    #[test]
+    fn lenient_diff_ignores_line_endings_and_trailing_whitespace() {
+        let temp = TempDir::new().unwrap();
+        let (left, right) = (temp.path().join("a.json"), temp.path().join("b.json"));
+        fs::write(&left, "{\n  \"theme\": \"nord\"\n}\n").unwrap();
+        fs::write(&right, "{\r\n  \"theme\": \"nord\"  \r\n}\r\n").unwrap();
+
+        assert!(!run_diff(&diff_args(false), &left, &right).unwrap());
+    }
+
+    #[test]
+    fn profile_file_path_joins_relative_file() {
+        let profile = Path::new("/profiles/opencode/work");
+        assert_eq!(
+            profile_file_path(profile, "opencode.jsonc").unwrap(),
+            profile.join("opencode.jsonc")
+        );
+        assert_eq!(
+            profile_file_path(profile, "agent/review.md").unwrap(),
+            profile.join("agent").join("review.md")
+        );
+    }
+
+    #[test]
+    fn show_json_goes_to_output_file() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("claude-code").unwrap();
+        let name = ProfileName::new("work").unwrap();
+        get_manager()
+            .unwrap()
+            .create_profile(&harness, &name)
+            .unwrap();
+
+        let path = temp.path().join("out/nested/x.json");
+        crate::cli::output::set_output_file(Some(path.clone()));
+        let shown = show_profile(
+            "claude-code",
+            "work",
+            &[],
+            ScopeArg::Global,
+            TextStyle::default(),
+            ResolvedFormat::Json,
+        );
+        crate::cli::output::set_output_file(None);
+        shown.unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["name"], "work");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn profile_names_resolve_from_a_unique_prefix() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("opencode").unwrap();
+        let manager = get_manager().unwrap();
+        for name in ["work", "web", "personal", "per"] {
+            manager
+                .create_profile(&harness, &ProfileName::new(name).unwrap())
+                .unwrap();
+        }
+        let resolve = |input| resolve_profile_name(&manager, &harness, input);
+
+        assert_eq!(resolve("wo").unwrap().as_str(), "work");
+        assert_eq!(resolve("pers").unwrap().as_str(), "personal");
+        // An exact name wins over the longer names it prefixes.
+        assert_eq!(resolve("per").unwrap().as_str(), "per");
+        match resolve("w") {
+            Err(Error::AmbiguousProfile { matches, .. }) => assert_eq!(matches, ["web", "work"]),
+            other => panic!("expected an ambiguous prefix, got {other:?}"),
+        }
+        assert!(matches!(resolve("x"), Err(Error::ProfileNotFound(_))));
+    }
+
+    #[test]
+    fn show_print_path_prints_only_the_absolute_profile_dir() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("opencode").unwrap();
+        let path = get_manager()
+            .unwrap()
+            .create_profile(&harness, &ProfileName::new("work").unwrap())
+            .unwrap();
+
+        let out = temp.path().join("path.txt");
+        crate::cli::output::set_output_file(Some(out.clone()));
+        let printed = print_profile_path("opencode", "work");
+        let missing = print_profile_path("opencode", "nope");
+        crate::cli::output::set_output_file(None);
+        printed.unwrap();
+
+        assert!(path.is_absolute());
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            format!("{}\n", path.display())
+        );
+        assert!(matches!(missing, Err(Error::ProfileNotFound(_))));
+    }
+
+    /// Makes OpenCode look installed, with its live config in
+    /// `<temp>/xdg/opencode`, until the guard drops. Only valid while the
+    /// [`setup_test_env`] guard is held, as it changes the process's env.
+    #[cfg(target_os = "linux")]
+    struct FakeOpencode {
+        prev: Vec<(&'static str, Option<std::ffi::OsString>)>,
+    }
+
+    #[cfg(target_os = "linux")]
+    impl FakeOpencode {
+        fn install(temp: &TempDir) -> Self {
+            use std::os::unix::fs::PermissionsExt;
+
+            let live = temp.path().join("xdg/opencode");
+            fs::create_dir_all(&live).unwrap();
+            fs::write(live.join("opencode.jsonc"), "{}").unwrap();
+            let bin = temp.path().join("bin");
+            fs::create_dir_all(&bin).unwrap();
+            fs::write(bin.join("opencode"), "#!/bin/sh\nexit 0\n").unwrap();
+            fs::set_permissions(bin.join("opencode"), fs::Permissions::from_mode(0o755)).unwrap();
+
+            let path = std::env::var_os("PATH").unwrap_or_default();
+            let paths = std::iter::once(bin).chain(std::env::split_paths(&path));
+            let vars = [
+                ("XDG_CONFIG_HOME", temp.path().join("xdg").into_os_string()),
+                ("PATH", std::env::join_paths(paths).unwrap()),
+            ];
+            let prev = vars
+                .iter()
+                .map(|(k, _)| (*k, std::env::var_os(k)))
+                .collect();
+            for (key, value) in vars {
+                unsafe { std::env::set_var(key, value) };
+            }
+            Self { prev }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    impl Drop for FakeOpencode {
+        fn drop(&mut self) {
+            for (key, value) in &self.prev {
+                match value {
+                    Some(value) => unsafe { std::env::set_var(key, value) },
+                    None => unsafe { std::env::remove_var(key) },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edit_leaves_no_backups_when_the_editor_cannot_start() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let profile = get_manager().unwrap().profile_path(
+            &Harness::new(HarnessKind::OpenCode),
+            &ProfileName::new("work").unwrap(),
+        );
+        fs::create_dir_all(&profile).unwrap();
+        fs::write(profile.join("opencode.jsonc"), "{}").unwrap();
+
+        let prev = std::env::var_os("VISUAL");
+        unsafe { std::env::set_var("VISUAL", temp.path().join("no-such-editor")) };
+        let result = edit_profile("opencode", "work", None);
+        match prev {
+            Some(value) => unsafe { std::env::set_var("VISUAL", value) },
+            None => unsafe { std::env::remove_var("VISUAL") },
+        }
+
+        assert!(result.is_err());
+        assert!(profile.join("opencode.jsonc").exists());
+        assert!(!profile.join("opencode.jsonc.bridle_edit_bak").exists());
+    }
+
+    #[test]
+    fn prune_orphans_deletes_only_once_confirmed() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let orphan = BridleConfig::profiles_dir().unwrap().join("gone-harness");
+        fs::create_dir_all(orphan.join("work")).unwrap();
+
+        prune_orphans_with(false, false, ResolvedFormat::Text, &|_| Ok(false)).unwrap();
+        assert!(orphan.join("work").is_dir());
+
+        let never = |_: &str| panic!("--yes must not ask");
+        prune_orphans_with(false, true, ResolvedFormat::Text, &never).unwrap();
+        assert!(!orphan.exists());
+    }
+
+    /// What `run` writes to stdout, given `--output-file` stands in for it.
+    fn stdout_of(temp: &TempDir, run: impl FnOnce() -> Result<()>) -> String {
+        let out = temp.path().join("stdout.txt");
+        crate::cli::output::set_output_file(Some(out.clone()));
+        let result = run();
+        crate::cli::output::set_output_file(None);
+        result.unwrap();
+        fs::read_to_string(&out).unwrap()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn create_and_switch_print_path_print_only_the_path() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let _opencode = FakeOpencode::install(&temp);
+        let harness = resolve_harness("opencode").unwrap();
+        let manager = get_manager().unwrap();
+        let path_line = |name: &str| {
+            let name = ProfileName::new(name).unwrap();
+            format!("{}\n", manager.profile_path(&harness, &name).display())
+        };
+
+        let empty = ProfileName::new("empty").unwrap();
+        let printed = stdout_of(&temp, || create_profile("opencode", &empty, true));
+        assert_eq!(printed, path_line("empty"));
+
+        let captured = ProfileName::new("captured").unwrap();
+        let capture = CaptureArgs {
+            scope: ScopeArg::Global,
+            filter: CaptureFilter::default(),
+            no_resources: true,
+        };
+        let printed = stdout_of(&temp, || {
+            create_profile_from_current("opencode", &captured, capture, true)
+        });
+        assert_eq!(printed, path_line("captured"));
+
+        let printed = stdout_of(&temp, || {
+            switch_profile("opencode", "empty", ScopeArg::Global, true, false, true)
+        });
+        assert_eq!(
+            printed,
+            format!("{}\n", temp.path().join("xdg/opencode").display())
+        );
+    }
+
+    /// Registers a custom harness `acme` whose live config is `live`, with
+    /// MCP servers in `mcp.json`.
+    fn register_acme(live: &Path) -> CustomHarness {
+        let descriptors = CustomHarness::descriptors_dir().unwrap();
+        fs::create_dir_all(&descriptors).unwrap();
+        fs::write(
+            descriptors.join("acme.toml"),
+            format!("id = \"acme\"\nconfig_dir = {live:?}\nmcp_path = \"mcp.json\"\n"),
+        )
+        .unwrap();
+        CustomHarness::find("acme").unwrap().unwrap()
+    }
+
+    #[test]
+    fn compact_list_prints_tab_separated_fields_and_marks_the_active_profile() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live = temp.path().join("live");
+        fs::create_dir_all(&live).unwrap();
+        fs::write(live.join("mcp.json"), r#"{"mcpServers": {"files": {}}}"#).unwrap();
+        let harness = register_acme(&live);
+        let manager = get_manager().unwrap();
+        for name in ["home", "work"] {
+            manager
+                .create_profile(&harness, &ProfileName::new(name).unwrap())
+                .unwrap();
+        }
+        fs::write(
+            manager
+                .profile_path(&harness, &ProfileName::new("work").unwrap())
+                .join("mcp.json"),
+            r#"{"mcpServers": {"files": {}, "search": {}}}"#,
+        )
+        .unwrap();
+        let mut config = BridleConfig::load().unwrap();
+        config.set_active_profile("acme", "home");
+        config.save().unwrap();
+
+        let out = temp.path().join("compact.txt");
+        crate::cli::output::set_output_file(Some(out.clone()));
+        let listed = list_profiles_compact("acme", true);
+        crate::cli::output::set_output_file(None);
+        listed.unwrap();
+
+        // The active profile's servers come from the live config.
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "home\t-\t1\tactive\nwork\t-\t2\t-\n"
+        );
+    }
+
+    #[test]
+    fn list_reads_names_without_loading_profiles() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live = temp.path().join("live");
+        fs::create_dir_all(&live).unwrap();
+        let harness = register_acme(&live);
+        let manager = get_manager().unwrap();
+        manager
+            .create_profile(&harness, &ProfileName::new("home").unwrap())
+            .unwrap();
+        // An archive that can't be unpacked fails to load, but still has a name.
+        let acme_dir = manager.profiles_dir().join("acme");
+        fs::write(acme_dir.join("work.tar.gz"), "not gzip").unwrap();
+        let mut config = BridleConfig::load().unwrap();
+        config.set_active_profile("acme", "work");
+        config.save().unwrap();
+
+        let out = temp.path().join("list.json");
+        crate::cli::output::set_output_file(Some(out.clone()));
+        let listed = list_profiles("acme", ResolvedFormat::Json);
+        crate::cli::output::set_output_file(None);
+        listed.unwrap();
+
+        let entries: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(
+            entries,
+            serde_json::json!([
+                {"name": "home", "harness_id": "acme", "is_active": false},
+                {"name": "work", "harness_id": "acme", "is_active": true},
+            ])
+        );
+        assert!(list_profiles_compact("acme", false).is_err());
+    }
+
+    #[test]
+    fn log_prints_the_recorded_history() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("opencode").unwrap();
+        let path = get_manager()
+            .unwrap()
+            .create_profile(&harness, &ProfileName::new("work").unwrap())
+            .unwrap();
+        profile_history::record(&path, HistoryEvent::Switched).unwrap();
+
+        let out = temp.path().join("log.json");
+        crate::cli::output::set_output_file(Some(out.clone()));
+        let logged = log_profile("opencode", "work", ResolvedFormat::Json);
+        crate::cli::output::set_output_file(None);
+        logged.unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+        let events: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["event"].as_str().unwrap())
+            .collect();
+        assert_eq!(events, ["created", "switched"]);
+    }
+
+    #[test]
+    fn delete_dry_run_reports_the_path_and_keeps_the_profile() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("claude-code").unwrap();
+        let manager = get_manager().unwrap();
+        let name = ProfileName::new("temp-a").unwrap();
+        let path = manager.create_profile(&harness, &name).unwrap();
+        fs::write(path.join("settings.json"), "x".repeat(2048)).unwrap();
+
+        let preview = deletion_preview(&manager, &harness, &name).unwrap();
+        delete_profile("claude-code", "temp-a", true).unwrap();
+        delete_profiles_matching("claude-code", "temp-*", false, true).unwrap();
+
+        assert!(preview.contains(&path.display().to_string()), "{preview}");
+        assert!(preview.ends_with(" KiB)"), "{preview}");
+        assert!(path.join("settings.json").is_file());
+
+        let mut meta = crate::config::ProfileMeta::load(&path).unwrap();
+        meta.locked = true;
+        meta.save(&path).unwrap();
+        let err = delete_profile("claude-code", "temp-a", true).unwrap_err();
+        assert!(matches!(err, Error::ProfileLocked(_)), "{err}");
+    }
+
+    #[test]
+    fn compare_reports_model_and_mcp_changes_from_stored_profiles() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = resolve_harness("claude-code").unwrap();
+        let manager = get_manager().unwrap();
+        for (name, model, servers) in [
+            (
+                "home",
+                "sonnet",
+                r#""files": {"command": "npx", "args": ["files"]}, "git": {"command": "git-mcp"}"#,
+            ),
+            (
+                "work",
+                "opus",
+                r#""git": {"command": "git-mcp", "args": ["-v"]}, "search": {"type": "http", "url": "https://example.com/mcp"}"#,
+            ),
+        ] {
+            let path = manager
+                .create_profile(&harness, &ProfileName::new(name).unwrap())
+                .unwrap();
+            fs::write(
+                path.join("settings.json"),
+                format!(r#"{{"model": "{model}"}}"#),
+            )
+            .unwrap();
+            fs::write(
+                path.join(".mcp.json"),
+                format!(r#"{{"mcpServers": {{{servers}}}}}"#),
+            )
+            .unwrap();
+        }
+
+        let path = temp.path().join("compare.json");
+        crate::cli::output::set_output_file(Some(path.clone()));
+        let compared = compare_profiles("claude-code", "home", "work", ResolvedFormat::Json);
+        crate::cli::output::set_output_file(None);
+        compared.unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"section": "Model", "change": "sonnet → opus"},
+                {"section": "MCP Servers", "change": "- files"},
+                {"section": "MCP Servers", "change": "~ git"},
+                {"section": "MCP Servers", "change": "+ search"},
+            ])
+        );
+    }
+
+    #[test]
+    fn cat_reads_live_copy_of_active_profile_unless_stored() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live = temp.path().join("live");
+        fs::create_dir_all(&live).unwrap();
+        fs::write(live.join("settings.json"), "live").unwrap();
+        let harness = register_acme(&live);
+        let name = ProfileName::new("work").unwrap();
+        let stored = get_manager()
+            .unwrap()
+            .create_profile(&harness, &name)
+            .unwrap();
+        fs::write(stored.join("settings.json"), "stored").unwrap();
+
+        let read = |stored| {
+            let path = profile_file_to_read("acme", "work", "settings.json", stored).unwrap();
+            fs::read_to_string(path).unwrap()
+        };
+        assert_eq!(read(false), "stored");
+
+        let mut config = BridleConfig::load().unwrap_or_default();
+        config.set_active_profile(&harness.active_key(), "work");
+        config.save().unwrap();
+        assert_eq!(read(false), "live");
+        assert_eq!(read(true), "stored");
+
+        assert!(matches!(
+            profile_file_to_read("acme", "work", "../work/settings.json", true),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            profile_file_to_read("acme", "work", "missing.json", true),
+            Err(Error::Command(_))
+        ));
+    }
+
+    #[test]
+    fn profile_file_path_rejects_traversal() {
+        let profile = Path::new("/profiles/opencode/work");
+        for bad in [
+            "../other/opencode.jsonc",
+            "/etc/passwd",
+            "agent/../../x",
+            "",
+            ".",
+        ] {
+            assert!(
+                matches!(profile_file_path(profile, bad), Err(Error::InvalidValue(_))),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+}
@@ -2,12 +2,19 @@ use harness_locate::{Harness, HarnessKind, InstallationStatus, Scope};
 use serde::Serialize;
 
 use crate::cli::output::{ResolvedFormat, output};
-use crate::config::BridleConfig;
+use crate::cli::profile::get_manager;
+use crate::config::{BridleConfig, McpServerInfo, ProfileManager, ProfileName};
+use crate::display::WARNING_SYMBOL;
+use crate::error::Result;
+use crate::harness::HarnessConfig;
 
 #[derive(Debug, Serialize)]
 pub struct StatusOutput {
     pub harnesses: Vec<HarnessStatus>,
     pub active_profiles: Vec<ActiveProfile>,
+    /// Problems spanning several harnesses, such as a shared config dir.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -16,6 +23,49 @@ pub struct HarnessStatus {
     pub name: String,
     pub status: String,
     pub config_path: Option<String>,
+    /// MCP servers of the active profile; `None` without an active profile.
+    pub mcp: Option<McpBreakdown>,
+}
+
+/// MCP server counts by transport. SSE servers count as `http`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct McpBreakdown {
+    pub total: usize,
+    pub stdio: usize,
+    pub http: usize,
+    pub disabled: usize,
+}
+
+impl McpBreakdown {
+    pub fn from_servers(servers: &[McpServerInfo]) -> Self {
+        let mut breakdown = Self {
+            total: servers.len(),
+            ..Self::default()
+        };
+        for server in servers {
+            match server.transport() {
+                "stdio" => breakdown.stdio += 1,
+                _ => breakdown.http += 1,
+            }
+            if !server.enabled {
+                breakdown.disabled += 1;
+            }
+        }
+        breakdown
+    }
+}
+
+/// The MCP breakdown of `harness`'s active profile, if it has one that can
+/// be read.
+fn active_mcp_breakdown(
+    config: &BridleConfig,
+    manager: &ProfileManager,
+    harness: &Harness,
+) -> Option<McpBreakdown> {
+    let active = config.active_profile_for(&harness.active_key())?;
+    let name = ProfileName::new(active).ok()?;
+    let (servers, _) = manager.profile_mcp_servers(harness, &name).ok()?;
+    Some(McpBreakdown::from_servers(&servers))
 }
 
 #[derive(Debug, Serialize)]
@@ -24,7 +74,9 @@ pub struct ActiveProfile {
     pub profile: String,
 }
 
-pub fn display_status(format: ResolvedFormat) {
+pub fn display_status(format: ResolvedFormat) -> Result<()> {
+    let config = BridleConfig::load()?;
+    let manager = get_manager()?;
     let harnesses: Vec<HarnessStatus> = HarnessKind::ALL
         .iter()
         .map(|kind| {
@@ -48,26 +100,24 @@ pub fn display_status(format: ResolvedFormat) {
                 name: kind.to_string(),
                 status: status.to_string(),
                 config_path,
+                mcp: active_mcp_breakdown(&config, &manager, &harness),
             }
         })
         .collect();
 
-    let active_profiles: Vec<ActiveProfile> = BridleConfig::load()
-        .map(|config| {
-            config
-                .active
-                .iter()
-                .map(|(harness, profile)| ActiveProfile {
-                    harness: harness.clone(),
-                    profile: profile.clone(),
-                })
-                .collect()
+    let active_profiles: Vec<ActiveProfile> = config
+        .active
+        .iter()
+        .map(|(harness, profile)| ActiveProfile {
+            harness: harness.clone(),
+            profile: profile.clone(),
         })
-        .unwrap_or_default();
+        .collect();
 
     let status = StatusOutput {
         harnesses,
         active_profiles,
+        warnings: crate::harness::shared_config_dir_warnings(),
     };
 
     output(&status, format, |s| {
@@ -77,6 +127,12 @@ pub fn display_status(format: ResolvedFormat) {
             if let Some(path) = &h.config_path {
                 println!("    Config: {}", path);
             }
+            if let Some(mcp) = &h.mcp {
+                println!(
+                    "    MCP: {} ({} stdio, {} http, {} disabled)",
+                    mcp.total, mcp.stdio, mcp.http, mcp.disabled
+                );
+            }
         }
 
         if !s.active_profiles.is_empty() {
@@ -85,5 +141,52 @@ pub fn display_status(format: ResolvedFormat) {
                 println!("  {}: {}", ap.harness, ap.profile);
             }
         }
-    });
+
+        if !s.warnings.is_empty() {
+            println!("\nWarnings:");
+            for warning in &s.warnings {
+                println!("  {} {}", WARNING_SYMBOL, warning);
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(name: &str, server_type: Option<&str>, enabled: bool) -> McpServerInfo {
+        McpServerInfo {
+            name: name.to_string(),
+            enabled,
+            server_type: server_type.map(String::from),
+            command: server_type.is_none().then(|| "npx".to_string()),
+            args: None,
+            url: server_type.map(|_| "https://example.com/mcp".to_string()),
+            env_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn breakdown_counts_transports_and_disabled_servers() {
+        let servers = [
+            server("files", None, true),
+            server("git", None, false),
+            server("search", Some("http"), true),
+            server("events", Some("sse"), false),
+        ];
+
+        assert_eq!(
+            McpBreakdown::from_servers(&servers),
+            McpBreakdown {
+                total: 4,
+                stdio: 2,
+                http: 2,
+                disabled: 2,
+            }
+        );
+        assert_eq!(McpBreakdown::from_servers(&[]), McpBreakdown::default());
+    }
 }
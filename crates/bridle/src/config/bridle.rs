@@ -13,8 +13,8 @@ pub enum ViewPreference {
     Dashboard,
     /// Legacy list view.
     Legacy,
-    /// Card-based view (requires tui-cards feature).
-    #[cfg(feature = "tui-cards")]
+    /// Card-based view. Builds without the tui-cards feature fall back to
+    /// the dashboard, so the same config file works with either build.
     Cards,
 }
 
@@ -24,6 +24,92 @@ pub struct TuiConfig {
     /// Preferred view mode.
     #[serde(default)]
     pub view: ViewPreference,
+    /// Colors by role.
+    #[serde(default, skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
+    /// Set once the first-run welcome screen has been dismissed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub onboarding_seen: bool,
+}
+
+/// TUI colors by semantic role (`[tui.theme]`), as names ratatui accepts:
+/// `"blue"`, `"lightgreen"`, `"#ff8800"`, or a 256-color index. Roles left
+/// unset keep their defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_active: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_active: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_enabled: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_disabled: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+impl ThemeConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How MCP servers are ordered when a profile is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum McpOrder {
+    /// Alphabetical by server name.
+    #[default]
+    Name,
+    /// The order the servers are found in: the harness's primary config
+    /// first, then standalone MCP files.
+    File,
+}
+
+/// Profile capture settings (`[profile]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    /// Abort capturing the live config once it exceeds this many bytes.
+    /// Unlimited when unset.
+    #[serde(default)]
+    pub max_capture_bytes: Option<u64>,
+    /// Hardlink captured files that are identical to ones already stored in
+    /// another profile instead of copying them.
+    #[serde(default)]
+    pub dedupe: bool,
+    /// How many directories deep to look for nested skills, e.g. 2 for
+    /// `skills/<category>/<skill>/SKILL.md`.
+    #[serde(default = "default_skill_depth")]
+    pub skill_depth: usize,
+    /// How many times a switch retries removing or renaming a file that
+    /// another process has locked, backing off between attempts. Only used
+    /// on Windows.
+    #[serde(default = "default_fs_retries")]
+    pub fs_retries: u32,
+    /// Order of MCP servers in `profile show`, the TUI, and JSON output.
+    #[serde(default)]
+    pub mcp_order: McpOrder,
+}
+
+fn default_skill_depth() -> usize {
+    1
+}
+
+fn default_fs_retries() -> u32 {
+    3
+}
+
+impl Default for ProfileConfig {
+    fn default() -> Self {
+        Self {
+            max_capture_bytes: None,
+            dedupe: false,
+            skill_depth: default_skill_depth(),
+            fs_retries: default_fs_retries(),
+            mcp_order: McpOrder::default(),
+        }
+    }
 }
 
 /// Bridle's configuration.
@@ -43,7 +129,7 @@ pub struct BridleConfig {
     active_profile: Option<String>,
 
     /// Preferred editor for editing profiles.
-    /// Falls back to $EDITOR env var, then "vi".
+    /// `$VISUAL` takes precedence; `$EDITOR` and a platform default are fallbacks.
     #[serde(default)]
     pub editor: Option<String>,
 
@@ -54,14 +140,49 @@ pub struct BridleConfig {
     /// Default harness to show when TUI opens.
     #[serde(default)]
     pub default_harness: Option<String>,
+
+    /// Profile capture settings.
+    #[serde(default)]
+    pub profile: ProfileConfig,
+
+    /// Request timeout in seconds for `bridle install` fetches.
+    #[serde(default)]
+    pub fetch_timeout_secs: Option<u64>,
+}
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+fn resolve_editor(
+    visual: Option<String>,
+    configured: Option<String>,
+    editor: Option<String>,
+) -> String {
+    [visual, configured, editor]
+        .into_iter()
+        .flatten()
+        .find(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+fn split_editor(editor: &str) -> (String, Vec<String>) {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR).to_string();
+    let args: Vec<String> = parts.map(String::from).collect();
+    (program, args)
 }
 
 impl BridleConfig {
+    /// Resolve the editor: `$VISUAL`, then the configured editor, then `$EDITOR`,
+    /// then the platform default.
     pub fn editor(&self) -> String {
-        self.editor
-            .clone()
-            .or_else(|| std::env::var("EDITOR").ok())
-            .unwrap_or_else(|| "vi".to_string())
+        resolve_editor(
+            std::env::var("VISUAL").ok(),
+            self.editor.clone(),
+            std::env::var("EDITOR").ok(),
+        )
     }
 
     /// Parse editor string into program and arguments.
@@ -69,11 +190,7 @@ impl BridleConfig {
     /// Handles commands like "code --wait" by splitting on whitespace.
     /// Returns (program, args) tuple for use with `std::process::Command`.
     pub fn editor_command(&self) -> (String, Vec<String>) {
-        let editor = self.editor();
-        let mut parts = editor.split_whitespace();
-        let program = parts.next().unwrap_or("vi").to_string();
-        let args: Vec<String> = parts.map(String::from).collect();
-        (program, args)
+        split_editor(&self.editor())
     }
 }
 
@@ -152,4 +269,44 @@ impl BridleConfig {
     pub fn set_default_harness(&mut self, harness_id: Option<&str>) {
         self.default_harness = harness_id.map(String::from);
     }
+
+    /// Request timeout for network fetches, defaulting to 30 seconds.
+    pub fn fetch_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.fetch_timeout_secs.unwrap_or(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn some(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    #[test]
+    fn editor_precedence_visual_config_editor_default() {
+        assert_eq!(
+            resolve_editor(some("nvim"), some("code --wait"), some("nano")),
+            "nvim"
+        );
+        assert_eq!(
+            resolve_editor(None, some("code --wait"), some("nano")),
+            "code --wait"
+        );
+        assert_eq!(resolve_editor(None, None, some("nano")), "nano");
+        assert_eq!(resolve_editor(None, None, None), DEFAULT_EDITOR);
+    }
+
+    #[test]
+    fn editor_skips_blank_values() {
+        assert_eq!(resolve_editor(some(""), some("  "), some("nano")), "nano");
+    }
+
+    #[test]
+    fn editor_command_splits_arguments() {
+        let (program, args) = split_editor("code --wait --new-window");
+        assert_eq!(program, "code");
+        assert_eq!(args, vec!["--wait", "--new-window"]);
+    }
 }
@@ -0,0 +1,162 @@
+//! Snapshots of structured config files taken before an interactive edit.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::jsonc::strip_jsonc_comments;
+use crate::error::Result;
+
+/// Suffix appended to a file's name for its pre-edit copy.
+pub const BACKUP_SUFFIX: &str = ".bridle_edit_bak";
+
+/// Pre-edit copies of the JSON/JSONC/YAML files an editor may touch.
+///
+/// For a file target only that file is snapshotted; for a directory, its
+/// top-level structured files are.
+#[derive(Debug)]
+pub struct EditSnapshot {
+    files: Vec<PathBuf>,
+}
+
+impl EditSnapshot {
+    /// Copies each structured file under `target` to `<file>.bridle_edit_bak`.
+    ///
+    /// # Errors
+    /// Returns an IO error if a file cannot be read or copied; backups already
+    /// written are removed first.
+    pub fn take(target: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        if target.is_file() {
+            if is_structured(target) {
+                files.push(target.to_path_buf());
+            }
+        } else if target.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(target)?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && is_structured(p))
+                .collect();
+            entries.sort();
+            files = entries;
+        }
+
+        let mut snapshot = Self {
+            files: Vec::with_capacity(files.len()),
+        };
+        for file in files {
+            if let Err(e) = fs::copy(&file, backup_path(&file)) {
+                snapshot.discard()?;
+                return Err(e.into());
+            }
+            snapshot.files.push(file);
+        }
+        Ok(snapshot)
+    }
+
+    /// Returns each snapshotted file that no longer parses, with the parse error.
+    pub fn invalid_files(&self) -> Vec<(PathBuf, String)> {
+        self.files
+            .iter()
+            .filter_map(|file| validate_file(file).err().map(|e| (file.clone(), e)))
+            .collect()
+    }
+
+    /// Restores the snapshot of every file that no longer parses, then removes
+    /// all backups.
+    ///
+    /// # Errors
+    /// Returns an IO error if a backup cannot be copied back.
+    pub fn revert_invalid(self) -> Result<Vec<PathBuf>> {
+        let reverted: Vec<PathBuf> = self.invalid_files().into_iter().map(|(f, _)| f).collect();
+        for file in &reverted {
+            fs::copy(backup_path(file), file)?;
+        }
+        self.discard()?;
+        Ok(reverted)
+    }
+
+    /// Removes all backups, keeping the edited files as they are.
+    ///
+    /// # Errors
+    /// Returns an IO error if a backup exists but cannot be removed.
+    pub fn discard(self) -> Result<()> {
+        for file in &self.files {
+            let backup = backup_path(file);
+            if backup.exists() {
+                fs::remove_file(backup)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn backup_path(file: &Path) -> PathBuf {
+    let mut name = file.file_name().unwrap_or_default().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    file.with_file_name(name)
+}
+
+pub(crate) fn is_structured(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("json" | "jsonc" | "yaml" | "yml")
+    )
+}
+
+pub(crate) fn validate_file(path: &Path) -> std::result::Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str::<serde_json::Value>(&content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        Some("jsonc") => serde_json::from_str::<serde_json::Value>(&strip_jsonc_comments(&content))
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        Some("yaml" | "yml") => serde_yaml::from_str::<serde_yaml::Value>(&content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn broken_edit_is_flagged_and_reverted() {
+        let temp = TempDir::new().unwrap();
+        let config = temp.path().join("opencode.jsonc");
+        fs::write(&config, "{\n  // model\n  \"model\": \"gpt-4\"\n}").unwrap();
+        fs::write(temp.path().join("notes.md"), "not validated").unwrap();
+
+        let snapshot = EditSnapshot::take(temp.path()).unwrap();
+        assert!(temp.path().join("opencode.jsonc.bridle_edit_bak").exists());
+
+        fs::write(&config, "{ \"model\": ").unwrap();
+        let invalid = snapshot.invalid_files();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, config);
+
+        let reverted = snapshot.revert_invalid().unwrap();
+        assert_eq!(reverted, vec![config.clone()]);
+        assert!(fs::read_to_string(&config).unwrap().contains("gpt-4"));
+        assert!(!temp.path().join("opencode.jsonc.bridle_edit_bak").exists());
+    }
+
+    #[test]
+    fn valid_edit_needs_no_revert() {
+        let temp = TempDir::new().unwrap();
+        let config = temp.path().join("config.yaml");
+        fs::write(&config, "model: a\n").unwrap();
+
+        let snapshot = EditSnapshot::take(&config).unwrap();
+        fs::write(&config, "model: b\n").unwrap();
+        assert!(snapshot.invalid_files().is_empty());
+
+        snapshot.discard().unwrap();
+        assert!(!temp.path().join("config.yaml.bridle_edit_bak").exists());
+        assert_eq!(fs::read_to_string(&config).unwrap(), "model: b\n");
+    }
+}
@@ -1,5 +1,7 @@
 //! JSONC parsing utilities for OpenCode config files.
 
+use crate::error::{Error, Result};
+
 pub fn strip_jsonc_comments(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
     let mut chars = input.chars().peekable();
@@ -43,6 +45,10 @@ pub fn strip_jsonc_comments(input: &str) -> String {
                             chars.next();
                             break;
                         }
+                        // Keep line numbers in parse errors aligned with the original file.
+                        if ch == '\n' {
+                            result.push('\n');
+                        }
                     }
                 }
                 _ => result.push(c),
@@ -85,6 +91,206 @@ fn strip_trailing_commas(input: &str) -> String {
     result
 }
 
+/// Sets the value at `path` (a chain of object keys) in the JSONC document
+/// `input`, leaving comments and formatting elsewhere untouched.
+///
+/// Objects missing along the path are created; a non-object in the way is
+/// replaced. Plain JSON is valid input too.
+///
+/// # Errors
+/// Returns [`Error::Config`] if `input` isn't a JSONC object or `path` is empty.
+pub fn set_jsonc_value(input: &str, path: &[&str], value: &serde_json::Value) -> Result<String> {
+    if path.is_empty() {
+        return Err(Error::Config("empty key".to_string()));
+    }
+    let bytes = input.as_bytes();
+    let root = skip_trivia(bytes, 0);
+    if bytes.get(root) != Some(&b'{') {
+        return Err(Error::Config("expected a JSON object".to_string()));
+    }
+    let (start, end, replacement) = object_edit(input, root, path, value)?;
+    Ok(format!(
+        "{}{}{}",
+        &input[..start],
+        replacement,
+        &input[end..]
+    ))
+}
+
+/// The `(start, end, replacement)` edit that sets `path` in the object
+/// opening at `open`.
+fn object_edit(
+    input: &str,
+    open: usize,
+    path: &[&str],
+    value: &serde_json::Value,
+) -> Result<(usize, usize, String)> {
+    let bytes = input.as_bytes();
+    let mut i = open + 1;
+    // Key start and value end of the last member, and whether a comma follows it.
+    let mut last: Option<(usize, usize, Option<usize>)> = None;
+    let close = loop {
+        i = skip_trivia(bytes, i);
+        match bytes.get(i) {
+            Some(b'}') => break i,
+            Some(b'"') => {
+                let key_end = skip_string(bytes, i)?;
+                let key: String = serde_json::from_str(&input[i..key_end])?;
+                let colon = skip_trivia(bytes, key_end);
+                if bytes.get(colon) != Some(&b':') {
+                    return Err(malformed(colon));
+                }
+                let value_start = skip_trivia(bytes, colon + 1);
+                let value_end = skip_value(bytes, value_start)?;
+                if key == path[0] {
+                    if path.len() > 1 && bytes[value_start] == b'{' {
+                        return object_edit(input, value_start, &path[1..], value);
+                    }
+                    return Ok((value_start, value_end, nested(&path[1..], value)));
+                }
+                let key_start = i;
+                i = skip_trivia(bytes, value_end);
+                let comma = (bytes.get(i) == Some(&b',')).then(|| {
+                    i += 1;
+                    i
+                });
+                last = Some((key_start, value_end, comma));
+            }
+            _ => return Err(malformed(i)),
+        }
+    };
+
+    let member = format!(
+        "{}: {}",
+        serde_json::Value::String(path[0].to_string()),
+        nested(&path[1..], value)
+    );
+    let close_indent = line_indent(input, close);
+    let Some((key_start, value_end, comma)) = last else {
+        let inner = &input[open + 1..close];
+        if inner.trim().is_empty() {
+            return Ok((
+                open + 1,
+                close,
+                format!("\n{}  {}\n{}", close_indent, member, close_indent),
+            ));
+        }
+        return Ok((close, close, format!("  {}\n{}", member, close_indent)));
+    };
+
+    let after = comma.unwrap_or(value_end);
+    let line_end = input[after..].find('\n').map_or(input.len(), |n| after + n);
+    let rest = input[after..line_end].trim();
+    if line_end < close && (rest.is_empty() || rest.starts_with("//")) {
+        // Own line after the last member, keeping any comment on its line.
+        let indent = line_indent(input, key_start);
+        let (sep, tail) = if comma.is_some() {
+            ("", ",")
+        } else {
+            (",", "")
+        };
+        return Ok((
+            value_end,
+            line_end,
+            format!(
+                "{}{}\n{}{}{}",
+                sep,
+                &input[value_end..line_end],
+                indent,
+                member,
+                tail
+            ),
+        ));
+    }
+    match comma {
+        Some(after_comma) => Ok((after_comma, after_comma, format!(" {},", member))),
+        None => Ok((value_end, value_end, format!(", {}", member))),
+    }
+}
+
+/// `value` wrapped in one object per key of `path`, as JSON text.
+fn nested(path: &[&str], value: &serde_json::Value) -> String {
+    let value = path.iter().rev().fold(value.clone(), |inner, key| {
+        serde_json::Value::Object([(key.to_string(), inner)].into_iter().collect())
+    });
+    value.to_string()
+}
+
+fn malformed(at: usize) -> Error {
+    Error::Config(format!("malformed JSONC at byte {}", at))
+}
+
+/// Leading whitespace of the line holding byte `at`.
+fn line_indent(input: &str, at: usize) -> &str {
+    let line_start = input[..at].rfind('\n').map_or(0, |n| n + 1);
+    let line = &input[line_start..at];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Index of the first byte at or after `i` that isn't whitespace or a comment.
+fn skip_trivia(bytes: &[u8], mut i: usize) -> usize {
+    loop {
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        if bytes[i..].starts_with(b"//") {
+            while bytes.get(i).is_some_and(|&b| b != b'\n') {
+                i += 1;
+            }
+        } else if bytes[i..].starts_with(b"/*") {
+            i += 2;
+            while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                i += 1;
+            }
+            i = (i + 2).min(bytes.len());
+        } else {
+            return i;
+        }
+    }
+}
+
+/// Index just past the string literal opening at `i`.
+fn skip_string(bytes: &[u8], mut i: usize) -> Result<usize> {
+    let start = i;
+    i += 1;
+    while let Some(&b) = bytes.get(i) {
+        match b {
+            b'\\' => i += 2,
+            b'"' => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+    Err(malformed(start))
+}
+
+/// Index just past the value starting at `i`.
+fn skip_value(bytes: &[u8], mut i: usize) -> Result<usize> {
+    match bytes.get(i) {
+        Some(b'"') => skip_string(bytes, i),
+        Some(b'{' | b'[') => {
+            i += 1;
+            loop {
+                i = skip_trivia(bytes, i);
+                match bytes.get(i) {
+                    Some(b'}' | b']') => return Ok(i + 1),
+                    Some(b'"') => i = skip_string(bytes, i)?,
+                    Some(b'{' | b'[') => i = skip_value(bytes, i)?,
+                    Some(_) => i += 1,
+                    None => return Err(malformed(i)),
+                }
+            }
+        }
+        Some(_) => {
+            let start = i;
+            while bytes.get(i).is_some_and(|b| !b",}] \t\r\n/".contains(b)) {
+                i += 1;
+            }
+            if i == start { Err(malformed(i)) } else { Ok(i) }
+        }
+        None => Err(malformed(i)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,10 +318,62 @@ mod tests {
         assert_eq!(result, input);
     }
 
+    #[test]
+    fn block_comments_keep_line_count() {
+        let input = "{\n/* one\ntwo */\n\"key\": 1\n}";
+        let result = strip_jsonc_comments(input);
+        assert_eq!(result.lines().count(), input.lines().count());
+    }
+
     #[test]
     fn strips_trailing_commas() {
         let input = r#"{"a": 1, "b": 2,}"#;
         let result = strip_jsonc_comments(input);
         assert_eq!(result, r#"{"a": 1, "b": 2}"#);
     }
+
+    #[test]
+    fn set_value_keeps_comments_and_replaces_in_place() {
+        let input = "{\n  // Theme picked by hand\n  \"theme\": \"opencode\", // keep me\n  \"model\": \"gpt-4\"\n}\n";
+        let out = set_jsonc_value(input, &["theme"], &serde_json::json!("tokyonight")).unwrap();
+        assert_eq!(
+            out,
+            "{\n  // Theme picked by hand\n  \"theme\": \"tokyonight\", // keep me\n  \"model\": \"gpt-4\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_value_appends_missing_keys_and_objects() {
+        let input = "{\n  \"model\": \"gpt-4\" // default\n}\n";
+        let out = set_jsonc_value(
+            input,
+            &["agent", "general", "model"],
+            &serde_json::json!("o3"),
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            "{\n  \"model\": \"gpt-4\", // default\n  \"agent\": {\"general\":{\"model\":\"o3\"}}\n}\n"
+        );
+
+        let nested = set_jsonc_value(
+            &out,
+            &["agent", "general", "temperature"],
+            &serde_json::json!(0.2),
+        )
+        .unwrap();
+        let parsed: serde_json::Value =
+            serde_json::from_str(&strip_jsonc_comments(&nested)).unwrap();
+        assert_eq!(parsed["agent"]["general"]["temperature"], 0.2);
+        assert_eq!(parsed["agent"]["general"]["model"], "o3");
+
+        assert_eq!(
+            set_jsonc_value("{}", &["a"], &serde_json::json!(1)).unwrap(),
+            "{\n  \"a\": 1\n}"
+        );
+        assert_eq!(
+            set_jsonc_value(r#"{"a": 1}"#, &["b"], &serde_json::json!(true)).unwrap(),
+            r#"{"a": 1, "b": true}"#
+        );
+    }
 }
@@ -0,0 +1,127 @@
+//! Per-invocation `--exclude`/`--include` patterns for capturing the live
+//! config into a profile.
+
+use super::extraction;
+
+/// Glob patterns layered over the default capture exclusions.
+///
+/// Patterns are matched against paths relative to the config dir, using `/`
+/// as the separator: `*` and `?` stay within one path segment, `{a,b}`
+/// picks an alternative, and `**` spans any number of segments. A pattern
+/// without a `/` matches an entry of that name at any depth. A pattern that
+/// matches a directory applies to everything below it.
+///
+/// Includes win over excludes and over the defaults, so
+/// `--exclude '*.log' --include important.log` keeps just that one log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaptureFilter {
+    exclude: Vec<String>,
+    include: Vec<String>,
+}
+
+impl CaptureFilter {
+    /// A filter from `--exclude` and `--include` patterns.
+    pub fn new(exclude: Vec<String>, include: Vec<String>) -> Self {
+        Self { exclude, include }
+    }
+
+    /// Whether an include pattern could re-admit a path below the excluded
+    /// directory `rel_dir`, so it is still worth walking. A bare name can
+    /// match at any depth; a path pattern only inside the dirs it names.
+    pub(super) fn may_include_below(&self, rel_dir: &str) -> bool {
+        let dir: Vec<&str> = rel_dir.split('/').filter(|s| !s.is_empty()).collect();
+        self.include.iter().any(|pattern| {
+            let pattern = pattern.trim_matches('/');
+            !pattern.contains('/')
+                || could_match_below(&pattern.split('/').collect::<Vec<_>>(), &dir)
+        })
+    }
+
+    /// Whether `rel` is captured, given whether the defaults exclude it.
+    pub(super) fn admits(&self, rel: &str, excluded_by_default: bool) -> bool {
+        if matches_any(&self.include, rel) {
+            true
+        } else if matches_any(&self.exclude, rel) {
+            false
+        } else {
+            !excluded_by_default
+        }
+    }
+}
+
+/// True if a pattern matches `rel` or one of its ancestor directories.
+fn matches_any(patterns: &[String], rel: &str) -> bool {
+    let segments: Vec<&str> = rel.split('/').filter(|s| !s.is_empty()).collect();
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim_matches('/');
+        if pattern.contains('/') {
+            let pattern: Vec<&str> = pattern.split('/').collect();
+            (1..=segments.len()).any(|n| glob_segments(&pattern, &segments[..n]))
+        } else {
+            segments.iter().any(|s| glob_segment(pattern, s))
+        }
+    })
+}
+
+fn glob_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_segments(rest, &path[skip..])),
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(seg, tail)| glob_segment(first, seg) && glob_segments(rest, tail)),
+    }
+}
+
+/// Whether `pattern` matches `dir`, one of its ancestors, or anything below
+/// it.
+fn could_match_below(pattern: &[&str], dir: &[&str]) -> bool {
+    match (pattern.split_first(), dir.split_first()) {
+        (None, _) | (_, None) | (Some((&"**", _)), _) => true,
+        (Some((first, rest)), Some((seg, tail))) => {
+            glob_segment(first, seg) && could_match_below(rest, tail)
+        }
+    }
+}
+
+/// `*` and `?` matching within a single path segment.
+fn glob_segment(pattern: &str, name: &str) -> bool {
+    extraction::matches_pattern(Some(name), pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_match_names_paths_and_ancestors() {
+        let filter = CaptureFilter::new(vec!["logs/**".into(), "*.log".into()], vec![]);
+        assert!(!filter.admits("logs/today/a.txt", false));
+        assert!(!filter.admits("debug.log", false));
+        assert!(!filter.admits("plugins/x/run.log", false));
+        assert!(filter.admits("settings.json", false));
+        assert!(filter.admits("catalogs/a.txt", false));
+
+        let dirs = CaptureFilter::new(vec!["cache".into()], vec!["node_modules/keep".into()]);
+        assert!(!dirs.admits("plugins/cache/blob", false));
+        assert!(dirs.admits("node_modules/keep/index.js", true));
+        assert!(!dirs.admits("node_modules/other/index.js", true));
+    }
+
+    #[test]
+    fn only_dirs_an_include_can_reach_are_walked() {
+        let filter = CaptureFilter::new(vec![], vec!["node_modules/keep".into()]);
+        assert!(filter.may_include_below("node_modules"));
+        assert!(filter.may_include_below("node_modules/keep/lib"));
+        assert!(!filter.may_include_below("node_modules/other"));
+        assert!(!filter.may_include_below("cache"));
+
+        let deep = CaptureFilter::new(vec![], vec!["plugins/**/*.toml".into()]);
+        assert!(deep.may_include_below("plugins/a/b"));
+        assert!(!deep.may_include_below("logs"));
+
+        let by_name = CaptureFilter::new(vec![], vec!["important.log".into()]);
+        assert!(by_name.may_include_below("logs/today"));
+        assert!(!CaptureFilter::default().may_include_below("logs"));
+    }
+}
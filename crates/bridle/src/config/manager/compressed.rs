@@ -0,0 +1,328 @@
+//! Profiles packed into a single `<name>.tar.gz` to save space
+//! (`profile compress`).
+//!
+//! A compressed profile is listed like any other. Showing one unpacks it into
+//! a staging directory; switching to one unpacks it back into a directory
+//! first, since the active profile is saved into on the next switch. Anything
+//! that writes into a profile expands it first, so a new directory never
+//! shadows the archive.
+//!
+//! Archives are read and written by the small ustar implementation below,
+//! which handles only regular files and directories. It should be replaced
+//! by the `tar` crate once that is available as a dependency.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use super::{ProfileManager, retry};
+use crate::config::{BridleConfig, ProfileName};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+/// File name suffix of a compressed profile.
+pub(super) const COMPRESSED_SUFFIX: &str = ".tar.gz";
+
+const BLOCK: usize = 512;
+
+impl ProfileManager {
+    /// Where profile `name` is kept when compressed.
+    pub fn compressed_path(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> PathBuf {
+        self.profiles_dir
+            .join(harness.id())
+            .join(format!("{}{}", name.as_str(), COMPRESSED_SUFFIX))
+    }
+
+    /// Whether profile `name` is stored only as an archive.
+    pub fn is_compressed(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> bool {
+        !self.profile_path(harness, name).exists() && self.compressed_path(harness, name).is_file()
+    }
+
+    /// Packs profile `name` into `<name>.tar.gz` and removes its directory.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile has no directory,
+    /// [`Error::ProfileLocked`] if it is locked, and [`Error::Config`] if it
+    /// is active or holds something other than regular files and directories.
+    pub fn compress_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<PathBuf> {
+        let profile_path = self.profile_path(harness, name);
+        if !profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        if self.is_locked(harness, name) {
+            return Err(Error::ProfileLocked(name.as_str().to_string()));
+        }
+        let config = BridleConfig::load().unwrap_or_default();
+        if config.active_profile_for(&harness.active_key()) == Some(name.as_str()) {
+            return Err(Error::Config(format!(
+                "'{}' is active; switch away before compressing it",
+                name
+            )));
+        }
+
+        let archive = self.compressed_path(harness, name);
+        let partial = archive.with_extension("gz.partial");
+        if let Err(e) = pack(&profile_path, &partial) {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        retry::rename(&partial, &archive, self.fs_retries)?;
+        retry::remove_dir_all(&profile_path, self.fs_retries)?;
+        Ok(archive)
+    }
+
+    /// Turns a compressed profile back into a directory, unpacking next to it
+    /// first so a failed unpack leaves the archive untouched. Does nothing for
+    /// a profile that isn't compressed.
+    ///
+    /// # Errors
+    /// Returns an IO error if the archive can't be unpacked or replaced.
+    pub fn expand_profile(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> Result<()> {
+        if !self.is_compressed(harness, name) {
+            return Ok(());
+        }
+        let profile_path = self.profile_path(harness, name);
+        let staging = profile_path.with_file_name(format!(".{}.expanding", name.as_str()));
+        let _ = fs::remove_dir_all(&staging);
+        if let Err(e) = unpack(&self.compressed_path(harness, name), &staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+        retry::rename(&staging, &profile_path, self.fs_retries)?;
+        retry::remove_file(&self.compressed_path(harness, name), self.fs_retries)?;
+        Ok(())
+    }
+
+    /// Unpacks a compressed profile into a staging directory for reading and
+    /// returns it. The caller removes it when done.
+    pub(super) fn stage_compressed(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<PathBuf> {
+        let staging = self
+            .backups_dir()
+            .join(harness.id())
+            .join("compressed")
+            .join(name.as_str());
+        let _ = fs::remove_dir_all(&staging);
+        unpack(&self.compressed_path(harness, name), &staging)?;
+        Ok(staging)
+    }
+}
+
+/// Writes `dir` as a gzipped ustar archive at `archive`.
+fn pack(dir: &Path, archive: &Path) -> Result<()> {
+    let mut out = GzEncoder::new(fs::File::create(archive)?, Compression::default());
+    append_dir(&mut out, dir, "")?;
+    out.write_all(&[0; BLOCK * 2])?;
+    out.finish()?.sync_all()?;
+    Ok(())
+}
+
+fn append_dir(out: &mut impl Write, dir: &Path, prefix: &str) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel = format!("{prefix}{name}");
+        let meta = fs::symlink_metadata(entry.path())?;
+        if meta.is_dir() {
+            out.write_all(&header(&format!("{rel}/"), b'5', 0, mode(&meta))?)?;
+            append_dir(out, &entry.path(), &format!("{rel}/"))?;
+        } else if meta.is_file() {
+            out.write_all(&header(&rel, b'0', meta.len(), mode(&meta))?)?;
+            let copied = io::copy(&mut fs::File::open(entry.path())?, out)?;
+            out.write_all(&vec![0; padding(copied)])?;
+        } else {
+            return Err(Error::Config(format!(
+                "cannot compress {}: only files and directories are supported",
+                rel
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn mode(meta: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode(meta: &fs::Metadata) -> u32 {
+    if meta.is_dir() { 0o755 } else { 0o644 }
+}
+
+fn padding(len: u64) -> usize {
+    (BLOCK - (len as usize % BLOCK)) % BLOCK
+}
+
+/// A ustar header block. Paths over 100 bytes are split into the 155-byte
+/// prefix field at a `/`.
+fn header(path: &str, kind: u8, size: u64, mode: u32) -> Result<[u8; BLOCK]> {
+    let too_long = || Error::Config(format!("cannot compress {}: path too long", path));
+    let (prefix, name) = if path.len() <= 100 {
+        ("", path)
+    } else {
+        let split = path[..path.len() - 1]
+            .char_indices()
+            .filter(|&(i, c)| c == '/' && i <= 155 && path.len() - i - 1 <= 100)
+            .map(|(i, _)| i)
+            .next()
+            .ok_or_else(too_long)?;
+        (&path[..split], &path[split + 1..])
+    };
+
+    let mut block = [0u8; BLOCK];
+    block[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut block[100..108], u64::from(mode));
+    write_octal(&mut block[108..116], 0);
+    write_octal(&mut block[116..124], 0);
+    write_octal(&mut block[124..136], size);
+    write_octal(&mut block[136..148], 0);
+    block[156] = kind;
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+    block[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    block[148..156].fill(b' ');
+    let sum: u32 = block.iter().map(|&b| u32::from(b)).sum();
+    write_octal(&mut block[148..155], u64::from(sum));
+    Ok(block)
+}
+
+/// Zero-padded octal digits followed by a NUL, filling `field`.
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}\0", value, width = field.len() - 1);
+    field.copy_from_slice(digits.as_bytes());
+}
+
+fn read_octal(field: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(field).ok()?;
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(text, 8).ok()
+}
+
+fn field_str(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Unpacks a gzipped ustar archive into `dest`, rejecting entries that would
+/// land outside it.
+fn unpack(archive: &Path, dest: &Path) -> Result<()> {
+    let corrupt = |why: &str| Error::Config(format!("{}: {}", archive.display(), why));
+    let mut input = GzDecoder::new(fs::File::open(archive)?);
+    fs::create_dir_all(dest)?;
+    let mut block = [0u8; BLOCK];
+    loop {
+        input
+            .read_exact(&mut block)
+            .map_err(|_| corrupt("unexpected end of archive"))?;
+        if block.iter().all(|&b| b == 0) {
+            return Ok(());
+        }
+        let name = field_str(&block[..100]);
+        let prefix = field_str(&block[345..500]);
+        let rel = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let rel = Path::new(rel.trim_end_matches('/'));
+        if !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(corrupt(&format!("unsafe path {}", rel.display())));
+        }
+        let size = read_octal(&block[124..136]).ok_or_else(|| corrupt("bad entry size"))?;
+        let mode = read_octal(&block[100..108]).unwrap_or(0o644) as u32;
+        let path = dest.join(rel);
+        match block[156] {
+            b'5' => fs::create_dir_all(&path)?,
+            b'0' | 0 => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut file = fs::File::create(&path)?;
+                let copied = io::copy(&mut (&mut input).take(size), &mut file)?;
+                if copied != size {
+                    return Err(corrupt("unexpected end of archive"));
+                }
+                io::copy(
+                    &mut (&mut input).take(padding(size) as u64),
+                    &mut io::sink(),
+                )?;
+                set_mode(&path, mode)?;
+            }
+            other => {
+                return Err(corrupt(&format!(
+                    "unsupported entry type '{}'",
+                    other as char
+                )));
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn pack_and_unpack_round_trip_nested_files() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        let deep = format!("skills/{}/{}/SKILL.md", "n".repeat(60), "m".repeat(60));
+        for (rel, contents) in [
+            ("opencode.jsonc", "{}"),
+            ("agent/helper.md", "helper"),
+            (deep.as_str(), "deep"),
+        ] {
+            let path = src.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        fs::create_dir_all(src.join("empty")).unwrap();
+        let archive = temp.path().join("p.tar.gz");
+
+        pack(&src, &archive).unwrap();
+        let out = temp.path().join("out");
+        unpack(&archive, &out).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(out.join("opencode.jsonc")).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            fs::read_to_string(out.join("agent/helper.md")).unwrap(),
+            "helper"
+        );
+        assert_eq!(fs::read_to_string(out.join(&deep)).unwrap(), "deep");
+        assert!(out.join("empty").is_dir());
+    }
+}
@@ -0,0 +1,220 @@
+//! Hardlinking identical files across profiles (`profile.dedupe`).
+//!
+//! Linked files share storage, so anything that writes a profile file in place
+//! must call [`detach_hardlinks`] first. Capture and switch never do: they
+//! remove and recreate files, which replaces the link rather than writing
+//! through it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::retry;
+use crate::error::Result;
+
+/// Files smaller than this are always copied; small configs change often and
+/// linking them saves nothing.
+const DEDUPE_MIN_BYTES: u64 = 4096;
+
+/// Replaces files under `profile_path` with hardlinks to identical files
+/// elsewhere in `store`, returning how many were linked.
+///
+/// Candidates are matched by size and hash, then compared byte for byte. If a
+/// link cannot be made (for example across filesystems) the copy is kept.
+pub(super) fn link_duplicates(profile_path: &Path, store: &Path, retries: u32) -> Result<usize> {
+    let mut own = Vec::new();
+    walk_files(profile_path, &mut own)?;
+    let own: Vec<(PathBuf, u64)> = own
+        .into_iter()
+        .filter(|(_, len)| *len >= DEDUPE_MIN_BYTES)
+        .collect();
+    if own.is_empty() {
+        return Ok(0);
+    }
+    let sizes: HashSet<u64> = own.iter().map(|(_, len)| *len).collect();
+
+    let mut others = Vec::new();
+    walk_files(store, &mut others)?;
+    let mut index: HashMap<(u64, u64), Vec<PathBuf>> = HashMap::new();
+    for (path, len) in others {
+        if path.starts_with(profile_path) || !sizes.contains(&len) {
+            continue;
+        }
+        index
+            .entry((len, hash_file(&path)?))
+            .or_default()
+            .push(path);
+    }
+
+    let mut linked = 0;
+    for (path, len) in own {
+        let Some(candidates) = index.get(&(len, hash_file(&path)?)) else {
+            continue;
+        };
+        for candidate in candidates {
+            if same_file(candidate, &path)? {
+                break;
+            }
+            if same_contents(candidate, &path)? {
+                if replace_with_link(candidate, &path, retries).is_ok() {
+                    linked += 1;
+                }
+                break;
+            }
+        }
+    }
+    Ok(linked)
+}
+
+/// Gives every hardlinked file under `path` (a file or directory) its own copy,
+/// so editing it cannot change another profile.
+pub(super) fn detach_hardlinks(path: &Path, retries: u32) -> Result<()> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        files.push((path.to_path_buf(), 0));
+    } else {
+        walk_files(path, &mut files)?;
+    }
+    for (file, _) in files {
+        if link_count(&file)? > 1 {
+            let tmp = sibling_tmp(&file);
+            fs::copy(&file, &tmp)?;
+            retry::rename(&tmp, &file, retries)?;
+        }
+    }
+    Ok(())
+}
+
+fn walk_files(dir: &Path, out: &mut Vec<(PathBuf, u64)>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk_files(&entry.path(), out)?;
+        } else if file_type.is_file() {
+            out.push((entry.path(), entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<u64> {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut file = fs::File::open(path)?;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+fn same_contents(a: &Path, b: &Path) -> Result<bool> {
+    let (mut a, mut b) = (fs::File::open(a)?, fs::File::open(b)?);
+    let mut buf_a = [0u8; 64 * 1024];
+    let mut buf_b = [0u8; 64 * 1024];
+    loop {
+        let n = a.read(&mut buf_a)?;
+        if n == 0 {
+            return Ok(b.read(&mut buf_b[..1])? == 0);
+        }
+        if b.read_exact(&mut buf_b[..n]).is_err() || buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+fn replace_with_link(existing: &Path, path: &Path, retries: u32) -> Result<()> {
+    let tmp = sibling_tmp(path);
+    fs::hard_link(existing, &tmp)?;
+    if let Err(e) = retry::rename(&tmp, path, retries) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn sibling_tmp(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bridle_link_tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let (a, b) = (fs::metadata(a)?, fs::metadata(b)?);
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+#[cfg(not(unix))]
+fn same_file(_a: &Path, _b: &Path) -> Result<bool> {
+    Ok(false)
+}
+
+#[cfg(unix)]
+fn link_count(path: &Path) -> Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(fs::metadata(path)?.nlink())
+}
+
+#[cfg(not(unix))]
+fn link_count(_path: &Path) -> Result<u64> {
+    Ok(1)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+    use tempfile::TempDir;
+
+    #[test]
+    fn identical_files_become_hardlinks_and_detach_on_edit() {
+        let temp = TempDir::new().unwrap();
+        let store = temp.path();
+        let blob = vec![b'x'; 8192];
+        for profile in ["a", "b"] {
+            fs::create_dir_all(store.join(profile).join("skills")).unwrap();
+            fs::write(store.join(profile).join("skills/big.bin"), &blob).unwrap();
+            fs::write(store.join(profile).join("small.json"), "{}").unwrap();
+        }
+        let a = store.join("a/skills/big.bin");
+        let b = store.join("b/skills/big.bin");
+
+        assert_eq!(link_duplicates(&store.join("b"), store, 0).unwrap(), 1);
+        assert_eq!(
+            fs::metadata(&a).unwrap().ino(),
+            fs::metadata(&b).unwrap().ino()
+        );
+        assert_eq!(fs::metadata(store.join("b/small.json")).unwrap().nlink(), 1);
+
+        detach_hardlinks(&store.join("b"), 0).unwrap();
+        assert_ne!(
+            fs::metadata(&a).unwrap().ino(),
+            fs::metadata(&b).unwrap().ino()
+        );
+        fs::write(&b, "edited").unwrap();
+        assert_eq!(fs::read(&a).unwrap(), blob);
+    }
+
+    #[test]
+    fn differing_files_stay_separate() {
+        let temp = TempDir::new().unwrap();
+        let store = temp.path();
+        fs::create_dir_all(store.join("a")).unwrap();
+        fs::create_dir_all(store.join("b")).unwrap();
+        fs::write(store.join("a/big.bin"), vec![b'x'; 8192]).unwrap();
+        fs::write(store.join("b/big.bin"), vec![b'y'; 8192]).unwrap();
+
+        assert_eq!(link_duplicates(&store.join("b"), store, 0).unwrap(), 0);
+    }
+}
@@ -0,0 +1,289 @@
+//! Which files differ between a profile and another profile, the live config,
+//! or a backup (`profile diff`).
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use super::lifecycle::BACKUP_ACTIVE_FILE;
+use super::{ProfileManager, fingerprint};
+use crate::config::{ProfileName, is_probably_binary_file};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+impl ProfileManager {
+    /// Relative paths of files that differ between profile `name` and
+    /// `other`, or the live config when `other` is `None`, sorted.
+    ///
+    /// Unless `strict` is set, text files are compared ignoring line endings
+    /// and trailing whitespace, so a config re-saved with CRLF on Windows does
+    /// not count as changed. Binary files are always compared byte for byte.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if either profile doesn't exist.
+    pub fn changed_files(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+        other: Option<&ProfileName>,
+        strict: bool,
+    ) -> Result<Vec<String>> {
+        let left = self.stored_profile(harness, name)?;
+        let right = match other {
+            Some(other) => self.stored_profile(harness, other)?,
+            None => harness.config_dir()?,
+        };
+        changed_files_between(&left, &right, strict)
+    }
+
+    /// Relative paths of files that differ between the backup at `backup`
+    /// and profile `name`, or the live config when `name` is `None`, sorted.
+    /// Files are compared as in [`changed_files`](Self::changed_files).
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if `name` doesn't exist.
+    pub fn changed_files_since_backup(
+        &self,
+        harness: &dyn HarnessConfig,
+        backup: &Path,
+        name: Option<&ProfileName>,
+        strict: bool,
+    ) -> Result<Vec<String>> {
+        let current = match name {
+            Some(name) => self.stored_profile(harness, name)?,
+            None => harness.config_dir()?,
+        };
+        let mut changed = changed_files_between(backup, &current, strict)?;
+        changed.retain(|rel| rel != BACKUP_ACTIVE_FILE);
+        Ok(changed)
+    }
+
+    fn stored_profile(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> Result<PathBuf> {
+        let path = self.profile_path(harness, name);
+        if path.is_dir() {
+            Ok(path)
+        } else {
+            Err(Error::ProfileNotFound(name.as_str().to_string()))
+        }
+    }
+}
+
+fn changed_files_between(left: &Path, right: &Path, strict: bool) -> Result<Vec<String>> {
+    let list = |root: &Path| -> Result<BTreeSet<String>> {
+        let mut paths = Vec::new();
+        if root.is_dir() {
+            fingerprint::collect_files(root, "", &mut paths)?;
+        }
+        Ok(paths.into_iter().collect())
+    };
+    let (left_files, right_files) = (list(left)?, list(right)?);
+
+    let mut changed = Vec::new();
+    for rel in left_files.union(&right_files) {
+        let same = left_files.contains(rel)
+            && right_files.contains(rel)
+            && same_contents(&left.join(rel), &right.join(rel), strict)?;
+        if !same {
+            changed.push(rel.clone());
+        }
+    }
+    Ok(changed)
+}
+
+/// Bytes buffered per file while comparing.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Whether the files at `a` and `b` hold the same contents, reading both a
+/// chunk or a line at a time so large files are never loaded whole. Text
+/// with a line longer than [`CHUNK_BYTES`], such as minified JSON, is
+/// compared byte for byte.
+fn same_contents(a: &Path, b: &Path, strict: bool) -> Result<bool> {
+    if strict || is_probably_binary_file(a)? || is_probably_binary_file(b)? {
+        return same_bytes(a, b);
+    }
+    let (mut lines_a, mut lines_b) = (NormalizedLines::open(a)?, NormalizedLines::open(b)?);
+    loop {
+        let (x, y) = (lines_a.next_line()?, lines_b.next_line()?);
+        if lines_a.line_too_long || lines_b.line_too_long {
+            return same_bytes(a, b);
+        }
+        match (x, y) {
+            (None, None) => return Ok(true),
+            (Some(x), Some(y)) if x == y => {}
+            _ => return Ok(false),
+        }
+    }
+}
+
+fn same_bytes(a: &Path, b: &Path) -> Result<bool> {
+    if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut a = BufReader::with_capacity(CHUNK_BYTES, File::open(a)?);
+    let mut b = BufReader::with_capacity(CHUNK_BYTES, File::open(b)?);
+    loop {
+        let (x, y) = (a.fill_buf()?, b.fill_buf()?);
+        if x.is_empty() || y.is_empty() {
+            return Ok(x.is_empty() && y.is_empty());
+        }
+        let n = x.len().min(y.len());
+        if x[..n] != y[..n] {
+            return Ok(false);
+        }
+        a.consume(n);
+        b.consume(n);
+    }
+}
+
+/// The lines of a text file without trailing whitespace (including `\r`),
+/// ignoring blank lines at the end.
+struct NormalizedLines {
+    reader: BufReader<File>,
+    /// Blank lines read but not yet returned; dropped if nothing follows.
+    blank_run: usize,
+    /// The non-blank line that ended the current run of blank lines.
+    held: Option<Vec<u8>>,
+    /// Set, and the lines end, once a line runs past [`CHUNK_BYTES`].
+    line_too_long: bool,
+}
+
+impl NormalizedLines {
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            reader: BufReader::with_capacity(CHUNK_BYTES, File::open(path)?),
+            blank_run: 0,
+            held: None,
+            line_too_long: false,
+        })
+    }
+
+    fn next_line(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.held.is_none() {
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                let limit = CHUNK_BYTES as u64;
+                if (&mut self.reader)
+                    .take(limit)
+                    .read_until(b'\n', &mut line)?
+                    == 0
+                {
+                    return Ok(None);
+                }
+                if line.len() == CHUNK_BYTES && !line.ends_with(b"\n") {
+                    self.line_too_long = true;
+                    return Ok(None);
+                }
+                let trimmed = line.trim_ascii_end();
+                if !trimmed.is_empty() {
+                    self.held = Some(trimmed.to_vec());
+                    break;
+                }
+                self.blank_run += 1;
+            }
+        }
+        if self.blank_run > 0 {
+            self.blank_run -= 1;
+            return Ok(Some(Vec::new()));
+        }
+        Ok(self.held.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn crlf_text_matches_lf_unless_strict() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("settings.json"), "{\n  \"model\": \"x\"\n}\n").unwrap();
+        fs::write(b.join("settings.json"), "{\r\n  \"model\": \"x\"  \r\n}").unwrap();
+        fs::write(a.join("icon.bin"), b"\0\r\n").unwrap();
+        fs::write(b.join("icon.bin"), b"\0\n").unwrap();
+
+        assert_eq!(changed_files_between(&a, &b, false).unwrap(), ["icon.bin"]);
+        assert_eq!(
+            changed_files_between(&a, &b, true).unwrap(),
+            ["icon.bin", "settings.json"]
+        );
+    }
+
+    #[test]
+    fn large_files_differing_in_the_last_byte_are_changed() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        // Many chunks' worth of text, so a difference at the end is only
+        // seen after the readers have moved through the whole file.
+        let mut text = "a line of captured config\n".repeat(CHUNK_BYTES / 2);
+        fs::write(a.join("large.jsonl"), &text).unwrap();
+        fs::write(b.join("same.jsonl"), &text).unwrap();
+        fs::write(a.join("same.jsonl"), &text).unwrap();
+        text.pop();
+        text.push('!');
+        fs::write(b.join("large.jsonl"), &text).unwrap();
+        assert!(text.len() > 8 * CHUNK_BYTES);
+
+        for strict in [false, true] {
+            assert_eq!(
+                changed_files_between(&a, &b, strict).unwrap(),
+                ["large.jsonl"]
+            );
+        }
+    }
+
+    #[test]
+    fn a_single_long_line_is_compared_byte_for_byte() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        let minified = format!("{{\"servers\":[{}]}}", "\"x\",".repeat(CHUNK_BYTES));
+        fs::write(a.join("same.json"), &minified).unwrap();
+        fs::write(b.join("same.json"), &minified).unwrap();
+        fs::write(a.join("edited.json"), &minified).unwrap();
+        fs::write(b.join("edited.json"), minified.replacen('x', "y", 1)).unwrap();
+        // Too long to normalise, so even a trailing newline counts.
+        fs::write(a.join("newline.json"), &minified).unwrap();
+        fs::write(b.join("newline.json"), format!("{minified}\n")).unwrap();
+
+        assert_eq!(
+            changed_files_between(&a, &b, false).unwrap(),
+            ["edited.json", "newline.json"]
+        );
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored_but_inner_ones_are_not() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("end.md"), "x\n\ny").unwrap();
+        fs::write(b.join("end.md"), "x\n  \ny\n\n \n").unwrap();
+        fs::write(a.join("inner.md"), "x\n\ny").unwrap();
+        fs::write(b.join("inner.md"), "x\ny").unwrap();
+
+        assert_eq!(changed_files_between(&a, &b, false).unwrap(), ["inner.md"]);
+    }
+
+    #[test]
+    fn files_on_one_side_only_are_changed() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("missing"));
+        fs::create_dir_all(a.join("skills/review")).unwrap();
+        fs::write(a.join("skills/review/SKILL.md"), "# Review").unwrap();
+
+        assert_eq!(
+            changed_files_between(&a, &b, false).unwrap(),
+            ["skills/review/SKILL.md"]
+        );
+    }
+}
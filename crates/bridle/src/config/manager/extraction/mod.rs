@@ -3,10 +3,27 @@ use std::path::{Path, PathBuf};
 pub use harness_locate::DirectoryStructure;
 use harness_locate::{Harness, Scope};
 
+use crate::config::McpOrder;
 use crate::config::jsonc::strip_jsonc_comments;
-use crate::config::types::{McpServerInfo, ResourceSummary};
+use crate::config::types::{
+    EffectiveModel, FieldValue, McpServerInfo, ModelSource, ProfileInfo, ResourceSummary,
+};
 use crate::error::{Error, Result};
-use crate::harness::HarnessConfig;
+use crate::harness::{CustomHarness, HarnessConfig};
+
+/// Builds a parse error that leads with the file's line and column.
+fn json_parse_error(filename: &str, err: &serde_json::Error) -> Error {
+    let location = format!(" at line {} column {}", err.line(), err.column());
+    let message = err.to_string();
+    let message = message.strip_suffix(&location).unwrap_or(&message);
+    Error::Config(format!(
+        "Failed to parse {} at line {}, column {}: {}",
+        filename,
+        err.line(),
+        err.column(),
+        message
+    ))
+}
 
 pub fn extract_mcp_from_opencode_config(profile_path: &Path) -> Result<Vec<McpServerInfo>> {
     let config_path = profile_path.join("opencode.jsonc");
@@ -18,8 +35,8 @@ pub fn extract_mcp_from_opencode_config(profile_path: &Path) -> Result<Vec<McpSe
         .map_err(|e| Error::Config(format!("Failed to read opencode.jsonc: {}", e)))?;
     let content = strip_jsonc_comments(&content);
 
-    let config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| Error::Config(format!("Failed to parse opencode.jsonc: {}", e)))?;
+    let config: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| json_parse_error("opencode.jsonc", &e))?;
 
     let mcp_obj = match config.get("mcp").and_then(|v| v.as_object()) {
         Some(obj) => obj,
@@ -47,6 +64,7 @@ pub fn extract_mcp_from_opencode_config(profile_path: &Path) -> Result<Vec<McpSe
                 command,
                 args,
                 url,
+                env_keys: json_env_keys(value, "environment"),
             }
         })
         .collect();
@@ -63,8 +81,8 @@ fn extract_mcp_from_crush_config(profile_path: &Path) -> Result<Vec<McpServerInf
     let content = std::fs::read_to_string(&config_path)
         .map_err(|e| Error::Config(format!("Failed to read crush.json: {}", e)))?;
 
-    let config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| Error::Config(format!("Failed to parse crush.json: {}", e)))?;
+    let config: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| json_parse_error("crush.json", &e))?;
 
     let mcp_obj = match config.get("mcp").and_then(|v| v.as_object()) {
         Some(obj) => obj,
@@ -92,6 +110,7 @@ fn extract_mcp_from_crush_config(profile_path: &Path) -> Result<Vec<McpServerInf
                 command,
                 args,
                 url,
+                env_keys: json_env_keys(value, "env"),
             }
         })
         .collect();
@@ -99,17 +118,101 @@ fn extract_mcp_from_crush_config(profile_path: &Path) -> Result<Vec<McpServerInf
     Ok(servers)
 }
 
+/// Names of the variables in the `key` table of a JSON MCP server entry.
+fn json_env_keys(server: &serde_json::Value, key: &str) -> Vec<String> {
+    server
+        .get(key)
+        .and_then(|v| v.as_object())
+        .map(|env| env.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Standalone MCP files that may sit next to a harness's own config.
+const STANDALONE_MCP_FILES: &[&str] = &["mcp.json", ".mcp.json"];
+
+/// Extracts MCP servers from every known location in the profile.
+///
+/// The harness's primary config wins when a name appears in several places.
+/// Returns the merged servers, sorted as `order` asks, plus warnings for names
+/// defined differently across locations, or for secondary files that fail to
+/// parse.
 pub fn extract_mcp_servers(
     harness: &dyn HarnessConfig,
     profile_path: &Path,
-) -> Result<Vec<McpServerInfo>> {
-    match harness.id() {
-        "opencode" => extract_mcp_from_opencode_config(profile_path),
-        "crush" => extract_mcp_from_crush_config(profile_path),
-        "amp-code" => extract_mcp_from_ampcode_config(profile_path),
-        "claude-code" => extract_mcp_from_claudecode_config(profile_path),
-        "goose" => extract_mcp_from_goose_config(profile_path),
-        _ => extract_mcp_generic(harness, profile_path),
+    order: McpOrder,
+) -> Result<(Vec<McpServerInfo>, Vec<String>)> {
+    let (mut servers, primary_file) = match harness.id() {
+        "opencode" => (
+            extract_mcp_from_opencode_config(profile_path)?,
+            Some("opencode.jsonc".to_string()),
+        ),
+        "crush" => (
+            extract_mcp_from_crush_config(profile_path)?,
+            Some("crush.json".to_string()),
+        ),
+        "amp-code" => (
+            extract_mcp_from_ampcode_config(profile_path)?,
+            Some("settings.json".to_string()),
+        ),
+        "claude-code" => (
+            extract_mcp_from_claudecode_config(profile_path)?,
+            Some(".mcp.json".to_string()),
+        ),
+        "goose" => (
+            extract_mcp_from_goose_config(profile_path)?,
+            Some("config.yaml".to_string()),
+        ),
+        "copilot-cli" => (
+            extract_mcp_from_mcp_json(profile_path, "mcp-config.json")?,
+            Some("mcp-config.json".to_string()),
+        ),
+        _ => (
+            extract_mcp_generic(harness, profile_path)?,
+            harness.mcp_filename(),
+        ),
+    };
+
+    let mut warnings = Vec::new();
+    let primary_label = primary_file.as_deref().unwrap_or("config");
+    for file in STANDALONE_MCP_FILES {
+        if primary_file.as_deref() == Some(*file) {
+            continue;
+        }
+        match extract_mcp_from_mcp_json(profile_path, file) {
+            Ok(extra) => merge_mcp_servers(&mut servers, extra, primary_label, file, &mut warnings),
+            Err(e) => warnings.push(format!("MCP config: {}", e)),
+        }
+    }
+
+    if order == McpOrder::Name {
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    Ok((servers, warnings))
+}
+
+/// Adds `extra` servers not already present, warning when a duplicate name differs.
+fn merge_mcp_servers(
+    servers: &mut Vec<McpServerInfo>,
+    extra: Vec<McpServerInfo>,
+    primary_label: &str,
+    extra_label: &str,
+    warnings: &mut Vec<String>,
+) {
+    for server in extra {
+        match servers.iter().find(|s| s.name == server.name) {
+            Some(existing) => {
+                let same = existing.command == server.command
+                    && existing.args == server.args
+                    && existing.url == server.url;
+                if !same {
+                    warnings.push(format!(
+                        "MCP server '{}' is defined differently in {} and {}; using {}",
+                        server.name, primary_label, extra_label, primary_label
+                    ));
+                }
+            }
+            None => servers.push(server),
+        }
     }
 }
 
@@ -138,21 +241,29 @@ fn extract_mcp_generic(
             command: None,
             args: None,
             url: None,
+            env_keys: Vec::new(),
         })
         .collect())
 }
 
 fn extract_mcp_from_claudecode_config(profile_path: &Path) -> Result<Vec<McpServerInfo>> {
-    let config_path = profile_path.join(".mcp.json");
+    extract_mcp_from_mcp_json(profile_path, ".mcp.json")
+}
+
+/// Reads a Claude-style `{"mcpServers": {...}}` file, as also used by Copilot CLI.
+///
+/// Entries without a `type` are treated as stdio servers.
+fn extract_mcp_from_mcp_json(profile_path: &Path, filename: &str) -> Result<Vec<McpServerInfo>> {
+    let config_path = profile_path.join(filename);
     if !config_path.exists() {
         return Ok(Vec::new());
     }
 
     let content = std::fs::read_to_string(&config_path)
-        .map_err(|e| Error::Config(format!("Failed to read .mcp.json: {}", e)))?;
+        .map_err(|e| Error::Config(format!("Failed to read {}: {}", filename, e)))?;
 
-    let config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| Error::Config(format!("Failed to parse .mcp.json: {}", e)))?;
+    let config: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| json_parse_error(filename, &e))?;
 
     let mcp_obj = match config.get("mcpServers").and_then(|v| v.as_object()) {
         Some(obj) => obj,
@@ -176,13 +287,19 @@ fn extract_mcp_from_claudecode_config(profile_path: &Path) -> Result<Vec<McpServ
                     .collect()
             });
             let url = value.get("url").and_then(|v| v.as_str()).map(String::from);
+            let server_type = value
+                .get("type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("stdio")
+                .to_string();
             McpServerInfo {
                 name: name.clone(),
                 enabled: !disabled,
-                server_type: Some("stdio".to_string()),
+                server_type: Some(server_type),
                 command,
                 args,
                 url,
+                env_keys: json_env_keys(value, "env"),
             }
         })
         .collect();
@@ -226,7 +343,12 @@ fn extract_mcp_from_goose_config(profile_path: &Path) -> Result<Vec<McpServerInf
                     .filter_map(|a| a.as_str().map(String::from))
                     .collect()
             });
-            let url = value.get("url").and_then(|v| v.as_str()).map(String::from);
+            // Goose writes remote endpoints as `uri`; accept `url` as well.
+            let url = value
+                .get("uri")
+                .or_else(|| value.get("url"))
+                .and_then(|v| v.as_str())
+                .map(String::from);
             Some(McpServerInfo {
                 name: name_str.to_string(),
                 enabled,
@@ -234,6 +356,15 @@ fn extract_mcp_from_goose_config(profile_path: &Path) -> Result<Vec<McpServerInf
                 command,
                 args,
                 url,
+                env_keys: value
+                    .get("envs")
+                    .and_then(|v| v.as_mapping())
+                    .map(|envs| {
+                        envs.keys()
+                            .filter_map(|k| k.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
             })
         })
         .collect();
@@ -250,8 +381,8 @@ fn extract_mcp_from_ampcode_config(profile_path: &Path) -> Result<Vec<McpServerI
     let content = std::fs::read_to_string(&config_path)
         .map_err(|e| Error::Config(format!("Failed to read settings.json: {}", e)))?;
 
-    let config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| Error::Config(format!("Failed to parse settings.json: {}", e)))?;
+    let config: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| json_parse_error("settings.json", &e))?;
 
     let mcp_obj = match config.get("amp.mcpServers").and_then(|v| v.as_object()) {
         Some(obj) => obj,
@@ -278,6 +409,7 @@ fn extract_mcp_from_ampcode_config(profile_path: &Path) -> Result<Vec<McpServerI
                 command,
                 args,
                 url,
+                env_keys: json_env_keys(value, "env"),
             }
         })
         .collect();
@@ -285,67 +417,93 @@ fn extract_mcp_from_ampcode_config(profile_path: &Path) -> Result<Vec<McpServerI
     Ok(servers)
 }
 
-pub fn extract_theme(harness: &dyn HarnessConfig, profile_path: &Path) -> Option<String> {
+/// Reads a setting from the config file `file` in `profile_path` with `read`,
+/// telling a missing file apart from an unset key.
+fn config_field(
+    profile_path: &Path,
+    file: &str,
+    read: impl FnOnce(&str) -> Option<String>,
+) -> FieldValue {
+    match std::fs::read_to_string(profile_path.join(file)) {
+        Ok(content) => read(&content).map_or(FieldValue::Unset, FieldValue::Set),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => FieldValue::Missing,
+        Err(_) => FieldValue::Unset,
+    }
+}
+
+fn json_str(content: &str, key: &str) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(content).ok()?;
+    parsed.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+pub fn extract_theme(harness: &dyn HarnessConfig, profile_path: &Path) -> FieldValue {
     match harness.id() {
-        "opencode" => {
-            let config_path = profile_path.join("opencode.jsonc");
-            if !config_path.exists() {
-                return None;
-            }
-            let content = std::fs::read_to_string(&config_path).ok()?;
-            let clean_json = strip_jsonc_comments(&content);
-            let parsed: serde_json::Value = serde_json::from_str(&clean_json).ok()?;
-            parsed
-                .get("theme")
-                .and_then(|v| v.as_str())
-                .map(String::from)
-        }
-        "goose" => {
-            let config_path = profile_path.join("config.yaml");
-            let content = std::fs::read_to_string(&config_path).ok()?;
-            let parsed: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+        "opencode" => config_field(profile_path, "opencode.jsonc", |content| {
+            json_str(&strip_jsonc_comments(content), "theme")
+        }),
+        "goose" => config_field(profile_path, "config.yaml", |content| {
+            let parsed: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
             parsed
                 .get("GOOSE_CLI_THEME")
                 .and_then(|v| v.as_str())
                 .map(String::from)
-        }
-        "amp-code" => {
-            let config_path = profile_path.join("settings.json");
-            let content = std::fs::read_to_string(&config_path).ok()?;
-            let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-            parsed
-                .get("amp.theme")
-                .and_then(|v| v.as_str())
-                .map(String::from)
-        }
-        "claude-code" => {
-            let config_path = profile_path.join("settings.json");
-            let content = std::fs::read_to_string(&config_path).ok()?;
-            let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-            parsed
-                .get("theme")
-                .and_then(|v| v.as_str())
-                .map(String::from)
-        }
-        _ => None,
+        }),
+        "amp-code" => config_field(profile_path, "settings.json", |content| {
+            json_str(content, "amp.theme")
+        }),
+        "claude-code" => config_field(profile_path, "settings.json", |content| {
+            json_str(content, "theme")
+        }),
+        _ => FieldValue::Unset,
     }
 }
 
-pub fn extract_model(harness: &dyn HarnessConfig, profile_path: &Path) -> Option<String> {
+pub fn extract_model(harness: &dyn HarnessConfig, profile_path: &Path) -> FieldValue {
     match harness.id() {
-        "opencode" => extract_model_opencode(profile_path),
-        "claude-code" => extract_model_claude_code(profile_path),
-        "goose" => extract_model_goose(profile_path),
-        "amp-code" => extract_model_ampcode(profile_path),
-        "crush" => extract_model_crush(profile_path),
+        "opencode" => config_field(profile_path, "opencode.jsonc", model_opencode),
+        "claude-code" => config_field(profile_path, "settings.json", |c| json_str(c, "model")),
+        "goose" => config_field(profile_path, "config.yaml", model_goose),
+        "amp-code" => config_field(profile_path, "settings.json", model_ampcode),
+        "crush" => config_field(profile_path, "crush.json", model_crush),
+        "copilot-cli" => config_field(profile_path, "config.json", |c| json_str(c, "model")),
+        _ => FieldValue::Unset,
+    }
+}
+
+/// Environment variable that overrides the configured model, per harness.
+fn model_env_var(harness_id: &str) -> Option<&'static str> {
+    match harness_id {
+        "goose" => Some("GOOSE_MODEL"),
+        "claude-code" => Some("ANTHROPIC_MODEL"),
         _ => None,
     }
 }
 
-fn extract_model_opencode(profile_path: &Path) -> Option<String> {
-    let config_path = profile_path.join("opencode.jsonc");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let clean_json = strip_jsonc_comments(&content);
+/// Layers the harness's model env var, as returned by `lookup`, over the
+/// model from its config file.
+pub fn effective_model(
+    harness_id: &str,
+    config_model: Option<&str>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<EffectiveModel> {
+    let env = model_env_var(harness_id)
+        .and_then(|var| lookup(var).filter(|v| !v.is_empty()).map(|v| (var, v)));
+    match env {
+        Some((var, model)) => Some(EffectiveModel {
+            model,
+            source: ModelSource::Env,
+            env_var: Some(var.to_string()),
+        }),
+        None => config_model.map(|model| EffectiveModel {
+            model: model.to_string(),
+            source: ModelSource::Config,
+            env_var: None,
+        }),
+    }
+}
+
+fn model_opencode(content: &str) -> Option<String> {
+    let clean_json = strip_jsonc_comments(content);
     let parsed: serde_json::Value = serde_json::from_str(&clean_json).ok()?;
 
     parsed
@@ -361,30 +519,16 @@ fn extract_model_opencode(profile_path: &Path) -> Option<String> {
         .map(String::from)
 }
 
-fn extract_model_claude_code(profile_path: &Path) -> Option<String> {
-    let config_path = profile_path.join("settings.json");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-    parsed
-        .get("model")
-        .and_then(|v| v.as_str())
-        .map(String::from)
-}
-
-fn extract_model_goose(profile_path: &Path) -> Option<String> {
-    let config_path = profile_path.join("config.yaml");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let parsed: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+fn model_goose(content: &str) -> Option<String> {
+    let parsed: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
     parsed
         .get("GOOSE_MODEL")
         .and_then(|v| v.as_str())
         .map(String::from)
 }
 
-fn extract_model_ampcode(profile_path: &Path) -> Option<String> {
-    let config_path = profile_path.join("settings.json");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
+fn model_ampcode(content: &str) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(content).ok()?;
 
     // AMP Code uses dotted keys like "amp.model.default" directly containing the model name
     if let Some(model) = parsed.get("amp.model.default").and_then(|v| v.as_str()) {
@@ -399,10 +543,8 @@ fn extract_model_ampcode(profile_path: &Path) -> Option<String> {
         .map(String::from)
 }
 
-fn extract_model_crush(profile_path: &Path) -> Option<String> {
-    let config_path = profile_path.join("crush.json");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
+fn model_crush(content: &str) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(content).ok()?;
 
     parsed
         .get("model")
@@ -424,7 +566,7 @@ fn extract_model_crush(profile_path: &Path) -> Option<String> {
         .map(String::from)
 }
 
-fn dir_name_from_path(path: &Path) -> &str {
+pub(super) fn dir_name_from_path(path: &Path) -> &str {
     path.file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("skills")
@@ -439,15 +581,90 @@ fn fallback_dir_name(primary: &str) -> Option<&'static str> {
     }
 }
 
-pub fn extract_skills(harness: &Harness, profile_path: &Path) -> (ResourceSummary, Option<String>) {
+/// Fills `info` with the skills, commands, plugins, agents, and rules file of
+/// a built-in harness, recording failures in `info.extraction_errors`.
+///
+/// Resource directory names are those of `scope`, and nested skills are
+/// searched `skill_depth` directories deep.
+pub fn extract_builtin_resources(
+    harness: &Harness,
+    scope: &Scope,
+    profile_path: &Path,
+    skill_depth: usize,
+    info: &mut ProfileInfo,
+) {
+    let mut errors = Vec::new();
+    let mut record = |err: Option<String>| errors.extend(err);
+
+    let (skills, err) = extract_skills(harness, scope, profile_path, skill_depth);
+    record(err);
+    let (commands, err) = extract_commands(harness, scope, profile_path);
+    record(err);
+    let (plugins, err) = extract_plugins(harness, scope, profile_path);
+    record(err);
+    let (agents, err) = extract_agents(harness, scope, profile_path);
+    record(err);
+    let (rules_file, err) = extract_rules_file(harness, scope, profile_path);
+    record(err);
+
+    info.skills = skills;
+    info.commands = commands;
+    info.plugins = plugins;
+    info.agents = agents;
+    info.rules_file = rules_file;
+    info.extraction_errors.extend(errors);
+}
+
+/// Fills `info` with the resources a custom harness's descriptor declares.
+///
+/// Only the declared directories and patterns are looked at: skills are
+/// directories holding the descriptor's `skill_file`, agents and commands
+/// are files matching its `agents_pattern` and `commands_pattern`.
+pub fn extract_custom_resources(
+    harness: &CustomHarness,
+    profile_path: &Path,
+    info: &mut ProfileInfo,
+) {
+    let descriptor = harness.descriptor();
+    let flat = |pattern: &str| DirectoryStructure::Flat {
+        file_pattern: pattern.to_string(),
+    };
+    let summary = |dir: &Option<String>, structure: &DirectoryStructure| {
+        dir.as_deref()
+            .map(|d| extract_resource_summary(profile_path, d, structure))
+    };
+
+    info.skills = summary(
+        &descriptor.skills_dir,
+        &DirectoryStructure::Nested {
+            subdir_pattern: "*".to_string(),
+            file_name: descriptor.skill_file.clone(),
+        },
+    )
+    .unwrap_or_default();
+    info.commands = summary(
+        &descriptor.commands_dir,
+        &flat(&descriptor.commands_pattern),
+    )
+    .unwrap_or_default();
+    info.agents = summary(&descriptor.agents_dir, &flat(&descriptor.agents_pattern));
+}
+
+pub fn extract_skills(
+    harness: &Harness,
+    scope: &Scope,
+    profile_path: &Path,
+    depth: usize,
+) -> (ResourceSummary, Option<String>) {
     if harness.id() == "amp-code" {
         return extract_ampcode_skills(profile_path);
     }
 
-    match harness.skills(&Scope::Global) {
+    match harness.skills(scope) {
         Ok(Some(dir)) => {
             let subdir = dir_name_from_path(&dir.path);
-            let summary = extract_resource_summary(profile_path, subdir, &dir.structure);
+            let summary =
+                extract_resource_summary_with_depth(profile_path, subdir, &dir.structure, depth);
             if !summary.items.is_empty() {
                 return (summary, None);
             }
@@ -462,8 +679,12 @@ pub fn extract_skills(harness: &Harness, profile_path: &Path) -> (ResourceSummar
                 return (md_summary, None);
             }
             if let Some(fallback) = fallback_dir_name(subdir) {
-                let fallback_summary =
-                    extract_resource_summary(profile_path, fallback, &dir.structure);
+                let fallback_summary = extract_resource_summary_with_depth(
+                    profile_path,
+                    fallback,
+                    &dir.structure,
+                    depth,
+                );
                 if !fallback_summary.items.is_empty() {
                     return (fallback_summary, None);
                 }
@@ -488,6 +709,7 @@ fn extract_ampcode_skills(profile_path: &Path) -> (ResourceSummary, Option<Strin
                 ResourceSummary {
                     items: Vec::new(),
                     directory_exists: true,
+                    linked: Vec::new(),
                 },
                 Some(format!("skills: {}", e)),
             );
@@ -505,6 +727,7 @@ fn extract_ampcode_skills(profile_path: &Path) -> (ResourceSummary, Option<Strin
         ResourceSummary {
             items,
             directory_exists: true,
+            linked: Vec::new(),
         },
         None,
     )
@@ -512,6 +735,7 @@ fn extract_ampcode_skills(profile_path: &Path) -> (ResourceSummary, Option<Strin
 
 pub fn extract_commands(
     harness: &Harness,
+    scope: &Scope,
     profile_path: &Path,
 ) -> (ResourceSummary, Option<String>) {
     if harness.id() == "goose" {
@@ -522,7 +746,7 @@ pub fn extract_commands(
         return extract_ampcode_commands(profile_path);
     }
 
-    let dir_result = match harness.commands(&Scope::Global) {
+    let dir_result = match harness.commands(scope) {
         Ok(Some(dir)) => {
             let subdir = dir_name_from_path(&dir.path);
             let summary = extract_resource_summary(profile_path, subdir, &dir.structure);
@@ -554,6 +778,7 @@ pub fn extract_commands(
             ResourceSummary {
                 items: merged_items,
                 directory_exists: dir_result.0.directory_exists || config_summary.directory_exists,
+                linked: Vec::new(),
             },
             dir_result.1.or(config_err),
         );
@@ -589,6 +814,7 @@ fn extract_commands_from_opencode_config(profile_path: &Path) -> (ResourceSummar
         ResourceSummary {
             items: commands,
             directory_exists: false,
+            linked: Vec::new(),
         },
         None,
     )
@@ -612,6 +838,7 @@ fn extract_goose_recipes(profile_path: &Path) -> (ResourceSummary, Option<String
                 ResourceSummary {
                     items: Vec::new(),
                     directory_exists: true,
+                    linked: Vec::new(),
                 },
                 Some(format!("recipes: {}", e)),
             );
@@ -621,12 +848,7 @@ fn extract_goose_recipes(profile_path: &Path) -> (ResourceSummary, Option<String
     let items: Vec<String> = entries
         .filter_map(|e| e.ok())
         .filter(|e| {
-            let path = e.path();
-            path.is_file()
-                && matches!(
-                    path.extension().and_then(|ext| ext.to_str()),
-                    Some("yaml") | Some("yml") | Some("json") | Some("md")
-                )
+            e.path().is_file() && matches_pattern(e.file_name().to_str(), "*.{yaml,yml,json,md}")
         })
         .filter_map(|e| {
             e.path()
@@ -640,6 +862,7 @@ fn extract_goose_recipes(profile_path: &Path) -> (ResourceSummary, Option<String
         ResourceSummary {
             items,
             directory_exists: true,
+            linked: Vec::new(),
         },
         None,
     )
@@ -658,6 +881,7 @@ fn extract_ampcode_commands(profile_path: &Path) -> (ResourceSummary, Option<Str
                 ResourceSummary {
                     items: Vec::new(),
                     directory_exists: true,
+                    linked: Vec::new(),
                 },
                 Some(format!("commands: {}", e)),
             );
@@ -679,6 +903,7 @@ fn extract_ampcode_commands(profile_path: &Path) -> (ResourceSummary, Option<Str
         ResourceSummary {
             items,
             directory_exists: true,
+            linked: Vec::new(),
         },
         None,
     )
@@ -686,6 +911,7 @@ fn extract_ampcode_commands(profile_path: &Path) -> (ResourceSummary, Option<Str
 
 pub fn extract_plugins(
     harness: &Harness,
+    scope: &Scope,
     profile_path: &Path,
 ) -> (Option<ResourceSummary>, Option<String>) {
     if harness.id() == "opencode" {
@@ -696,7 +922,7 @@ pub fn extract_plugins(
         return extract_claude_code_plugins(profile_path);
     }
 
-    match harness.plugins(&Scope::Global) {
+    match harness.plugins(scope) {
         Ok(Some(dir)) => (
             Some(extract_resource_summary(
                 profile_path,
@@ -746,6 +972,7 @@ fn extract_plugins_from_opencode_config(
             Some(ResourceSummary {
                 items: plugins,
                 directory_exists: true,
+                linked: Vec::new(),
             }),
             None,
         )
@@ -772,6 +999,7 @@ fn extract_claude_code_plugins(profile_path: &Path) -> (Option<ResourceSummary>,
                 Some(ResourceSummary {
                     items: Vec::new(),
                     directory_exists: true,
+                    linked: Vec::new(),
                 }),
                 Some(format!("plugins: {}", e)),
             );
@@ -792,6 +1020,7 @@ fn extract_claude_code_plugins(profile_path: &Path) -> (Option<ResourceSummary>,
             Some(ResourceSummary {
                 items,
                 directory_exists: true,
+                linked: Vec::new(),
             }),
             None,
         )
@@ -825,6 +1054,7 @@ fn parse_marketplace_json(path: &Path) -> Option<(Option<ResourceSummary>, Optio
             Some(ResourceSummary {
                 items: plugins,
                 directory_exists: true,
+                linked: Vec::new(),
             }),
             None,
         ))
@@ -833,9 +1063,10 @@ fn parse_marketplace_json(path: &Path) -> Option<(Option<ResourceSummary>, Optio
 
 pub fn extract_agents(
     harness: &Harness,
+    scope: &Scope,
     profile_path: &Path,
 ) -> (Option<ResourceSummary>, Option<String>) {
-    let dir_result = match harness.agents(&Scope::Global) {
+    let dir_result = match harness.agents(scope) {
         Ok(Some(dir)) => {
             let subdir = dir_name_from_path(&dir.path);
             let summary = extract_resource_summary(profile_path, subdir, &dir.structure);
@@ -887,6 +1118,7 @@ pub fn extract_agents(
                         .as_ref()
                         .map(|s| s.directory_exists)
                         .unwrap_or(false),
+                    linked: Vec::new(),
                 }),
                 dir_result.1.or(config_err),
             );
@@ -923,13 +1155,17 @@ fn extract_agents_from_opencode_config(profile_path: &Path) -> (ResourceSummary,
         ResourceSummary {
             items: agents,
             directory_exists: false,
+            linked: Vec::new(),
         },
         None,
     )
 }
 
+/// Directories searched for agents when a built-in harness reports none.
+const FALLBACK_AGENT_DIRS: &[&str] = &["agent", "agents"];
+
 fn extract_agents_fallback(profile_path: &Path) -> (Option<ResourceSummary>, Option<String>) {
-    for subdir in ["agent", "agents"] {
+    for &subdir in FALLBACK_AGENT_DIRS {
         let dir_path = profile_path.join(subdir);
         if dir_path.exists() && dir_path.is_dir() {
             let summary = extract_resource_summary(
@@ -949,9 +1185,10 @@ fn extract_agents_fallback(profile_path: &Path) -> (Option<ResourceSummary>, Opt
 
 pub fn extract_rules_file(
     harness: &Harness,
+    scope: &Scope,
     profile_path: &Path,
 ) -> (Option<PathBuf>, Option<String>) {
-    match harness.rules(&Scope::Global) {
+    match harness.rules(scope) {
         Ok(Some(dir)) => {
             let rules_path = match &dir.structure {
                 DirectoryStructure::Flat { file_pattern } => {
@@ -974,6 +1211,27 @@ pub fn extract_rules_file(
     }
 }
 
+/// Names of the keys a `.env` file in `profile_path` declares, in file
+/// order. Comments, blank lines and anything that isn't `KEY=VALUE` are
+/// skipped, an `export ` prefix is dropped, and values are never kept.
+pub fn extract_env_file_keys(profile_path: &Path) -> (Vec<String>, Option<String>) {
+    let contents = match std::fs::read_to_string(profile_path.join(".env")) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return (Vec::new(), None),
+        Err(e) => return (Vec::new(), Some(format!(".env: {}", e))),
+    };
+    let keys = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, _)| key.strip_prefix("export ").unwrap_or(key).trim())
+        .filter(|key| !key.is_empty() && !key.contains(char::is_whitespace))
+        .map(String::from)
+        .collect();
+    (keys, None)
+}
+
 fn find_first_matching_file(dir: &Path, pattern: &str) -> Option<PathBuf> {
     let mut matches: Vec<PathBuf> = std::fs::read_dir(dir)
         .ok()?
@@ -986,27 +1244,62 @@ fn find_first_matching_file(dir: &Path, pattern: &str) -> Option<PathBuf> {
     matches.into_iter().next()
 }
 
+/// Whether `filename` matches `pattern`, where `*` matches any run of
+/// characters and `?` any one, so `*.md`, `draft-*`, and `v?.json` all work.
+/// A `{a,b}` group matches any of its alternatives, so `*.{md,mdx}` matches
+/// both extensions.
 pub fn matches_pattern(filename: Option<&str>, pattern: &str) -> bool {
     let Some(name) = filename else { return false };
-    if pattern == "*" {
-        return true;
-    }
-    if let Some(suffix) = pattern.strip_prefix("*.") {
-        return name.ends_with(&format!(".{}", suffix));
-    }
-    if let Some(suffix) = pattern.strip_prefix('*') {
-        return name.ends_with(suffix);
+    if let Some((open, close)) = pattern
+        .find('{')
+        .and_then(|open| Some((open, open + pattern[open..].find('}')?)))
+    {
+        let (head, tail) = (&pattern[..open], &pattern[close + 1..]);
+        return pattern[open + 1..close]
+            .split(',')
+            .any(|alt| matches_pattern(Some(name), &format!("{head}{alt}{tail}")));
     }
-    if let Some(prefix) = pattern.strip_suffix('*') {
-        return name.starts_with(prefix);
+    let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut pi, mut ni) = (0, 0);
+    let mut backtrack = None;
+    while ni < n.len() {
+        match p.get(pi) {
+            Some('*') => {
+                backtrack = Some((pi, ni));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == n[ni] => {
+                pi += 1;
+                ni += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    pi = star + 1;
+                    ni = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
     }
-    name == pattern
+    p[pi..].iter().all(|&c| c == '*')
 }
 
 pub fn extract_resource_summary(
     base_path: &Path,
     subdir: &str,
     structure: &DirectoryStructure,
+) -> ResourceSummary {
+    extract_resource_summary_with_depth(base_path, subdir, structure, 1)
+}
+
+/// Like [`extract_resource_summary`], searching `Nested` layouts up to `depth`
+/// directories deep (see [`list_nested_subdirs_with_file`]).
+pub fn extract_resource_summary_with_depth(
+    base_path: &Path,
+    subdir: &str,
+    structure: &DirectoryStructure,
+    depth: usize,
 ) -> ResourceSummary {
     let dir_path = base_path.join(subdir);
 
@@ -1014,6 +1307,7 @@ pub fn extract_resource_summary(
         return ResourceSummary {
             items: vec![],
             directory_exists: false,
+            linked: Vec::new(),
         };
     }
 
@@ -1022,12 +1316,13 @@ pub fn extract_resource_summary(
         DirectoryStructure::Nested {
             subdir_pattern,
             file_name,
-        } => list_subdirs_with_file(&dir_path, subdir_pattern, file_name),
+        } => list_nested_subdirs_with_file(&dir_path, subdir_pattern, file_name, depth),
     };
 
     ResourceSummary {
         items,
         directory_exists: true,
+        linked: Vec::new(),
     }
 }
 
@@ -1048,20 +1343,53 @@ pub fn list_files_matching(dir: &Path, pattern: &str) -> Vec<String> {
 }
 
 pub fn list_subdirs_with_file(dir: &Path, subdir_pattern: &str, file_name: &str) -> Vec<String> {
-    std::fs::read_dir(dir)
-        .ok()
-        .map(|entries| {
-            let mut items: Vec<String> = entries
-                .filter_map(|e| e.ok())
-                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
-                .filter(|e| matches_pattern(e.file_name().to_str(), subdir_pattern))
-                .filter(|e| e.path().join(file_name).exists())
-                .filter_map(|e| e.file_name().to_str().map(String::from))
-                .collect();
-            items.sort();
-            items
-        })
-        .unwrap_or_default()
+    list_nested_subdirs_with_file(dir, subdir_pattern, file_name, 1)
+}
+
+/// Lists subdirectories holding `file_name`, descending into directories that
+/// don't hold it up to `depth` levels, so `skills/<cat>/<skill>/SKILL.md` is
+/// found at depth 2 and named `cat/skill`.
+pub fn list_nested_subdirs_with_file(
+    dir: &Path,
+    subdir_pattern: &str,
+    file_name: &str,
+    depth: usize,
+) -> Vec<String> {
+    fn walk(
+        dir: &Path,
+        prefix: &str,
+        pattern: &str,
+        file_name: &str,
+        depth: usize,
+        out: &mut Vec<String>,
+    ) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if !matches_pattern(Some(&name), pattern) {
+                continue;
+            }
+            let qualified = format!("{prefix}{name}");
+            if entry.path().join(file_name).exists() {
+                out.push(qualified);
+            } else if depth > 1 {
+                let prefix = format!("{qualified}/");
+                walk(&entry.path(), &prefix, pattern, file_name, depth - 1, out);
+            }
+        }
+    }
+
+    let mut items = Vec::new();
+    walk(dir, "", subdir_pattern, file_name, depth, &mut items);
+    items.sort();
+    items
 }
 
 #[cfg(test)]
@@ -1069,6 +1397,279 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    struct IdOnly(&'static str);
+
+    impl HarnessConfig for IdOnly {
+        fn id(&self) -> &str {
+            self.0
+        }
+        fn config_dir(&self) -> Result<PathBuf> {
+            unreachable!()
+        }
+        fn installation_status(&self) -> Result<harness_locate::InstallationStatus> {
+            unreachable!()
+        }
+        fn mcp_filename(&self) -> Option<String> {
+            None
+        }
+        fn mcp_config_path(&self) -> Option<PathBuf> {
+            None
+        }
+        fn parse_mcp_servers(&self, _: &str, _: &str) -> Result<Vec<(String, bool)>> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn mcp_defined_in_two_places_merges_with_conflict_warning() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("opencode.jsonc"),
+            r#"{"mcp": {"fs": {"type": "local", "command": "fs-inline"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("mcp.json"),
+            r#"{"mcpServers": {
+                "fs": {"command": "fs-standalone"},
+                "git": {"command": "git-mcp"}
+            }}"#,
+        )
+        .unwrap();
+
+        let (servers, warnings) =
+            extract_mcp_servers(&IdOnly("opencode"), temp.path(), McpOrder::Name).unwrap();
+
+        let names: Vec<&str> = servers.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["fs", "git"]);
+        assert_eq!(servers[0].command.as_deref(), Some("fs-inline"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'fs'"), "{warnings:?}");
+    }
+
+    #[test]
+    fn identical_duplicate_mcp_is_not_a_conflict() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(".mcp.json"),
+            r#"{"mcpServers": {"fs": {"command": "fs-mcp"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("mcp.json"),
+            r#"{"mcpServers": {"fs": {"command": "fs-mcp"}}}"#,
+        )
+        .unwrap();
+
+        let (servers, warnings) =
+            extract_mcp_servers(&IdOnly("claude-code"), temp.path(), McpOrder::Name).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn mcp_servers_are_sorted_by_name_unless_file_order() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(".mcp.json"),
+            r#"{"mcpServers": {
+                "zeta": {"command": "z"}, "gamma": {"command": "g"}, "mu": {"command": "m"}
+            }}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("mcp.json"),
+            r#"{"mcpServers": {"alpha": {"command": "a"}}}"#,
+        )
+        .unwrap();
+        let names = |order| {
+            let (servers, _) =
+                extract_mcp_servers(&IdOnly("claude-code"), temp.path(), order).unwrap();
+            servers.into_iter().map(|s| s.name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(McpOrder::Name), ["alpha", "gamma", "mu", "zeta"]);
+        assert_eq!(names(McpOrder::File), ["zeta", "gamma", "mu", "alpha"]);
+    }
+
+    #[test]
+    fn copilot_cli_extracts_model_and_mcp() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("config.json"),
+            r#"{"model": "claude-sonnet-4.5", "trusted_folders": []}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("mcp-config.json"),
+            r#"{"mcpServers": {
+                "github": {"type": "http", "url": "https://api.githubcopilot.com/mcp/"},
+                "fs": {"type": "local", "command": "npx", "args": ["-y", "server-fs"]}
+            }}"#,
+        )
+        .unwrap();
+
+        let harness = IdOnly("copilot-cli");
+        assert_eq!(
+            extract_model(&harness, temp.path()).as_deref(),
+            Some("claude-sonnet-4.5")
+        );
+        assert_eq!(extract_theme(&harness, temp.path()), FieldValue::Unset);
+
+        let (servers, warnings) =
+            extract_mcp_servers(&harness, temp.path(), McpOrder::Name).unwrap();
+        assert!(warnings.is_empty());
+        let fs = servers.iter().find(|s| s.name == "fs").unwrap();
+        assert_eq!(fs.server_type.as_deref(), Some("local"));
+        assert_eq!(fs.command.as_deref(), Some("npx"));
+        let github = servers.iter().find(|s| s.name == "github").unwrap();
+        assert_eq!(
+            github.url.as_deref(),
+            Some("https://api.githubcopilot.com/mcp/")
+        );
+    }
+
+    #[test]
+    fn ampcode_default_model_is_returned_without_tier_key() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("settings.json"),
+            r#"{"amp.model.default": "claude-sonnet-4"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            extract_model(&IdOnly("amp-code"), temp.path()).as_deref(),
+            Some("claude-sonnet-4")
+        );
+    }
+
+    #[test]
+    fn goose_model_env_var_overrides_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("config.yaml"), "GOOSE_MODEL: gpt-4o\n").unwrap();
+        let config_model = extract_model(&IdOnly("goose"), temp.path());
+        assert_eq!(config_model.as_deref(), Some("gpt-4o"));
+
+        let env = |var: &str| (var == "GOOSE_MODEL").then(|| "claude-sonnet-4".to_string());
+        let effective = effective_model("goose", config_model.as_deref(), env).unwrap();
+        assert_eq!(effective.model, "claude-sonnet-4");
+        assert_eq!(effective.source.label(), "from env");
+        assert_eq!(effective.env_var.as_deref(), Some("GOOSE_MODEL"));
+
+        let effective = effective_model("goose", config_model.as_deref(), |_| None).unwrap();
+        assert_eq!(effective.model, "gpt-4o");
+        assert_eq!(effective.source.label(), "from config");
+    }
+
+    #[test]
+    fn opencode_missing_config_differs_from_unset_keys() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let harness = IdOnly("opencode");
+        assert_eq!(extract_theme(&harness, temp.path()), FieldValue::Missing);
+        assert_eq!(extract_model(&harness, temp.path()), FieldValue::Missing);
+
+        std::fs::write(
+            temp.path().join("opencode.jsonc"),
+            "{\n  // no theme\n  \"model\": \"gpt-4o\"\n}",
+        )
+        .unwrap();
+        assert_eq!(extract_theme(&harness, temp.path()), FieldValue::Unset);
+        assert_eq!(
+            extract_model(&harness, temp.path()),
+            FieldValue::Set("gpt-4o".to_string())
+        );
+    }
+
+    #[test]
+    fn copilot_cli_without_config_has_missing_model_and_no_servers() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let harness = IdOnly("copilot-cli");
+        assert_eq!(extract_model(&harness, temp.path()), FieldValue::Missing);
+        let (servers, _) = extract_mcp_servers(&harness, temp.path(), McpOrder::Name).unwrap();
+        assert!(servers.is_empty());
+    }
+
+    #[test]
+    fn malformed_jsonc_error_reports_original_line() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("opencode.jsonc"),
+            "{\n  /* servers\n     below */\n  \"mcp\": {\n    \"fs\": {\"command\": }\n  }\n}",
+        )
+        .unwrap();
+
+        let err = extract_mcp_from_opencode_config(temp.path()).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("opencode.jsonc at line 5, column"), "{msg}");
+    }
+
+    #[test]
+    fn goose_extensions_map_to_mcp_servers() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("config.yaml"),
+            r#"GOOSE_MODEL: gpt-4o
+extensions:
+  developer:
+    type: builtin
+    enabled: true
+  github:
+    type: stdio
+    cmd: npx
+    args: ["-y", "@modelcontextprotocol/server-github"]
+    enabled: true
+  search:
+    type: sse
+    uri: http://localhost:8080/sse
+    enabled: false
+"#,
+        )
+        .unwrap();
+
+        let (servers, _) =
+            extract_mcp_servers(&IdOnly("goose"), temp.path(), McpOrder::Name).unwrap();
+
+        assert_eq!(servers.len(), 2, "builtin extensions are not MCP servers");
+        let github = servers.iter().find(|s| s.name == "github").unwrap();
+        assert!(github.enabled);
+        assert_eq!(github.server_type.as_deref(), Some("stdio"));
+        assert_eq!(github.command.as_deref(), Some("npx"));
+        assert_eq!(github.args.as_ref().map(Vec::len), Some(2));
+        let search = servers.iter().find(|s| s.name == "search").unwrap();
+        assert!(!search.enabled);
+        assert_eq!(search.server_type.as_deref(), Some("sse"));
+        assert_eq!(search.url.as_deref(), Some("http://localhost:8080/sse"));
+    }
+
+    #[test]
+    fn env_file_surfaces_only_key_names() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(extract_env_file_keys(temp.path()), (vec![], None));
+
+        std::fs::write(
+            temp.path().join(".env"),
+            "# API credentials\n\nOPENAI_API_KEY=sk-secret\n  # GITHUB_TOKEN=ghp-old\nexport GITHUB_TOKEN = ghp-new\nnot a pair\nEMPTY=\n",
+        )
+        .unwrap();
+
+        let (keys, err) = extract_env_file_keys(temp.path());
+        assert_eq!(keys, ["OPENAI_API_KEY", "GITHUB_TOKEN", "EMPTY"]);
+        assert!(err.is_none());
+    }
+
+    #[test]
+    fn brace_patterns_match_any_alternative() {
+        let pattern = "*.{md,mdx}";
+        assert!(matches_pattern(Some("review.md"), pattern));
+        assert!(matches_pattern(Some("review.mdx"), pattern));
+        assert!(!matches_pattern(Some("review.mdz"), pattern));
+        assert!(!matches_pattern(Some("review.txt"), pattern));
+        assert!(!matches_pattern(Some("md"), pattern));
+        assert!(matches_pattern(Some("build.yml"), "*.{yaml,yml,json}"));
+        assert!(matches_pattern(Some("AGENTS.md"), "{AGENTS,CLAUDE}.md"));
+        assert!(!matches_pattern(Some("RULES.md"), "{AGENTS,CLAUDE}.md"));
+    }
+
     #[test]
     fn dir_name_from_path_extracts_final_component() {
         assert_eq!(dir_name_from_path(Path::new("/foo/bar/skill")), "skill");
@@ -1124,6 +1725,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn descriptor_agent_dir_is_found_where_fallback_misses() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let agents = temp.path().join("subagents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("planner.md"), "planner").unwrap();
+        std::fs::write(agents.join("notes.txt"), "not an agent").unwrap();
+
+        assert!(extract_agents_fallback(temp.path()).0.is_none());
+
+        let harness = CustomHarness::from_toml(
+            "id = \"acme\"\nconfig_dir = \"/x\"\nagent_dir = \"subagents\"\n",
+        )
+        .unwrap();
+        let mut info = ProfileInfo::default();
+        extract_custom_resources(&harness, temp.path(), &mut info);
+        assert_eq!(info.agents.unwrap().items, ["planner"]);
+    }
+
     #[test]
     fn fallback_dir_name_maps_singular_to_plural() {
         assert_eq!(fallback_dir_name("skill"), Some("skills"));
@@ -3,6 +3,8 @@ use std::path::Path;
 use chrono::Local;
 use harness_locate::{Harness, HarnessKind, Scope};
 
+use super::CaptureFilter;
+use crate::config::is_probably_binary;
 use crate::error::Result;
 use crate::harness::HarnessConfig;
 use crate::install::installer::{sanitize_name_for_opencode, transform_skill_for_opencode};
@@ -25,8 +27,23 @@ const SESSION_DATA: &[&str] = &[
     "history.jsonl",
 ];
 
-fn is_excluded(name: &str) -> bool {
-    ALWAYS_EXCLUDED.contains(&name) || SESSION_DATA.contains(&name)
+pub(super) fn is_excluded(name: &str) -> bool {
+    ALWAYS_EXCLUDED.contains(&name)
+        || SESSION_DATA.contains(&name)
+        || name == crate::config::PROFILE_META_FILE
+        || name == crate::config::PROFILE_LINKS_FILE
+        || name == crate::config::PROFILE_BASE_FILE
+        || name == crate::config::PROFILE_HISTORY_FILE
+        || name.starts_with(super::MARKER_PREFIX)
+        || name.ends_with(crate::config::edit_backup::BACKUP_SUFFIX)
+}
+
+/// Like [`is_excluded`], for entries below the top level of a config dir.
+///
+/// Session data and bridle's own files only live at the top level, so a
+/// plugin's `commands/debug/` is kept.
+pub(super) fn is_excluded_nested(name: &str) -> bool {
+    ALWAYS_EXCLUDED.contains(&name) || name.ends_with(crate::config::edit_backup::BACKUP_SUFFIX)
 }
 
 fn is_session_data(name: &str) -> bool {
@@ -35,88 +52,250 @@ fn is_session_data(name: &str) -> bool {
 
 const MAX_EXTRA_BACKUPS: usize = 5;
 
+/// Fails with [`Error::CaptureTooLarge`](crate::error::Error::CaptureTooLarge) if the
+/// files a capture of `dir` would copy exceed `limit` bytes.
+///
+/// Walks `dir` with the same rules as [`capture_config_files`], so caches like
+/// `node_modules`, `filter`'s excludes, and the top-level entries in `skip`
+/// don't count.
+pub fn check_capture_size(
+    dir: &Path,
+    limit: u64,
+    filter: &CaptureFilter,
+    skip: &[&str],
+) -> Result<()> {
+    let mut total = 0;
+    walk_capturable(dir, "", filter, skip, false, &mut |path, len| {
+        total += len;
+        if total > limit {
+            return Err(crate::error::Error::CaptureTooLarge {
+                path: path.to_path_buf(),
+                limit,
+            });
+        }
+        Ok(())
+    })
+}
+
+/// Whether capture copies the entry `rel` named `name`, and whether a
+/// directory must be walked for includes even when it isn't copied.
+/// `excluded` says its parent directory was left out.
+fn capture_admission(
+    filter: &CaptureFilter,
+    rel: &str,
+    name: &str,
+    excluded: bool,
+    is_dir: bool,
+) -> (bool, bool) {
+    let by_default = excluded
+        || if rel.contains('/') {
+            is_excluded_nested(name)
+        } else {
+            is_excluded(name)
+        };
+    let admitted = filter.admits(rel, by_default);
+    (admitted, is_dir && filter.may_include_below(rel))
+}
+
+/// Calls `visit` with each regular file a capture of `dir` would copy and its
+/// length, in name order. `rel` is `dir` relative to the capture root.
+fn walk_capturable(
+    dir: &Path,
+    rel: &str,
+    filter: &CaptureFilter,
+    skip: &[&str],
+    excluded: bool,
+    visit: &mut dyn FnMut(&Path, u64) -> Result<()>,
+) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if rel.is_empty() && skip.contains(&name.as_ref()) {
+            continue;
+        }
+        let entry_rel = format!("{rel}{name}");
+        let file_type = entry.file_type()?;
+        let (admitted, walk_for_includes) =
+            capture_admission(filter, &entry_rel, &name, excluded, file_type.is_dir());
+        if file_type.is_dir() && (admitted || walk_for_includes) {
+            let nested_rel = format!("{entry_rel}/");
+            walk_capturable(&entry.path(), &nested_rel, filter, skip, !admitted, visit)?;
+        } else if file_type.is_file() && admitted {
+            visit(&entry.path(), entry.metadata()?.len())?;
+        }
+    }
+    Ok(())
+}
+
+/// The number of files below `dir` and their total size in bytes.
+pub fn dir_usage(dir: &Path) -> Result<(u64, u64)> {
+    let (mut files, mut bytes) = (0, 0);
+    walk_files(dir, &mut |_, len| {
+        files += 1;
+        bytes += len;
+        Ok(())
+    })?;
+    Ok((files, bytes))
+}
+
+/// Calls `visit` with each regular file below `dir` and its length, in name
+/// order. A missing `dir` has no files.
+fn walk_files(dir: &Path, visit: &mut dyn FnMut(&Path, u64) -> Result<()>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk_files(&entry.path(), visit)?;
+        } else if file_type.is_file() {
+            visit(&entry.path(), entry.metadata()?.len())?;
+        }
+    }
+    Ok(())
+}
+
 pub fn copy_config_files(
     harness: &dyn HarnessConfig,
     source_is_live: bool,
     profile_path: &Path,
 ) -> Result<()> {
-    use std::collections::HashSet;
+    if source_is_live {
+        return capture_config_files(harness, profile_path, &CaptureFilter::default(), &[]);
+    }
 
     let config_dir = harness.config_dir()?;
-    let mut copied_files: HashSet<std::path::PathBuf> = HashSet::new();
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)?;
+    }
 
-    if source_is_live {
-        if config_dir.exists() {
-            for entry in std::fs::read_dir(&config_dir)? {
-                let entry = entry?;
-                let file_name = entry.file_name();
-                let name_str = file_name.to_string_lossy();
-
-                if is_excluded(&name_str) {
-                    continue;
-                }
+    let mcp_filename = harness
+        .mcp_config_path()
+        .and_then(|p| p.file_name().map(|f| f.to_os_string()));
 
-                let file_type = entry.file_type()?;
-                let dest = profile_path.join(&file_name);
+    for entry in std::fs::read_dir(profile_path)? {
+        let entry = entry?;
+        let filename = entry.file_name();
+        let file_type = entry.file_type()?;
 
-                if file_type.is_file() {
-                    std::fs::copy(entry.path(), &dest)?;
-                    if let Ok(canonical) = entry.path().canonicalize() {
-                        copied_files.insert(canonical);
-                    }
-                } else if file_type.is_dir() {
-                    copy_dir_filtered(&entry.path(), &dest)?;
-                }
+        if file_type.is_dir() {
+            if !is_excluded(&filename.to_string_lossy()) {
+                copy_dir_filtered(&entry.path(), &config_dir.join(&filename))?;
             }
-        }
-
-        if let Some(mcp_path) = harness.mcp_config_path() {
-            let dominated = mcp_path
-                .canonicalize()
-                .map(|c| copied_files.contains(&c))
-                .unwrap_or(false);
-
-            if !dominated
-                && mcp_path.exists()
-                && mcp_path.is_file()
-                && let Some(filename) = mcp_path.file_name()
+        } else if file_type.is_file() {
+            if let Some(ref mcp_name) = mcp_filename
+                && &filename == mcp_name
+                && let Some(mcp_path) = harness.mcp_config_path()
             {
-                let dest = profile_path.join(filename);
-                std::fs::copy(&mcp_path, dest)?;
+                std::fs::copy(entry.path(), &mcp_path)?;
+                continue;
             }
+
+            let dest = config_dir.join(&filename);
+            std::fs::copy(entry.path(), dest)?;
         }
-    } else {
-        if !config_dir.exists() {
-            std::fs::create_dir_all(&config_dir)?;
-        }
+    }
 
-        let mcp_filename = harness
-            .mcp_config_path()
-            .and_then(|p| p.file_name().map(|f| f.to_os_string()));
+    Ok(())
+}
 
-        for entry in std::fs::read_dir(profile_path)? {
-            let entry = entry?;
-            if entry.file_type()?.is_file() {
-                let filename = entry.file_name();
+/// Copies the live config into `profile_path`, applying the default
+/// exclusions with `filter` layered over them. Top-level entries named in
+/// `skip` are left out whatever the filter says.
+pub(super) fn capture_config_files(
+    harness: &dyn HarnessConfig,
+    profile_path: &Path,
+    filter: &CaptureFilter,
+    skip: &[&str],
+) -> Result<()> {
+    use std::collections::HashSet;
 
-                if let Some(ref mcp_name) = mcp_filename
-                    && &filename == mcp_name
-                    && let Some(mcp_path) = harness.mcp_config_path()
-                {
-                    std::fs::copy(entry.path(), &mcp_path)?;
-                    continue;
-                }
+    let config_dir = harness.config_dir()?;
+    let mut copied_files: HashSet<std::path::PathBuf> = HashSet::new();
 
-                let dest = config_dir.join(&filename);
-                std::fs::copy(entry.path(), dest)?;
+    if config_dir.exists() {
+        for entry in std::fs::read_dir(&config_dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let name_str = file_name.to_string_lossy();
+            if skip.contains(&name_str.as_ref()) {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            let (admitted, walk_for_includes) =
+                capture_admission(filter, &name_str, &name_str, false, file_type.is_dir());
+            let dest = profile_path.join(&file_name);
+
+            if file_type.is_file() && admitted {
+                std::fs::copy(entry.path(), &dest)?;
+                if let Ok(canonical) = entry.path().canonicalize() {
+                    copied_files.insert(canonical);
+                }
+            } else if file_type.is_dir() && (admitted || walk_for_includes) {
+                copy_dir_matching(
+                    &entry.path(),
+                    &dest,
+                    &format!("{name_str}/"),
+                    filter,
+                    !admitted,
+                    &mut |_| {},
+                )?;
             }
         }
     }
 
+    if let Some(mcp_path) = harness.mcp_config_path() {
+        let dominated = mcp_path
+            .canonicalize()
+            .map(|c| copied_files.contains(&c))
+            .unwrap_or(false);
+
+        if !dominated
+            && mcp_path.exists()
+            && mcp_path.is_file()
+            && let Some(filename) = mcp_path.file_name()
+        {
+            let dest = profile_path.join(filename);
+            std::fs::copy(&mcp_path, dest)?;
+        }
+    }
+
     Ok(())
 }
 
 pub fn copy_all_contents(src: &Path, dst: &Path) -> Result<()> {
+    copy_all_contents_with_progress(src, dst, &mut |_| {})
+}
+
+/// Like [`copy_all_contents`], calling `progress` with each source entry as it is copied.
+pub fn copy_all_contents_with_progress(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(&Path),
+) -> Result<()> {
+    copy_top_level(src, dst, &[], progress)
+}
+
+/// Like [`copy_all_contents`], leaving out top-level entries named in `skip`.
+pub fn copy_all_contents_except(src: &Path, dst: &Path, skip: &[&str]) -> Result<()> {
+    copy_top_level(src, dst, skip, &mut |_| {})
+}
+
+fn copy_top_level(
+    src: &Path,
+    dst: &Path,
+    skip: &[&str],
+    progress: &mut dyn FnMut(&Path),
+) -> Result<()> {
     if !src.exists() {
         return Ok(());
     }
@@ -126,14 +305,15 @@ pub fn copy_all_contents(src: &Path, dst: &Path) -> Result<()> {
         let file_name = entry.file_name();
         let name_str = file_name.to_string_lossy();
 
-        if is_excluded(&name_str) {
+        if is_excluded(&name_str) || skip.contains(&name_str.as_ref()) {
             continue;
         }
 
         let src_path = entry.path();
         let dst_path = dst.join(&file_name);
+        progress(&src_path);
         if entry.file_type()?.is_dir() {
-            copy_dir_filtered(&src_path, &dst_path)?;
+            copy_dir_filtered_with_progress(&src_path, &dst_path, progress)?;
         } else {
             std::fs::copy(&src_path, &dst_path)?;
         }
@@ -214,6 +394,80 @@ pub fn switch_config_dir_safely(
     profile_path: &Path,
     config_dir: &Path,
     backup_dir: &Path,
+) -> Result<()> {
+    let retries = crate::config::ProfileConfig::default().fs_retries;
+    switch_config_dir_safely_with_progress(
+        profile_path,
+        config_dir,
+        backup_dir,
+        &[],
+        retries,
+        &mut |_| {},
+    )
+}
+
+/// Removes everything in `config_dir` except session data and the top-level
+/// entries named in `keep_live`.
+pub(super) fn clear_config_dir(config_dir: &Path, keep_live: &[&str], retries: u32) -> Result<()> {
+    if !config_dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(config_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let name_str = file_name.to_string_lossy();
+
+        if is_session_data(&name_str) || keep_live.contains(&name_str.as_ref()) {
+            continue;
+        }
+
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            super::retry::remove_dir_all(&path, retries)?;
+        } else {
+            super::retry::remove_file(&path, retries)?;
+        }
+    }
+    Ok(())
+}
+
+/// Like [`switch_config_dir_safely`], reporting each profile entry copied into `config_dir`.
+///
+/// Top-level live entries named in `keep_live` are neither wiped nor
+/// overwritten, and a locked file is retried up to `retries` times.
+pub fn switch_config_dir_safely_with_progress(
+    profile_path: &Path,
+    config_dir: &Path,
+    backup_dir: &Path,
+    keep_live: &[&str],
+    retries: u32,
+    progress: &mut dyn FnMut(&Path),
+) -> Result<()> {
+    switch_config_dir_journaled(
+        profile_path,
+        config_dir,
+        backup_dir,
+        keep_live,
+        retries,
+        progress,
+        &mut |_| Ok(()),
+    )
+}
+
+/// Like [`switch_config_dir_safely_with_progress`], calling `before_wipe`
+/// once the live config is backed up and before anything in it is removed.
+///
+/// `before_wipe` gets the backup path, or `None` if the live config was empty.
+/// If it fails, the live config is left untouched.
+pub(super) fn switch_config_dir_journaled(
+    profile_path: &Path,
+    config_dir: &Path,
+    backup_dir: &Path,
+    keep_live: &[&str],
+    retries: u32,
+    progress: &mut dyn FnMut(&Path),
+    before_wipe: &mut dyn FnMut(Option<&Path>) -> Result<()>,
 ) -> Result<()> {
     use crate::error::Error;
 
@@ -234,28 +488,16 @@ pub fn switch_config_dir_safely(
         false
     };
 
-    if config_dir.exists() {
-        for entry in std::fs::read_dir(config_dir)? {
-            let entry = entry?;
-            let file_name = entry.file_name();
-            let name_str = file_name.to_string_lossy();
-
-            if is_session_data(&name_str) {
-                continue;
-            }
-
-            let path = entry.path();
-            let file_type = entry.file_type()?;
-            if file_type.is_dir() {
-                std::fs::remove_dir_all(&path)?;
-            } else {
-                std::fs::remove_file(&path)?;
-            }
+    if let Err(e) = before_wipe(has_backup.then_some(backup_path.as_path())) {
+        if has_backup {
+            let _ = std::fs::remove_dir_all(&backup_path);
         }
+        return Err(e);
     }
+    clear_config_dir(config_dir, keep_live, retries)?;
 
     // Copy profile contents
-    let copy_result = copy_all_contents(profile_path, config_dir);
+    let copy_result = copy_top_level(profile_path, config_dir, keep_live, progress);
 
     match copy_result {
         Ok(()) => {
@@ -266,7 +508,7 @@ pub fn switch_config_dir_safely(
             Ok(())
         }
         Err(e) => {
-            eprintln!("Profile switch failed, restoring from backup...");
+            crate::diagnostics::warn("Profile switch failed, restoring from backup...");
 
             // Wipe partial copy (best-effort, continue even if individual deletes fail)
             if config_dir.exists() {
@@ -324,13 +566,41 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
 /// Copy directory recursively, preserving symlinks and skipping excluded dirs.
 /// Continues on errors (logs warning) rather than aborting.
 pub fn copy_dir_filtered(src: &Path, dst: &Path) -> Result<()> {
+    copy_dir_filtered_with_progress(src, dst, &mut |_| {})
+}
+
+/// Like [`copy_dir_filtered`], calling `progress` with each source entry as it is copied.
+pub fn copy_dir_filtered_with_progress(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(&Path),
+) -> Result<()> {
+    copy_dir_matching(src, dst, "", &CaptureFilter::default(), false, progress)
+}
+
+/// [`copy_dir_filtered_with_progress`] with `filter` applied to each entry's
+/// path, `rel` being `src` relative to the capture root. When `excluded`,
+/// `src` itself was left out and only entries an include re-admits are
+/// copied; `dst` is removed again if nothing was.
+fn copy_dir_matching(
+    src: &Path,
+    dst: &Path,
+    rel: &str,
+    filter: &CaptureFilter,
+    excluded: bool,
+    progress: &mut dyn FnMut(&Path),
+) -> Result<()> {
     std::fs::create_dir_all(dst)?;
 
     for entry in std::fs::read_dir(src)? {
         let entry = match entry {
             Ok(e) => e,
             Err(e) => {
-                eprintln!("Warning: Failed to read entry in {}: {}", src.display(), e);
+                crate::diagnostics::warn(format!(
+                    "Warning: Failed to read entry in {}: {}",
+                    src.display(),
+                    e
+                ));
                 continue;
             }
         };
@@ -338,45 +608,71 @@ pub fn copy_dir_filtered(src: &Path, dst: &Path) -> Result<()> {
         let file_name = entry.file_name();
         let name_str = file_name.to_string_lossy();
 
-        if is_excluded(&name_str) {
+        let entry_rel = format!("{rel}{name_str}");
+        let file_type = entry.file_type()?;
+        let (admitted, walk_for_includes) =
+            capture_admission(filter, &entry_rel, &name_str, excluded, file_type.is_dir());
+        if !(admitted || walk_for_includes) {
             continue;
         }
 
         let src_path = entry.path();
         let dst_path = dst.join(&file_name);
-        let file_type = entry.file_type()?;
+        progress(&src_path);
 
         #[cfg(unix)]
         if file_type.is_symlink() {
             if let Ok(target) = std::fs::read_link(&src_path) {
                 let _ = std::fs::remove_file(&dst_path);
                 if let Err(e) = std::os::unix::fs::symlink(&target, &dst_path) {
-                    eprintln!(
+                    crate::diagnostics::warn(format!(
                         "Warning: Failed to create symlink {}: {}",
                         dst_path.display(),
                         e
-                    );
+                    ));
                 }
             }
             continue;
         }
 
         if file_type.is_dir() {
-            if let Err(e) = copy_dir_filtered(&src_path, &dst_path) {
-                eprintln!(
+            let nested_rel = format!("{entry_rel}/");
+            if let Err(e) = copy_dir_matching(
+                &src_path,
+                &dst_path,
+                &nested_rel,
+                filter,
+                !admitted,
+                progress,
+            ) {
+                crate::diagnostics::warn(format!(
                     "Warning: Failed to copy directory {}: {}",
                     src_path.display(),
                     e
-                );
+                ));
             }
         } else if let Err(e) = std::fs::copy(&src_path, &dst_path) {
-            eprintln!("Warning: Failed to copy file {}: {}", src_path.display(), e);
+            crate::diagnostics::warn(format!(
+                "Warning: Failed to copy file {}: {}",
+                src_path.display(),
+                e
+            ));
         }
     }
 
+    if excluded {
+        let _ = std::fs::remove_dir(dst);
+    }
     Ok(())
 }
 
+/// Top-level config directory names that hold skills, agents, commands, or plugins.
+///
+/// Covers both the canonical plural names and OpenCode's singular ones.
+pub const RESOURCE_DIR_NAMES: &[&str] = &[
+    "skills", "skill", "agents", "agent", "commands", "command", "plugins", "plugin",
+];
+
 /// Canonical directory names used inside profiles for resource storage.
 /// These are bridle's internal convention - harness-locate maps them to actual paths.
 pub const CANONICAL_COMMANDS_DIR: &str = "commands";
@@ -384,6 +680,23 @@ pub const CANONICAL_AGENTS_DIR: &str = "agents";
 pub const CANONICAL_SKILLS_DIR: &str = "skills";
 pub const CANONICAL_PLUGINS_DIR: &str = "plugins";
 
+/// Copies `src` to `dst` through `transform`, or byte for byte if `src`
+/// looks binary.
+fn copy_transforming_text(
+    src: &Path,
+    dst: &Path,
+    transform: impl FnOnce(&str) -> String,
+) -> Result<()> {
+    let bytes = std::fs::read(src)?;
+    if is_probably_binary(&bytes) {
+        std::fs::write(dst, bytes)?;
+    } else {
+        let text = String::from_utf8_lossy(&bytes);
+        std::fs::write(dst, transform(&text))?;
+    }
+    Ok(())
+}
+
 fn copy_skills_for_opencode(src: &Path, dst: &Path) -> Result<()> {
     if !src.exists() {
         return Ok(());
@@ -417,9 +730,9 @@ fn copy_skills_for_opencode(src: &Path, dst: &Path) -> Result<()> {
                     .eq_ignore_ascii_case("SKILL.md");
 
                 if is_skill_md {
-                    let content = std::fs::read_to_string(&skill_src)?;
-                    let transformed = transform_skill_for_opencode(&content, &sanitized_name);
-                    std::fs::write(&skill_dst, transformed)?;
+                    copy_transforming_text(&skill_src, &skill_dst, |content| {
+                        transform_skill_for_opencode(content, &sanitized_name)
+                    })?;
                 } else {
                     std::fs::copy(&skill_src, &skill_dst)?;
                 }
@@ -432,43 +745,70 @@ fn copy_skills_for_opencode(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Copy resource directories between profile and harness using harness-aware paths.
-///
-/// When `to_profile` is true: harness paths → canonical profile dirs
-/// When `to_profile` is false: canonical profile dirs → harness paths
-///
-/// Uses canonical names inside profiles for cross-harness portability.
-pub fn copy_resource_directories(
-    harness: &Harness,
-    to_profile: bool,
-    profile_path: &Path,
-) -> Result<()> {
-    let scope = Scope::Global;
+/// Canonical profile directory names of the resource kinds bridle copies.
+pub const CANONICAL_RESOURCE_DIRS: &[&str] = &[
+    CANONICAL_COMMANDS_DIR,
+    CANONICAL_AGENTS_DIR,
+    CANONICAL_SKILLS_DIR,
+    CANONICAL_PLUGINS_DIR,
+];
 
-    let resources: Vec<(&str, Option<std::path::PathBuf>)> = vec![
+/// Each canonical resource directory name with the harness's live path for
+/// it, leaving out the kinds the harness doesn't have.
+fn resource_paths(harness: &Harness, scope: &Scope) -> Vec<(&'static str, std::path::PathBuf)> {
+    [
         (
             CANONICAL_COMMANDS_DIR,
-            harness.commands(&scope).ok().flatten().map(|r| r.path),
+            harness.commands(scope).ok().flatten().map(|r| r.path),
         ),
         (
             CANONICAL_AGENTS_DIR,
-            harness.agents(&scope).ok().flatten().map(|r| r.path),
+            harness.agents(scope).ok().flatten().map(|r| r.path),
         ),
         (
             CANONICAL_SKILLS_DIR,
-            harness.skills(&scope).ok().flatten().map(|r| r.path),
+            harness.skills(scope).ok().flatten().map(|r| r.path),
         ),
         (
             CANONICAL_PLUGINS_DIR,
-            harness.plugins(&scope).ok().flatten().map(|r| r.path),
+            harness.plugins(scope).ok().flatten().map(|r| r.path),
         ),
-    ];
+    ]
+    .into_iter()
+    .filter_map(|(name, path)| Some((name, path?)))
+    .collect()
+}
 
-    for (canonical_name, harness_path) in resources {
-        let Some(harness_path) = harness_path else {
-            continue;
-        };
+/// Removes the live resource directories of `harness` that `profile_path`
+/// holds a canonical copy of, so copying them back doesn't merge into
+/// whatever was added since.
+pub fn clear_resource_directories(
+    harness: &Harness,
+    scope: &Scope,
+    profile_path: &Path,
+    retries: u32,
+) -> Result<()> {
+    for (canonical_name, harness_path) in resource_paths(harness, scope) {
+        if profile_path.join(canonical_name).is_dir() && harness_path.is_dir() {
+            super::retry::remove_dir_all(&harness_path, retries)?;
+        }
+    }
+    Ok(())
+}
 
+/// Copy resource directories between profile and harness using harness-aware paths.
+///
+/// When `to_profile` is true: harness paths → canonical profile dirs
+/// When `to_profile` is false: canonical profile dirs → harness paths
+///
+/// Uses canonical names inside profiles for cross-harness portability.
+pub fn copy_resource_directories(
+    harness: &Harness,
+    scope: &Scope,
+    to_profile: bool,
+    profile_path: &Path,
+) -> Result<()> {
+    for (canonical_name, harness_path) in resource_paths(harness, scope) {
         let profile_resource = profile_path.join(canonical_name);
 
         let (src, dst) = if to_profile {
@@ -499,6 +839,22 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn binary_files_pass_through_text_transforms_unchanged() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("SKILL.md");
+        let dst = temp.path().join("out.md");
+        let bytes = b"name: x\0\x89PNG\r\n\x1a\n";
+        fs::write(&src, bytes).unwrap();
+
+        copy_transforming_text(&src, &dst, |_| "mangled".to_string()).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(), bytes);
+
+        fs::write(&src, "name: x\r\n").unwrap();
+        copy_transforming_text(&src, &dst, str::to_uppercase).unwrap();
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "NAME: X\r\n");
+    }
+
     #[test]
     fn copy_dir_filtered_skips_excluded_directories() {
         let src = TempDir::new().unwrap();
@@ -518,6 +874,38 @@ mod tests {
         assert!(dst.path().join("config.json").exists());
     }
 
+    #[test]
+    fn copy_dir_filtered_warnings_go_through_logger() {
+        use crate::diagnostics::{quiet, with_logger};
+        use std::sync::{Arc, Mutex};
+
+        let src = TempDir::new().unwrap();
+        let dst = TempDir::new().unwrap();
+        fs::write(src.path().join("settings.json"), "{}").unwrap();
+        fs::create_dir(dst.path().join("settings.json")).unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::<String>::new()));
+        let sink = Arc::clone(&seen);
+        with_logger(
+            Arc::new(move |m| sink.lock().unwrap().push(m.into())),
+            || {
+                with_logger(quiet(), || copy_dir_filtered(src.path(), dst.path())).unwrap();
+            },
+        );
+        assert!(seen.lock().unwrap().is_empty());
+
+        let sink = Arc::clone(&seen);
+        with_logger(
+            Arc::new(move |m| sink.lock().unwrap().push(m.into())),
+            || {
+                copy_dir_filtered(src.path(), dst.path()).unwrap();
+            },
+        );
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].starts_with("Warning: Failed to copy file"));
+    }
+
     #[test]
     fn copy_dir_filtered_copies_nested_directories() {
         let src = TempDir::new().unwrap();
@@ -533,39 +921,31 @@ mod tests {
         assert_eq!(content, "#!/bin/bash");
     }
 
-    #[test]
-    fn copy_config_files_copies_directories_when_saving() {
-        use crate::harness::HarnessConfig;
-        use std::path::PathBuf;
+    struct TestHarness(std::path::PathBuf);
 
-        struct TestHarness(PathBuf);
-        impl HarnessConfig for TestHarness {
-            fn id(&self) -> &str {
-                "test"
-            }
-            fn config_dir(&self) -> crate::error::Result<PathBuf> {
-                Ok(self.0.clone())
-            }
-            fn installation_status(
-                &self,
-            ) -> crate::error::Result<harness_locate::InstallationStatus> {
-                Ok(harness_locate::InstallationStatus::NotInstalled)
-            }
-            fn mcp_filename(&self) -> Option<String> {
-                None
-            }
-            fn mcp_config_path(&self) -> Option<PathBuf> {
-                None
-            }
-            fn parse_mcp_servers(
-                &self,
-                _: &str,
-                _: &str,
-            ) -> crate::error::Result<Vec<(String, bool)>> {
-                Ok(vec![])
-            }
+    impl crate::harness::HarnessConfig for TestHarness {
+        fn id(&self) -> &str {
+            "test"
+        }
+        fn config_dir(&self) -> crate::error::Result<std::path::PathBuf> {
+            Ok(self.0.clone())
+        }
+        fn installation_status(&self) -> crate::error::Result<harness_locate::InstallationStatus> {
+            Ok(harness_locate::InstallationStatus::NotInstalled)
+        }
+        fn mcp_filename(&self) -> Option<String> {
+            None
+        }
+        fn mcp_config_path(&self) -> Option<std::path::PathBuf> {
+            None
+        }
+        fn parse_mcp_servers(&self, _: &str, _: &str) -> crate::error::Result<Vec<(String, bool)>> {
+            Ok(vec![])
         }
+    }
 
+    #[test]
+    fn copy_config_files_copies_directories_when_saving() {
         let temp = TempDir::new().unwrap();
         let config_dir = temp.path().join("config");
         let profile_dir = temp.path().join("profile");
@@ -590,6 +970,90 @@ mod tests {
         assert!(profile_dir.join("custom-dir/nested/deep.txt").exists());
     }
 
+    #[test]
+    fn capture_include_readmits_excluded_paths() {
+        let temp = TempDir::new().unwrap();
+        let live = temp.path().join("live");
+        let profile_dir = temp.path().join("profile");
+        fs::create_dir_all(live.join("logs/old")).unwrap();
+        fs::create_dir_all(live.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(&profile_dir).unwrap();
+        fs::write(live.join("settings.json"), "{}").unwrap();
+        fs::write(live.join("debug.log"), "noise").unwrap();
+        fs::write(live.join("important.log"), "keep").unwrap();
+        fs::write(live.join("logs/old/a.txt"), "old").unwrap();
+        fs::write(live.join("node_modules/pkg/index.js"), "").unwrap();
+        fs::write(live.join("node_modules/pkg/patch.js"), "patched").unwrap();
+
+        let filter = CaptureFilter::new(
+            vec!["logs/**".to_string(), "*.log".to_string()],
+            vec![
+                "important.log".to_string(),
+                "node_modules/pkg/patch.js".to_string(),
+            ],
+        );
+        capture_config_files(&TestHarness(live), &profile_dir, &filter, &[]).unwrap();
+
+        assert!(profile_dir.join("settings.json").exists());
+        assert!(profile_dir.join("important.log").exists());
+        assert!(!profile_dir.join("debug.log").exists());
+        assert!(!profile_dir.join("logs").exists());
+        assert!(profile_dir.join("node_modules/pkg/patch.js").exists());
+        assert!(!profile_dir.join("node_modules/pkg/index.js").exists());
+    }
+
+    #[test]
+    fn capture_size_counts_exactly_what_capture_copies() {
+        let temp = TempDir::new().unwrap();
+        let live = temp.path().join("live");
+        let profile_dir = temp.path().join("profile");
+        fs::create_dir_all(live.join("commands/debug")).unwrap();
+        fs::create_dir_all(live.join("debug")).unwrap();
+        fs::create_dir_all(live.join("plugins/p/node_modules")).unwrap();
+        fs::create_dir_all(&profile_dir).unwrap();
+        fs::write(live.join("settings.json"), "{}").unwrap();
+        fs::write(live.join("commands/debug/trace.md"), "trace").unwrap();
+        fs::write(live.join("debug/session.log"), vec![0u8; 512]).unwrap();
+        fs::write(live.join("plugins/p/node_modules/dep.js"), vec![0u8; 512]).unwrap();
+        fs::write(live.join("plugins/p/index.js"), "main").unwrap();
+
+        let filter = CaptureFilter::default();
+        capture_config_files(&TestHarness(live.clone()), &profile_dir, &filter, &[]).unwrap();
+        let (_, copied) = dir_usage(&profile_dir).unwrap();
+
+        assert_eq!(copied, 2 + 5 + 4);
+        check_capture_size(&live, copied, &filter, &[]).unwrap();
+        assert!(check_capture_size(&live, copied - 1, &filter, &[]).is_err());
+    }
+
+    #[test]
+    fn copy_config_files_restores_directories_to_fresh_config() {
+        let temp = TempDir::new().unwrap();
+        let live = temp.path().join("live");
+        let profile_dir = temp.path().join("profile");
+        fs::create_dir_all(live.join("agents/nested")).unwrap();
+        fs::create_dir_all(live.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(&profile_dir).unwrap();
+        fs::write(live.join("settings.json"), "{}").unwrap();
+        fs::write(live.join("agents/reviewer.md"), "review").unwrap();
+        fs::write(live.join("agents/nested/deep.md"), "deep").unwrap();
+        fs::write(live.join("node_modules/pkg/index.js"), "").unwrap();
+
+        copy_config_files(&TestHarness(live), true, &profile_dir).unwrap();
+        fs::create_dir_all(profile_dir.join("node_modules/pkg")).unwrap();
+
+        let fresh = temp.path().join("fresh");
+        copy_config_files(&TestHarness(fresh.clone()), false, &profile_dir).unwrap();
+
+        assert!(fresh.join("settings.json").exists());
+        assert_eq!(
+            fs::read_to_string(fresh.join("agents/reviewer.md")).unwrap(),
+            "review"
+        );
+        assert!(fresh.join("agents/nested/deep.md").exists());
+        assert!(!fresh.join("node_modules").exists());
+    }
+
     #[cfg(unix)]
     #[test]
     fn copy_dir_filtered_preserves_symlinks() {
@@ -639,6 +1103,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn progress_reports_each_copied_entry_once() {
+        let temp = TempDir::new().unwrap();
+        let config_dir = temp.path().join("config");
+        let profile_dir = temp.path().join("profile");
+        fs::create_dir_all(profile_dir.join("skills/nested")).unwrap();
+        fs::create_dir_all(profile_dir.join(".git")).unwrap();
+        fs::write(profile_dir.join("config.json"), "{}").unwrap();
+        fs::write(profile_dir.join("skills/nested/SKILL.md"), "# Skill").unwrap();
+
+        let mut seen = Vec::new();
+        switch_config_dir_safely_with_progress(
+            &profile_dir,
+            &config_dir,
+            &temp.path().join("backups"),
+            &[],
+            0,
+            &mut |path| seen.push(path.strip_prefix(&profile_dir).unwrap().to_path_buf()),
+        )
+        .unwrap();
+
+        seen.sort();
+        let expected: Vec<std::path::PathBuf> = [
+            "config.json",
+            "skills",
+            "skills/nested",
+            "skills/nested/SKILL.md",
+        ]
+        .iter()
+        .map(Into::into)
+        .collect();
+        assert_eq!(seen, expected);
+    }
+
     #[test]
     fn switch_config_dir_safely_to_empty_profile() {
         let temp = TempDir::new().unwrap();
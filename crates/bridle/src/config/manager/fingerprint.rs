@@ -0,0 +1,129 @@
+//! Stable content hash of a stored profile (`profile fingerprint`).
+
+use std::fs;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use super::{ProfileManager, files};
+use crate::config::ProfileName;
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+impl ProfileManager {
+    /// SHA-256 over the profile's files, as lowercase hex.
+    ///
+    /// Files are hashed in path order together with their relative paths, so
+    /// the result depends only on contents. Entries capture would skip
+    /// (session data, bridle's own metadata, caches) are left out, which keeps
+    /// identical setups on different machines at the same fingerprint.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist, or an
+    /// IO error if a file cannot be read.
+    pub fn fingerprint_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<String> {
+        let profile_path = self.profile_path(harness, name);
+        if !profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        fingerprint_dir(&profile_path)
+    }
+}
+
+fn fingerprint_dir(root: &Path) -> Result<String> {
+    let mut paths = Vec::new();
+    collect_files(root, "", &mut paths)?;
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    for rel in paths {
+        // Streamed into the hasher, so large files are never held in memory.
+        let mut file = fs::File::open(root.join(&rel))?;
+        hasher.update(rel.as_bytes());
+        hasher.update([0]);
+        hasher.update(file.metadata()?.len().to_le_bytes());
+        std::io::copy(&mut file, &mut hasher)?;
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Collects `/`-separated paths of files under `dir`, relative to the root.
+pub(super) fn collect_files(dir: &Path, prefix: &str, out: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let excluded = if prefix.is_empty() {
+            files::is_excluded(&name)
+        } else {
+            files::is_excluded_nested(&name)
+        };
+        if excluded {
+            continue;
+        }
+        let rel = format!("{prefix}{name}");
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, &format!("{rel}/"), out)?;
+        } else if path.is_file() {
+            out.push(rel);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_all(root: &Path, files: &[(&str, &str)]) {
+        for (rel, contents) in files {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn entry_order_and_metadata_do_not_affect_fingerprint() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        let files = [
+            ("settings.json", "{}"),
+            ("skills/review/SKILL.md", "# Review"),
+            ("agents/planner.md", "plan"),
+        ];
+        write_all(&a, &files);
+        let mut reversed = files;
+        reversed.reverse();
+        write_all(&b, &reversed);
+        fs::write(
+            b.join(crate::config::PROFILE_META_FILE),
+            "created_at = \"x\"",
+        )
+        .unwrap();
+
+        assert_eq!(fingerprint_dir(&a).unwrap(), fingerprint_dir(&b).unwrap());
+
+        fs::write(b.join("agents/planner.md"), "changed").unwrap();
+        assert_ne!(fingerprint_dir(&a).unwrap(), fingerprint_dir(&b).unwrap());
+    }
+
+    #[test]
+    fn moving_content_between_files_changes_fingerprint() {
+        let temp = TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        write_all(&a, &[("x", "ab"), ("y", "")]);
+        write_all(&b, &[("x", "a"), ("y", "b")]);
+
+        assert_ne!(fingerprint_dir(&a).unwrap(), fingerprint_dir(&b).unwrap());
+    }
+}
@@ -0,0 +1,293 @@
+//! A journal of the profile switch in progress, so a switch cut short by a
+//! crash or power loss can be finished or undone on the next run.
+//!
+//! The journal is written to the live config's backups directory once the
+//! live config has been backed up and before it is wiped, and removed when
+//! the new profile is active. The switching process holds an exclusive lock
+//! next to it the whole time, so a journal found unlocked means the process
+//! died in between.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ProfileManager, files, retry};
+use crate::error::{Error, Result};
+
+/// File name of the journal inside a harness's backups directory.
+const SWITCH_JOURNAL_FILE: &str = ".switch-journal.json";
+
+/// Lock file held by the process switching the live config.
+const SWITCH_LOCK_FILE: &str = ".switch.lock";
+
+/// What a switch was doing when its journal was written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SwitchJournal {
+    pub harness_id: String,
+    /// Key the active profile is stored under in the bridle config.
+    pub active_key: String,
+    /// Profile the live config was saved into before the switch.
+    pub from: Option<String>,
+    /// Profile being switched to.
+    pub to: String,
+    pub profile_path: PathBuf,
+    pub config_dir: PathBuf,
+    /// Copy of the live config taken before the wipe, if it wasn't empty.
+    pub backup_path: Option<PathBuf>,
+    /// Top-level live entries the switch leaves alone.
+    pub keep_live: Vec<String>,
+}
+
+/// How [`ProfileManager::recover_interrupted_switch`] settled a switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// The live config was restored from its pre-switch backup.
+    RolledBack,
+    /// There was no backup to return to, so the target profile was copied
+    /// in and made active.
+    Completed,
+}
+
+/// An interrupted switch found and settled by
+/// [`ProfileManager::recover_interrupted_switch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveredSwitch {
+    pub harness_id: String,
+    pub from: Option<String>,
+    pub to: String,
+    pub action: RecoveryAction,
+}
+
+/// Opens the switch lock file in `dir`, creating both if needed.
+fn open_lock(dir: &Path) -> Result<fs::File> {
+    fs::create_dir_all(dir)?;
+    Ok(fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(SWITCH_LOCK_FILE))?)
+}
+
+/// Takes the switch lock of the backups directory `dir`, waiting for a
+/// switch of the same live config to finish. The lock is released when the
+/// returned file is dropped.
+pub(super) fn lock_switch(dir: &Path) -> Result<fs::File> {
+    let lock = open_lock(dir)?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// Writes the journal into `dir` atomically, so a crash never leaves half
+/// of one.
+pub(super) fn write_journal(dir: &Path, journal: &SwitchJournal, retries: u32) -> Result<()> {
+    let path = dir.join(SWITCH_JOURNAL_FILE);
+    fs::create_dir_all(dir)?;
+    let partial = path.with_extension("json.partial");
+    let mut file = fs::File::create(&partial)?;
+    serde_json::to_writer_pretty(&mut file, journal)?;
+    file.sync_all()?;
+    retry::rename(&partial, &path, retries)?;
+    Ok(())
+}
+
+pub(super) fn clear_journal(dir: &Path) {
+    let _ = fs::remove_file(dir.join(SWITCH_JOURNAL_FILE));
+}
+
+/// Journals directly in the harness directories under `backups_dir`, and in
+/// their per-project directories.
+fn find_journals(backups_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(backups_dir)?.filter_map(|entry| entry.ok()) {
+        let harness_dir = entry.path();
+        if let Ok(projects) = fs::read_dir(harness_dir.join("projects")) {
+            dirs.extend(projects.filter_map(|entry| entry.ok()).map(|e| e.path()));
+        }
+        dirs.push(harness_dir);
+    }
+    let mut journals: Vec<PathBuf> = dirs
+        .into_iter()
+        .map(|dir| dir.join(SWITCH_JOURNAL_FILE))
+        .filter(|path| path.is_file())
+        .collect();
+    journals.sort();
+    Ok(journals)
+}
+
+impl ProfileManager {
+    /// Settles every switch whose journal was left behind, rolling back to
+    /// the pre-switch backup when there is one and otherwise finishing the
+    /// switch, so the live config is never left wiped.
+    ///
+    /// Journals whose switch lock is still held belong to a switch running
+    /// in another process and are left alone.
+    ///
+    /// # Errors
+    /// Returns [`Error::Config`] if a journal can't be parsed, and any I/O
+    /// error from restoring the live config. The journal is kept on failure.
+    pub fn recover_interrupted_switch(&self) -> Result<Vec<RecoveredSwitch>> {
+        let backups_dir = self.backups_dir();
+        if !backups_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut recovered = Vec::new();
+        for path in find_journals(&backups_dir)? {
+            let dir = path.parent().unwrap_or(&backups_dir);
+            let lock = open_lock(dir)?;
+            match lock.try_lock() {
+                Ok(()) => {}
+                Err(fs::TryLockError::WouldBlock) => continue,
+                Err(fs::TryLockError::Error(e)) => return Err(e.into()),
+            }
+            let journal: SwitchJournal = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+            let action = self.settle(&journal)?;
+            clear_journal(dir);
+            let outcome = match action {
+                RecoveryAction::RolledBack => "rolled back",
+                RecoveryAction::Completed => "completed",
+            };
+            crate::diagnostics::warn(format!(
+                "Warning: An interrupted switch of {} to '{}' was {}",
+                journal.harness_id, journal.to, outcome
+            ));
+            recovered.push(RecoveredSwitch {
+                harness_id: journal.harness_id,
+                from: journal.from,
+                to: journal.to,
+                action,
+            });
+        }
+        Ok(recovered)
+    }
+
+    fn settle(&self, journal: &SwitchJournal) -> Result<RecoveryAction> {
+        let keep_live: Vec<&str> = journal.keep_live.iter().map(String::as_str).collect();
+        let config_dir = &journal.config_dir;
+        fs::create_dir_all(config_dir)?;
+
+        if let Some(backup) = journal.backup_path.as_deref().filter(|b| b.is_dir()) {
+            files::clear_config_dir(config_dir, &keep_live, self.fs_retries)?;
+            files::copy_all_contents(backup, config_dir)?;
+            retry::remove_dir_all(backup, self.fs_retries)?;
+            return Ok(RecoveryAction::RolledBack);
+        }
+
+        self.copy_profile_into(journal, &keep_live)?;
+        Self::mark_active(config_dir, &journal.active_key, &journal.to)?;
+        Ok(RecoveryAction::Completed)
+    }
+
+    /// Lays the journal's target profile, with its bases, over the live config.
+    fn copy_profile_into(&self, journal: &SwitchJournal, keep_live: &[&str]) -> Result<()> {
+        if !journal.profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(journal.to.clone()));
+        }
+        let staged = self.stage_layers(&journal.harness_id, &journal.profile_path)?;
+        let source: &Path = staged.as_deref().unwrap_or(&journal.profile_path);
+        let copied = files::clear_config_dir(&journal.config_dir, keep_live, self.fs_retries)
+            .and_then(|()| files::copy_all_contents_except(source, &journal.config_dir, keep_live));
+        if let Some(staged) = &staged {
+            let _ = fs::remove_dir_all(staged);
+        }
+        copied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BridleConfig;
+    use crate::config::test_env::setup_test_env;
+    use tempfile::TempDir;
+
+    fn journal(manager: &ProfileManager, temp: &TempDir, backup: Option<PathBuf>) -> SwitchJournal {
+        let profile_path = manager.profiles_dir.join("acme").join("work");
+        fs::create_dir_all(&profile_path).unwrap();
+        fs::write(profile_path.join("settings.json"), "work").unwrap();
+        SwitchJournal {
+            harness_id: "acme".to_string(),
+            active_key: "acme".to_string(),
+            from: Some("home".to_string()),
+            to: "work".to_string(),
+            profile_path,
+            config_dir: temp.path().join("live"),
+            backup_path: backup,
+            keep_live: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn leftover_journal_with_backup_rolls_back() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let backup = manager
+            .backups_dir()
+            .join("acme")
+            .join("20261014_120000_000_1");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("settings.json"), "home").unwrap();
+        let journal = journal(&manager, &temp, Some(backup.clone()));
+        // Died mid-copy: the live config holds a fragment of the new profile.
+        fs::create_dir_all(&journal.config_dir).unwrap();
+        fs::write(journal.config_dir.join("partial.json"), "").unwrap();
+        write_journal(&manager.backups_dir().join("acme"), &journal, 0).unwrap();
+
+        let recovered = manager.recover_interrupted_switch().unwrap();
+
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].action, RecoveryAction::RolledBack);
+        let live = &journal.config_dir;
+        assert_eq!(
+            fs::read_to_string(live.join("settings.json")).unwrap(),
+            "home"
+        );
+        assert!(!live.join("partial.json").exists());
+        assert!(!backup.exists());
+        assert!(manager.recover_interrupted_switch().unwrap().is_empty());
+    }
+
+    #[test]
+    fn leftover_journal_without_backup_completes_the_switch() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let journal = journal(&manager, &temp, None);
+        write_journal(&manager.backups_dir().join("acme"), &journal, 0).unwrap();
+
+        let recovered = manager.recover_interrupted_switch().unwrap();
+
+        assert_eq!(recovered[0].action, RecoveryAction::Completed);
+        assert_eq!(
+            fs::read_to_string(journal.config_dir.join("settings.json")).unwrap(),
+            "work"
+        );
+        let config = BridleConfig::load().unwrap();
+        assert_eq!(config.active_profile_for("acme"), Some("work"));
+    }
+
+    #[test]
+    fn journal_of_a_running_switch_is_left_alone() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let dir = manager.backups_dir().join("acme/projects/0123abcd");
+        let mut journal = journal(&manager, &temp, None);
+        journal.active_key = "acme@/work/app".to_string();
+        let lock = lock_switch(&dir).unwrap();
+        write_journal(&dir, &journal, 0).unwrap();
+
+        assert!(manager.recover_interrupted_switch().unwrap().is_empty());
+        assert!(dir.join(SWITCH_JOURNAL_FILE).is_file());
+        assert!(!journal.config_dir.exists());
+
+        drop(lock);
+        let recovered = manager.recover_interrupted_switch().unwrap();
+        assert_eq!(recovered[0].action, RecoveryAction::Completed);
+        let config = BridleConfig::load().unwrap();
+        assert_eq!(config.active_profile_for("acme@/work/app"), Some("work"));
+    }
+}
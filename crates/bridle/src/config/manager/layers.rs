@@ -0,0 +1,147 @@
+//! Applying a profile's base chain on switch and keeping inherited files out
+//! of the child on save.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ProfileManager, files, fingerprint};
+use crate::config::BaseLayering;
+use crate::config::profile_base::base_chain;
+use crate::error::Result;
+
+impl ProfileManager {
+    /// Merges the profile at `profile_path` over its bases into a staging
+    /// directory and returns it, or `None` if the profile has no base.
+    ///
+    /// The caller removes the staging directory when done with it.
+    pub(super) fn stage_layers(
+        &self,
+        harness_id: &str,
+        profile_path: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let chain = base_chain(profile_path)?;
+        if chain.is_empty() {
+            return Ok(None);
+        }
+        let staging = self.backups_dir().join(harness_id).join("layered");
+        let _ = fs::remove_dir_all(&staging);
+        fs::create_dir_all(&staging)?;
+        for (_, base) in chain.iter().rev() {
+            files::copy_all_contents(base, &staging)?;
+        }
+        files::copy_all_contents(profile_path, &staging)?;
+        Ok(Some(staging))
+    }
+}
+
+/// Deletes files from a freshly saved profile that match what its bases
+/// already provide, so only the profile's own overrides are stored.
+pub(super) fn prune_inherited(profile_path: &Path) -> Result<()> {
+    let chain = base_chain(profile_path)?;
+    if chain.is_empty() {
+        return Ok(());
+    }
+    for rel in own_files(profile_path)? {
+        let Some((_, base)) = chain.iter().find(|(_, base)| base.join(&rel).is_file()) else {
+            continue;
+        };
+        let path = profile_path.join(&rel);
+        if fs::read(&path)? == fs::read(base.join(&rel))? {
+            fs::remove_file(&path)?;
+            for dir in path.ancestors().skip(1) {
+                if dir == profile_path || fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Which of the profile's files come from its bases, or `None` without a base.
+pub(super) fn layering(profile_path: &Path) -> Result<Option<BaseLayering>> {
+    let chain = base_chain(profile_path)?;
+    if chain.is_empty() {
+        return Ok(None);
+    }
+    let own = own_files(profile_path)?;
+    let mut from_bases = BTreeSet::new();
+    for (_, base) in &chain {
+        from_bases.extend(own_files(base)?);
+    }
+    let (overridden, inherited): (Vec<String>, Vec<String>) =
+        from_bases.into_iter().partition(|rel| own.contains(rel));
+    Ok(Some(BaseLayering {
+        chain: chain.into_iter().map(|(name, _)| name).collect(),
+        inherited,
+        overridden,
+    }))
+}
+
+fn own_files(dir: &Path) -> Result<BTreeSet<String>> {
+    let mut paths = Vec::new();
+    fingerprint::collect_files(dir, "", &mut paths)?;
+    Ok(paths.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PROFILE_BASE_FILE;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn layering_splits_inherited_and_overridden_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(&root.join("common/settings.json"), "common");
+        write(&root.join("common/skills/review/SKILL.md"), "review");
+        write(&root.join("team/agents/planner.md"), "team");
+        write(
+            &root.join("team").join(PROFILE_BASE_FILE),
+            "base = \"common\"\n",
+        );
+        write(&root.join("work/agents/planner.md"), "work");
+        write(
+            &root.join("work").join(PROFILE_BASE_FILE),
+            "base = \"team\"\n",
+        );
+
+        let work = layering(&root.join("work")).unwrap().unwrap();
+        assert_eq!(work.chain, ["team", "common"]);
+        assert_eq!(work.overridden, ["agents/planner.md"]);
+        assert_eq!(work.inherited, ["settings.json", "skills/review/SKILL.md"]);
+        assert!(layering(&root.join("common")).unwrap().is_none());
+    }
+
+    #[test]
+    fn prune_keeps_only_overrides() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(&root.join("common/settings.json"), "shared");
+        write(&root.join("common/skills/review/SKILL.md"), "review");
+        write(&root.join("work/settings.json"), "shared");
+        write(&root.join("work/skills/review/SKILL.md"), "review");
+        write(&root.join("work/skills/own/SKILL.md"), "own");
+        write(&root.join("work/model.json"), "work");
+        write(
+            &root.join("work").join(PROFILE_BASE_FILE),
+            "base = \"common\"\n",
+        );
+
+        prune_inherited(&root.join("work")).unwrap();
+
+        let work = root.join("work");
+        assert!(!work.join("settings.json").exists());
+        assert!(!work.join("skills/review").exists());
+        assert!(work.join("skills/own/SKILL.md").exists());
+        assert!(work.join("model.json").exists());
+        assert!(work.join(PROFILE_BASE_FILE).exists());
+    }
+}
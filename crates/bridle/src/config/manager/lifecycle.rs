@@ -1,15 +1,53 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use chrono::Local;
+use chrono::{Local, NaiveDateTime};
 use harness_locate::Harness;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
 use super::ProfileManager;
-use super::files;
-use crate::config::BridleConfig;
+use super::journal::{SwitchJournal, clear_journal, lock_switch, write_journal};
+use super::{dedupe, files, layers, retry};
+use crate::config::profile_history::{self, HistoryEvent};
 use crate::config::profile_name::ProfileName;
+use crate::config::{
+    BridleConfig, PROFILE_BASE_FILE, PROFILE_HISTORY_FILE, PROFILE_LINKS_FILE, PROFILE_META_FILE,
+    ProfileMeta, profile_links,
+};
+use crate::diagnostics;
 use crate::error::{Error, Result};
 use crate::harness::HarnessConfig;
 
+/// File inside a live-config backup recording which profile was active.
+pub(super) const BACKUP_ACTIVE_FILE: &str = ".bridle_backup_active";
+
+/// Timestamp format of [`ProfileManager::backup_current`] directory names.
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// A live-config snapshot taken by [`ProfileManager::backup_current`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    /// When the snapshot was taken, parsed from its directory name.
+    pub taken_at: NaiveDateTime,
+}
+
+/// What [`ProfileManager::switch_profile_reporting`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwitchReport {
+    /// Live config directory the profile was copied into.
+    pub target_dir: PathBuf,
+    /// False when the profile was already active and nothing was touched.
+    pub switched: bool,
+    /// Snapshot of a live config that no profile owned, taken before it was replaced.
+    pub backed_up: Option<PathBuf>,
+    /// Previously active profile that the live config was saved into.
+    pub saved_outgoing: Option<ProfileName>,
+    /// Non-fatal warnings raised during the switch.
+    pub warnings: Vec<String>,
+}
+
 impl ProfileManager {
     pub fn backups_dir(&self) -> PathBuf {
         self.profiles_dir
@@ -19,8 +57,34 @@ impl ProfileManager {
             .join("backups")
     }
 
-    pub fn backup_current(&self, harness: &dyn HarnessConfig) -> Result<PathBuf> {
+    /// Directory holding the live-config snapshots of `harness`:
+    /// `backups/<harness>` for the global config, and one directory per
+    /// project under `backups/<harness>/projects` otherwise, so restoring a
+    /// global backup never brings back a project's files.
+    pub(super) fn live_backups_dir(&self, harness: &dyn HarnessConfig) -> PathBuf {
+        let dir = self.backups_dir().join(harness.id());
+        let key = harness.active_key();
+        if key == harness.id() {
+            return dir;
+        }
+        let digest: String = Sha256::digest(key.as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        dir.join("projects").join(digest)
+    }
+
+    /// Snapshots the live config, and the resource directories of
+    /// `harness_for_resources` when given, into a timestamped directory of
+    /// [`live_backups_dir`](Self::live_backups_dir).
+    pub fn backup_current(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+    ) -> Result<PathBuf> {
         let source_dir = harness.config_dir()?;
+        ensure_dir_or_missing(&source_dir)?;
         let has_config_dir = source_dir.exists();
         let has_mcp = harness
             .mcp_config_path()
@@ -34,24 +98,358 @@ impl ProfileManager {
             )));
         }
 
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let backup_path = self.backups_dir().join(harness.id()).join(&timestamp);
+        let timestamp = Local::now().format(BACKUP_TIMESTAMP_FORMAT).to_string();
+        let backup_path = self.live_backups_dir(harness).join(&timestamp);
 
         std::fs::create_dir_all(&backup_path)?;
         files::copy_config_files(harness, true, &backup_path)?;
+        if let Some(h) = harness_for_resources {
+            files::copy_resource_directories(h, &harness.scope(), true, &backup_path)?;
+        }
+        if let Some(active) = BridleConfig::load().ok().and_then(|c| {
+            c.active_profile_for(&harness.active_key())
+                .map(String::from)
+        }) {
+            std::fs::write(backup_path.join(BACKUP_ACTIVE_FILE), active)?;
+        }
 
-        let extra_dir = self.backups_dir().join(harness.id()).join("extra");
+        let extra_dir = self.live_backups_dir(harness).join("extra");
         let _ = files::backup_session_data(&source_dir, &extra_dir);
 
         Ok(backup_path)
     }
 
+    /// Every [`backup_current`](Self::backup_current) snapshot for `harness`,
+    /// oldest first.
+    ///
+    /// Directories whose names are not backup timestamps (such as `extra`)
+    /// are skipped, and so are the backups of other scopes.
+    pub fn list_backups(&self, harness: &dyn HarnessConfig) -> Result<Vec<BackupEntry>> {
+        let dir = self.live_backups_dir(harness);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let taken_at = entry
+                .file_name()
+                .to_str()
+                .and_then(|n| NaiveDateTime::parse_from_str(n, BACKUP_TIMESTAMP_FORMAT).ok());
+            if let Some(taken_at) = taken_at
+                && entry.file_type()?.is_dir()
+            {
+                backups.push(BackupEntry {
+                    path: entry.path(),
+                    taken_at,
+                });
+            }
+        }
+        backups.sort_by_key(|b| b.taken_at);
+        Ok(backups)
+    }
+
+    /// The most recent [`backup_current`](Self::backup_current) snapshot for
+    /// `harness`, if any.
+    pub fn latest_backup(&self, harness: &dyn HarnessConfig) -> Result<Option<PathBuf>> {
+        Ok(self.list_backups(harness)?.pop().map(|b| b.path))
+    }
+
+    /// The backup named by `timestamp` (as in `backup list`), or the newest
+    /// one for `latest`.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoBackup`] for `latest` when the harness has no
+    /// backups, and [`Error::InvalidValue`] if no backup has that timestamp.
+    pub fn find_backup(&self, harness: &dyn HarnessConfig, timestamp: &str) -> Result<PathBuf> {
+        if timestamp == "latest" {
+            return self
+                .latest_backup(harness)?
+                .ok_or_else(|| Error::NoBackup(harness.id().to_string()));
+        }
+        self.list_backups(harness)?
+            .into_iter()
+            .map(|b| b.path)
+            .find(|path| path.file_name().is_some_and(|n| n == timestamp))
+            .ok_or_else(|| {
+                Error::InvalidValue(format!("no {} backup named {}", harness.id(), timestamp))
+            })
+    }
+
+    /// Replaces the live config with the contents of `backup_path`.
+    ///
+    /// With `harness_for_resources`, the resource directories the backup
+    /// holds are copied back to that harness's resource paths rather than
+    /// into the live config under their canonical names.
+    ///
+    /// Returns the profile that was active when the backup was taken.
+    pub fn restore_backup(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        backup_path: &Path,
+    ) -> Result<Option<String>> {
+        let target_dir = harness.config_dir()?;
+        ensure_dir_or_missing(&target_dir)?;
+        std::fs::create_dir_all(&target_dir)?;
+        let mut keep_live = vec![BACKUP_ACTIVE_FILE];
+        if harness_for_resources.is_some() {
+            keep_live.extend_from_slice(files::CANONICAL_RESOURCE_DIRS);
+        }
+        files::switch_config_dir_safely_with_progress(
+            backup_path,
+            &target_dir,
+            &self.live_backups_dir(harness),
+            &keep_live,
+            self.fs_retries,
+            &mut |_| {},
+        )?;
+        if let Some(h) = harness_for_resources {
+            let scope = harness.scope();
+            files::clear_resource_directories(h, &scope, backup_path, self.fs_retries)?;
+            files::copy_resource_directories(h, &scope, false, backup_path)?;
+        }
+        restore_mcp_file(harness, backup_path)?;
+        Ok(
+            std::fs::read_to_string(backup_path.join(BACKUP_ACTIVE_FILE))
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+        )
+    }
+
+    /// Whether profile `name` is locked against being switched away from,
+    /// deleted, or compressed.
+    pub(super) fn is_locked(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> bool {
+        ProfileMeta::load(&self.profile_path(harness, name)).is_ok_and(|meta| meta.locked)
+    }
+
+    /// Fails with [`Error::ProfileLocked`] when the active profile of
+    /// `harness` is locked, unless locks are ignored.
+    fn ensure_active_unlocked(&self, harness: &dyn HarnessConfig) -> Result<()> {
+        if !self.ignore_locks
+            && let Ok(config) = BridleConfig::load()
+            && let Some(active_name) = config.active_profile_for(&harness.active_key())
+            && let Ok(active_profile) = ProfileName::new(active_name)
+            && self.is_locked(harness, &active_profile)
+        {
+            return Err(Error::ProfileLocked(active_name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Undoes the last switch: saves the live config into the active profile,
+    /// restores the most recent backup, and reactivates the profile that was
+    /// active when it was taken (or clears the active profile).
+    ///
+    /// Returns the restored backup and the now-active profile.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoBackup`] if the harness has no backups, and
+    /// [`Error::ProfileLocked`] if the active profile is locked.
+    pub fn rollback(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+    ) -> Result<(PathBuf, Option<String>)> {
+        let backup = self
+            .latest_backup(harness)?
+            .ok_or_else(|| Error::NoBackup(harness.id().to_string()))?;
+        self.ensure_active_unlocked(harness)?;
+
+        let mut config = BridleConfig::load().unwrap_or_default();
+        if let Some(current) = config
+            .active_profile_for(&harness.active_key())
+            .and_then(|n| ProfileName::new(n).ok())
+        {
+            self.save_to_profile(harness, harness_for_resources, &current)?;
+        }
+
+        let restored = self
+            .restore_backup(harness, harness_for_resources, &backup)?
+            .filter(|name| ProfileName::new(name).is_ok_and(|n| self.profile_exists(harness, &n)));
+        match &restored {
+            Some(name) => config.set_active_profile(&harness.active_key(), name),
+            None => config.clear_active_profile(&harness.active_key()),
+        }
+        config.save()?;
+        Self::update_marker_file(
+            harness,
+            restored.as_deref(),
+            config.profile_marker_enabled(),
+        )?;
+        Ok((backup, restored))
+    }
+
+    /// Copies a saved profile to `backups/<harness>/profiles/<name>/<timestamp>`.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist, or IO error on copy.
+    pub fn backup_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<PathBuf> {
+        let profile_path = self.profile_path(harness, name);
+        if !profile_path.exists() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let backup_path = self
+            .backups_dir()
+            .join(harness.id())
+            .join("profiles")
+            .join(name.as_str())
+            .join(&timestamp);
+        files::copy_all_contents(&profile_path, &backup_path)?;
+        Ok(backup_path)
+    }
+
+    /// Replaces an existing profile with the current config, backing it up first.
+    ///
+    /// Returns the new profile path and the backup path (if the profile existed).
+    ///
+    /// # Errors
+    /// Returns error if the live config exceeds `profile.max_capture_bytes`,
+    /// or if backup, removal, or copy fails.
+    pub fn recreate_from_current(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+    ) -> Result<(PathBuf, Option<PathBuf>)> {
+        if !self.profile_exists(harness, name) {
+            let path =
+                self.create_from_current_with_resources(harness, harness_for_resources, name)?;
+            return Ok((path, None));
+        }
+        self.expand_profile(harness, name)?;
+
+        self.check_capture_limit(harness, self.capture_skip())?;
+        self.ensure_writable()?;
+        let path = self.profile_path(harness, name);
+        let meta = ProfileMeta::load(&path)?;
+        let kept: Vec<(&str, Vec<u8>)> = [PROFILE_LINKS_FILE, PROFILE_HISTORY_FILE]
+            .into_iter()
+            .filter_map(|file| Some((file, std::fs::read(path.join(file)).ok()?)))
+            .collect();
+        let backup = self.backup_profile(harness, name)?;
+        retry::remove_dir_all(&path, self.fs_retries)?;
+
+        std::fs::create_dir_all(&path)?;
+        meta.save(&path)?;
+        for (file, contents) in kept {
+            std::fs::write(path.join(file), contents)?;
+        }
+        if self.skip_resources {
+            // Only the config files are replaced; the stored resources stay.
+            for dir in files::RESOURCE_DIR_NAMES {
+                let stored = backup.join(dir);
+                if stored.is_dir() {
+                    std::fs::create_dir_all(path.join(dir))?;
+                    files::copy_all_contents(&stored, &path.join(dir))?;
+                }
+            }
+        }
+        self.capture_into(harness, harness_for_resources, &path)?;
+        profile_history::note(&path, HistoryEvent::Synced);
+        if let Ok(mut config) = BridleConfig::load() {
+            config.set_active_profile(&harness.active_key(), name.as_str());
+            let _ = config.save();
+        }
+        Ok((path, Some(backup)))
+    }
+
+    /// Copies the live config into an existing profile dir, honoring its metadata.
+    pub(super) fn capture_into(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        profile_path: &std::path::Path,
+    ) -> Result<()> {
+        let meta = ProfileMeta::load(profile_path)?;
+        // `--no-resources` also covers resource dirs inside the config dir.
+        files::capture_config_files(
+            harness,
+            profile_path,
+            &self.capture_filter,
+            self.capture_skip(),
+        )?;
+        note_captured_with(harness, profile_path)?;
+        if !meta.capture_resources {
+            for name in files::RESOURCE_DIR_NAMES {
+                let dir = profile_path.join(name);
+                if dir.is_dir() {
+                    std::fs::remove_dir_all(dir)?;
+                }
+            }
+        } else if let Some(h) = harness_for_resources.filter(|_| !self.skip_resources) {
+            files::copy_resource_directories(h, &harness.scope(), true, profile_path)?;
+        }
+        self.dedupe_if_enabled(profile_path)
+    }
+
+    /// Hardlinks the profile's files against the rest of the store when
+    /// `profile.dedupe` is on.
+    fn dedupe_if_enabled(&self, profile_path: &std::path::Path) -> Result<()> {
+        let enabled = BridleConfig::load()
+            .map(|c| c.profile.dedupe)
+            .unwrap_or(false);
+        if enabled {
+            dedupe::link_duplicates(profile_path, self.profiles_dir(), self.fs_retries)?;
+        }
+        Ok(())
+    }
+
+    /// Live entries a switch to or save of this profile must leave untouched.
+    fn resource_dirs_to_skip(
+        &self,
+        profile_path: &std::path::Path,
+    ) -> Result<&'static [&'static str]> {
+        let meta = ProfileMeta::load(profile_path)?;
+        Ok(if meta.capture_resources && !self.skip_resources {
+            &[]
+        } else {
+            files::RESOURCE_DIR_NAMES
+        })
+    }
+
+    /// Enforces `profile.max_capture_bytes` against what a capture of the
+    /// harness's live config dir would copy, leaving out the top-level `skip`.
+    pub(super) fn check_capture_limit(
+        &self,
+        harness: &dyn HarnessConfig,
+        skip: &[&str],
+    ) -> Result<()> {
+        let limit = BridleConfig::load()
+            .ok()
+            .and_then(|c| c.profile.max_capture_bytes);
+        match limit {
+            Some(limit) => {
+                files::check_capture_size(&harness.config_dir()?, limit, &self.capture_filter, skip)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Top-level live entries a capture leaves out: the resource dirs under
+    /// `--no-resources`.
+    pub(super) fn capture_skip(&self) -> &'static [&'static str] {
+        if self.skip_resources {
+            files::RESOURCE_DIR_NAMES
+        } else {
+            &[]
+        }
+    }
+
     pub fn save_to_profile(
         &self,
         harness: &dyn HarnessConfig,
         harness_for_resources: Option<&Harness>,
         name: &ProfileName,
     ) -> Result<()> {
+        self.expand_profile(harness, name)?;
         let profile_path = self.profile_path(harness, name);
         if !profile_path.exists() {
             return Ok(());
@@ -66,18 +464,34 @@ impl ProfileManager {
         if !has_config {
             return Ok(());
         }
+        let skip = self.resource_dirs_to_skip(&profile_path)?;
+        self.check_capture_limit(harness, skip)?;
+        // A one-off skip keeps the resources the profile already holds.
+        let keep_stored = if self.skip_resources { skip } else { &[] };
 
         for entry in std::fs::read_dir(&profile_path)? {
             let entry = entry?;
+            if [
+                PROFILE_META_FILE,
+                PROFILE_LINKS_FILE,
+                PROFILE_BASE_FILE,
+                PROFILE_HISTORY_FILE,
+            ]
+            .iter()
+            .chain(keep_stored)
+            .any(|keep| entry.file_name() == *keep)
+            {
+                continue;
+            }
             let path = entry.path();
             if path.is_file() {
-                std::fs::remove_file(&path)?;
+                retry::remove_file(&path, self.fs_retries)?;
             } else if path.is_dir() {
-                std::fs::remove_dir_all(&path)?;
+                retry::remove_dir_all(&path, self.fs_retries)?;
             }
         }
 
-        files::copy_all_contents(&source_dir, &profile_path)?;
+        files::copy_all_contents_except(&source_dir, &profile_path, skip)?;
         if let Some(mcp_path) = harness.mcp_config_path()
             && mcp_path.exists()
             && mcp_path.is_file()
@@ -86,7 +500,11 @@ impl ProfileManager {
             let dest = profile_path.join(filename);
             std::fs::copy(&mcp_path, dest)?;
         }
-        let _ = harness_for_resources;
+        note_captured_with(harness, &profile_path)?;
+        profile_links::prune(&profile_path, harness_for_resources)?;
+        layers::prune_inherited(&profile_path)?;
+        self.dedupe_if_enabled(&profile_path)?;
+        profile_history::note(&profile_path, HistoryEvent::Synced);
         Ok(())
     }
 
@@ -104,73 +522,218 @@ impl ProfileManager {
         harness_for_resources: Option<&Harness>,
         name: &ProfileName,
     ) -> Result<PathBuf> {
+        self.switch_profile_with_progress(harness, harness_for_resources, name, &mut |_| {})
+    }
+
+    /// Switches profiles, calling `progress` with each profile entry copied into the live config.
+    ///
+    /// The outgoing profile is saved first; if that fails the switch stops
+    /// before the live config is touched, so unsaved edits survive.
+    pub fn switch_profile_with_progress(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        progress: &mut dyn FnMut(&std::path::Path),
+    ) -> Result<PathBuf> {
+        self.switch_profile_reporting(harness, harness_for_resources, name, progress)
+            .map(|report| report.target_dir)
+    }
+
+    /// Like [`switch_profile_with_progress`](Self::switch_profile_with_progress),
+    /// also describing what the switch did besides replacing the live config.
+    ///
+    /// Warnings raised along the way are collected into the report and still
+    /// passed on to the diagnostics logger once the switch finishes.
+    pub fn switch_profile_reporting(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        progress: &mut dyn FnMut(&std::path::Path),
+    ) -> Result<SwitchReport> {
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&warnings);
+        let mut report = SwitchReport::default();
+        let result = diagnostics::with_logger(
+            Arc::new(move |m| sink.lock().unwrap().push(m.to_string())),
+            || self.switch_and_report(harness, harness_for_resources, name, progress, &mut report),
+        );
+        report.warnings = std::mem::take(&mut *warnings.lock().unwrap());
+        for warning in &report.warnings {
+            diagnostics::warn(warning);
+        }
+        result.map(|()| report)
+    }
+
+    fn switch_and_report(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        progress: &mut dyn FnMut(&std::path::Path),
+        report: &mut SwitchReport,
+    ) -> Result<()> {
+        self.expand_profile(harness, name)?;
         let profile_path = self.profile_path(harness, name);
 
         if !profile_path.exists() {
             return Err(Error::ProfileNotFound(name.as_str().to_string()));
         }
 
-        let harness_id = harness.id();
+        let target_dir = harness.config_dir()?;
+        ensure_dir_or_missing(&target_dir)?;
+        crate::config::profile_base::base_chain(&profile_path)?;
+        report.target_dir = target_dir.clone();
+
+        let active_key = harness.active_key();
 
         // Check if already on this profile - if so, it's a no-op
         // (avoids wiping changes made since activation)
         if let Ok(config) = BridleConfig::load()
-            && let Some(active_name) = config.active_profile_for(harness_id)
+            && let Some(active_name) = config.active_profile_for(&active_key)
             && active_name == name.as_str()
         {
-            return Ok(profile_path);
+            return Ok(());
         }
+        self.ensure_active_unlocked(harness)?;
+        self.ensure_writable()?;
 
-        let saved_to_profile = if let Ok(config) = BridleConfig::load()
-            && let Some(active_name) = config.active_profile_for(harness_id)
+        if let Ok(config) = BridleConfig::load()
+            && let Some(active_name) = config.active_profile_for(&active_key)
             && let Ok(active_profile) = ProfileName::new(active_name)
             && active_profile.as_str() != name.as_str()
         {
             self.save_to_profile(harness, harness_for_resources, &active_profile)?;
-            true
-        } else {
-            false
-        };
-
-        let target_dir = harness.config_dir()?;
+            report.saved_outgoing = Some(active_profile);
+        }
 
         // If no active profile was saved, backup current state to "no-profile" folder
         // This preserves unknown files when switching for the first time
-        if !saved_to_profile && target_dir.exists() {
-            let no_profile_backup = self.backups_dir().join(harness.id()).join("no-profile");
+        if report.saved_outgoing.is_none() && target_dir.exists() {
+            let no_profile_backup = self.live_backups_dir(harness).join("no-profile");
             let _ = std::fs::remove_dir_all(&no_profile_backup);
             std::fs::create_dir_all(&no_profile_backup)?;
             files::copy_all_contents(&target_dir, &no_profile_backup)?;
+            report.backed_up = Some(no_profile_backup);
         }
 
+        let from = report.saved_outgoing.as_ref().map(ToString::to_string);
+        self.apply_profile(harness, harness_for_resources, name, from, progress)?;
+        profile_history::note(&profile_path, HistoryEvent::Switched);
+        report.switched = true;
+        Ok(())
+    }
+
+    /// Replaces the live config with profile `name` under a switch journal
+    /// and marks it active. `from` is the profile the live config held.
+    fn apply_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        from: Option<String>,
+        progress: &mut dyn FnMut(&std::path::Path),
+    ) -> Result<()> {
+        let profile_path = self.profile_path(harness, name);
+        let target_dir = harness.config_dir()?;
+        let active_key = harness.active_key();
+
         if !target_dir.exists() {
             std::fs::create_dir_all(&target_dir)?;
         }
 
-        let backup_dir = self.backups_dir().join(harness.id());
-        files::switch_config_dir_safely(&profile_path, &target_dir, &backup_dir)?;
+        let backup_dir = self.live_backups_dir(harness);
+        let _lock = lock_switch(&backup_dir)?;
+        let staged = self.stage_layers(harness.id(), &profile_path)?;
+        let source = staged.as_deref().unwrap_or(&profile_path);
+        let keep_live = self.resource_dirs_to_skip(&profile_path)?;
+        let mut journal = SwitchJournal {
+            harness_id: harness.id().to_string(),
+            active_key: active_key.clone(),
+            from,
+            to: name.to_string(),
+            profile_path: profile_path.clone(),
+            config_dir: target_dir.clone(),
+            backup_path: None,
+            keep_live: keep_live.iter().map(ToString::to_string).collect(),
+        };
+        let switched = files::switch_config_dir_journaled(
+            source,
+            &target_dir,
+            &backup_dir,
+            keep_live,
+            self.fs_retries,
+            progress,
+            &mut |backup| {
+                journal.backup_path = backup.map(Path::to_path_buf);
+                write_journal(&backup_dir, &journal, self.fs_retries)
+            },
+        )
+        .and_then(|()| restore_mcp_file(harness, source));
+        if let Some(staged) = &staged {
+            let _ = std::fs::remove_dir_all(staged);
+        }
+        if let Err(e) = switched {
+            // The switch rolled itself back, so there is nothing to recover.
+            clear_journal(&backup_dir);
+            return Err(e);
+        }
 
-        if let Some(mcp_path) = harness.mcp_config_path()
-            && let Some(filename) = mcp_path.file_name()
-        {
-            let mcp_in_profile = profile_path.join(filename);
-            if mcp_in_profile.exists() {
-                std::fs::copy(&mcp_in_profile, &mcp_path)?;
-            }
+        profile_links::apply(&profile_path, &target_dir, harness_for_resources)?;
+        Self::mark_active(&target_dir, &active_key, name.as_str())?;
+        clear_journal(&backup_dir);
+        Ok(())
+    }
+
+    /// Throws away edits to the live config by laying the active profile's
+    /// stored contents back over it. The live config is backed up first and,
+    /// unlike a switch, is not saved into the profile.
+    ///
+    /// Returns the profile re-applied and the backup, if there was a live
+    /// config to back up.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoActiveProfile`] if the harness has no active
+    /// profile, and [`Error::ProfileNotFound`] if its directory is gone.
+    pub fn reset_to_stored(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+    ) -> Result<(ProfileName, Option<PathBuf>)> {
+        let name = BridleConfig::load()
+            .unwrap_or_default()
+            .active_profile_for(&harness.active_key())
+            .and_then(|n| ProfileName::new(n).ok())
+            .ok_or(Error::NoActiveProfile)?;
+        let profile_path = self.profile_path(harness, &name);
+        if !profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
         }
+        crate::config::profile_base::base_chain(&profile_path)?;
 
-        let _ = harness_for_resources;
+        let backup = match self.backup_current(harness, harness_for_resources) {
+            Ok(backup) => Some(backup),
+            Err(Error::NoConfigFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let from = Some(name.to_string());
+        self.apply_profile(harness, harness_for_resources, &name, from, &mut |_| {})?;
+        Ok((name, backup))
+    }
 
+    /// Records `name` as the active profile and refreshes the marker file in
+    /// `target_dir`.
+    pub(super) fn mark_active(target_dir: &Path, active_key: &str, name: &str) -> Result<()> {
         let mut config = BridleConfig::load().unwrap_or_default();
-        config.set_active_profile(harness.id(), name.as_str());
+        config.set_active_profile(active_key, name);
         config.save()?;
 
-        Self::delete_marker_files(&target_dir)?;
+        Self::delete_marker_files(target_dir)?;
         if config.profile_marker_enabled() {
-            Self::create_marker_file(&target_dir, name.as_str())?;
+            Self::create_marker_file(target_dir, name)?;
         }
-
-        Ok(target_dir)
+        Ok(())
     }
 
     pub fn update_marker_file(
@@ -186,3 +749,43 @@ impl ProfileManager {
         Ok(())
     }
 }
+
+/// Records the harness version the profile at `profile_path` was just
+/// captured with, when it differs from the stored one.
+fn note_captured_with(harness: &dyn HarnessConfig, profile_path: &Path) -> Result<()> {
+    let meta = ProfileMeta::load(profile_path)?;
+    let captured_with = harness.version();
+    if captured_with != meta.captured_with {
+        ProfileMeta {
+            captured_with,
+            ..meta
+        }
+        .save(profile_path)?;
+    }
+    Ok(())
+}
+
+/// Copies the MCP config stored in `source` (a profile or backup) to the
+/// harness's MCP config path, which may live outside its config dir.
+fn restore_mcp_file(harness: &dyn HarnessConfig, source: &Path) -> Result<()> {
+    if let Some(mcp_path) = harness.mcp_config_path()
+        && let Some(filename) = mcp_path.file_name()
+    {
+        let stored = source.join(filename);
+        if stored.exists() {
+            std::fs::copy(&stored, &mcp_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Rejects a harness config dir path that exists but is not a directory.
+fn ensure_dir_or_missing(path: &std::path::Path) -> Result<()> {
+    if path.exists() && !path.is_dir() {
+        return Err(Error::Config(format!(
+            "config dir is not a directory: {}",
+            path.display()
+        )));
+    }
+    Ok(())
+}
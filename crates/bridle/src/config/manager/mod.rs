@@ -3,19 +3,39 @@
 //! This module provides [`ProfileManager`], the central coordinator for all profile
 //! operations including creation, deletion, switching, and configuration extraction.
 
+mod capture_filter;
+mod compressed;
+mod dedupe;
+mod drift;
 mod extraction;
 mod files;
+mod fingerprint;
+mod journal;
+mod layers;
 mod lifecycle;
+mod orphans;
+mod parallel;
+mod retry;
+mod verify;
+
+pub use capture_filter::CaptureFilter;
+pub use journal::{RecoveredSwitch, RecoveryAction};
+pub use lifecycle::{BackupEntry, SwitchReport};
+pub use verify::{ConfigValidation, Severity, VerifyIssue, VerifyReport};
 
 use std::path::PathBuf;
 
 use harness_locate::{Harness, InstallationStatus};
 
 use super::BridleConfig;
+use super::profile_history::{self, HistoryEntry, HistoryEvent};
+use super::profile_links::{self, ProfileLinks};
+use super::profile_meta::ProfileMeta;
 use super::profile_name::ProfileName;
-use super::types::ProfileInfo;
+use super::templates::{self, ProfileTemplate};
+use super::types::{McpServerInfo, ProfileInfo};
 use crate::error::{Error, Result};
-use crate::harness::HarnessConfig;
+use crate::harness::{CustomHarness, HarnessConfig, ScopedHarness};
 
 /// Manages harness configuration profiles.
 ///
@@ -38,6 +58,10 @@ use crate::harness::HarnessConfig;
 #[derive(Debug)]
 pub struct ProfileManager {
     profiles_dir: PathBuf,
+    capture_filter: CaptureFilter,
+    skip_resources: bool,
+    ignore_locks: bool,
+    fs_retries: u32,
 }
 
 const MARKER_PREFIX: &str = "BRIDLE_PROFILE_";
@@ -45,7 +69,42 @@ const MARKER_PREFIX: &str = "BRIDLE_PROFILE_";
 impl ProfileManager {
     /// Creates a new profile manager with the given profiles directory.
     pub fn new(profiles_dir: PathBuf) -> Self {
-        Self { profiles_dir }
+        Self {
+            profiles_dir,
+            capture_filter: CaptureFilter::default(),
+            skip_resources: false,
+            ignore_locks: false,
+            fs_retries: super::ProfileConfig::default().fs_retries,
+        }
+    }
+
+    /// Applies `filter` on top of the default exclusions whenever this
+    /// manager captures the live config into a profile.
+    pub fn with_capture_filter(mut self, filter: CaptureFilter) -> Self {
+        self.capture_filter = filter;
+        self
+    }
+
+    /// Leaves skills, agents, commands, and plugins out of every capture and
+    /// switch this manager makes, as if each profile had `capture_resources`
+    /// off, without changing any profile's metadata.
+    pub fn without_resources(mut self) -> Self {
+        self.skip_resources = true;
+        self
+    }
+
+    /// Lets this manager switch away from a locked active profile. Locked
+    /// profiles still can't be deleted.
+    pub fn ignoring_locks(mut self) -> Self {
+        self.ignore_locks = true;
+        self
+    }
+
+    /// Retries a file another process has locked up to `retries` times, as
+    /// `profile.fs_retries` sets, instead of the default.
+    pub fn with_fs_retries(mut self, retries: u32) -> Self {
+        self.fs_retries = retries;
+        self
     }
 
     fn delete_marker_files(dir: &std::path::Path) -> Result<()> {
@@ -71,23 +130,72 @@ impl ProfileManager {
         Ok(())
     }
 
+    /// Fails with a readable error, before anything is touched, when the
+    /// profiles directory can't be written, such as on a read-only mount.
+    /// Reading profiles never needs this.
+    pub fn ensure_writable(&self) -> Result<()> {
+        let not_writable = |e: std::io::Error| {
+            Error::Config(format!(
+                "profiles directory is not writable: {}: {}",
+                self.profiles_dir.display(),
+                e
+            ))
+        };
+        std::fs::create_dir_all(&self.profiles_dir).map_err(not_writable)?;
+        let probe = self
+            .profiles_dir
+            .join(format!(".write-probe-{}", std::process::id()));
+        std::fs::File::create(&probe).map_err(not_writable)?;
+        let _ = std::fs::remove_file(&probe);
+        Ok(())
+    }
+
     /// Returns the base directory where all profiles are stored.
     pub fn profiles_dir(&self) -> &PathBuf {
         &self.profiles_dir
     }
 
+    /// Breaks any `profile.dedupe` hardlinks under `path` so an edit only
+    /// touches this profile.
+    ///
+    /// # Errors
+    /// Returns an IO error if a linked file cannot be copied.
+    pub fn detach_hardlinks(&self, path: &std::path::Path) -> Result<()> {
+        dedupe::detach_hardlinks(path, self.fs_retries)
+    }
+
     /// Returns the filesystem path for a specific profile.
     pub fn profile_path(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> PathBuf {
         self.profiles_dir.join(harness.id()).join(name.as_str())
     }
 
-    /// Checks if a profile exists on disk.
+    /// Checks if a profile exists on disk, as a directory or compressed.
     pub fn profile_exists(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> bool {
-        self.profile_path(harness, name).is_dir()
+        self.profile_path(harness, name).is_dir() || self.is_compressed(harness, name)
     }
 
     /// Lists all profiles for a harness, sorted alphabetically.
     ///
+    /// ```
+    /// use bridle::{ProfileManager, ProfileName};
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let dir = tempfile::tempdir()?;
+    /// let manager = ProfileManager::new(dir.path().to_path_buf());
+    /// let harness = Harness::new(HarnessKind::OpenCode);
+    ///
+    /// manager.create_profile(&harness, &ProfileName::new("work")?)?;
+    /// manager.create_profile(&harness, &ProfileName::new("personal")?)?;
+    ///
+    /// let names: Vec<String> = manager
+    ///     .list_profiles(&harness)?
+    ///     .iter()
+    ///     .map(|n| n.to_string())
+    ///     .collect();
+    /// assert_eq!(names, ["personal", "work"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
     /// # Errors
     /// Returns an error if the profiles directory cannot be read.
     pub fn list_profiles(&self, harness: &dyn HarnessConfig) -> Result<Vec<ProfileName>> {
@@ -100,22 +208,31 @@ impl ProfileManager {
         let mut profiles = Vec::new();
         for entry in std::fs::read_dir(&harness_dir)? {
             let entry = entry?;
-            if entry.file_type()?.is_dir()
-                && let Some(name) = entry.file_name().to_str()
-                && let Ok(profile_name) = ProfileName::new(name)
-            {
+            let file_type = entry.file_type()?;
+            let Some(name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            let name = match name.strip_suffix(compressed::COMPRESSED_SUFFIX) {
+                Some(stem) if file_type.is_file() => stem.to_string(),
+                _ if file_type.is_dir() => name,
+                _ => continue,
+            };
+            if let Ok(profile_name) = ProfileName::new(&name) {
                 profiles.push(profile_name);
             }
         }
 
         profiles.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        profiles.dedup();
         Ok(profiles)
     }
 
     /// Creates an empty profile directory.
     ///
     /// # Errors
-    /// Returns [`Error::ProfileExists`] if profile already exists, or IO error on failure.
+    /// Returns [`Error::ProfileExists`] if profile already exists,
+    /// [`Error::Config`] if the profiles directory is read-only, or IO error
+    /// on failure.
     pub fn create_profile(
         &self,
         harness: &dyn HarnessConfig,
@@ -126,8 +243,40 @@ impl ProfileManager {
         if path.exists() {
             return Err(Error::ProfileExists(name.as_str().to_string()));
         }
+        self.ensure_writable()?;
 
         std::fs::create_dir_all(&path)?;
+        ProfileMeta {
+            created_at: Some(chrono::Local::now().to_rfc3339()),
+            ..Default::default()
+        }
+        .save(&path)?;
+        profile_history::note(&path, HistoryEvent::Created);
+        Ok(path)
+    }
+
+    /// Creates a profile holding the files of a built-in starter `template`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidValue`] if bridle has no such template for the
+    /// harness, and [`Error::ProfileExists`] if the profile exists.
+    pub fn create_from_template(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+        template: ProfileTemplate,
+    ) -> Result<PathBuf> {
+        let files = templates::template_files(harness.id(), template).ok_or_else(|| {
+            Error::InvalidValue(format!(
+                "no '{}' template for {}",
+                template.as_str(),
+                harness.id()
+            ))
+        })?;
+        let path = self.create_profile(harness, name)?;
+        for (rel, contents) in files {
+            std::fs::write(path.join(rel), contents)?;
+        }
         Ok(path)
     }
 
@@ -146,27 +295,123 @@ impl ProfileManager {
     /// Creates a profile from current config, optionally including resource directories.
     ///
     /// # Errors
-    /// Returns error if profile exists or copy fails.
+    /// Returns error if profile exists, the live config exceeds
+    /// `profile.max_capture_bytes`, or copy fails.
     pub fn create_from_current_with_resources(
         &self,
         harness: &dyn HarnessConfig,
         harness_for_resources: Option<&Harness>,
         name: &ProfileName,
     ) -> Result<PathBuf> {
-        let profile_path = self.create_profile(harness, name)?;
-        files::copy_config_files(harness, true, &profile_path)?;
-        if let Some(h) = harness_for_resources {
-            files::copy_resource_directories(h, true, &profile_path)?;
+        if self.profile_exists(harness, name) {
+            return Err(Error::ProfileExists(name.as_str().to_string()));
         }
+        self.check_capture_limit(harness, self.capture_skip())?;
+
+        let profile_path = self.create_profile(harness, name)?;
+        self.capture_into(harness, harness_for_resources, &profile_path)?;
 
         if let Ok(mut config) = BridleConfig::load() {
-            config.set_active_profile(harness.id(), name.as_str());
+            config.set_active_profile(&harness.active_key(), name.as_str());
             let _ = config.save();
         }
 
         Ok(profile_path)
     }
 
+    /// Creates `to` for `to_harness` holding only the skills, agents, and
+    /// commands of `from_harness`'s profile `from`.
+    ///
+    /// Harness-specific config files are left behind. Resource directories are
+    /// renamed to the target harness's naming (e.g. `skill` for OpenCode).
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the source is missing,
+    /// [`Error::ProfileExists`] if the target exists, or IO error on copy
+    /// failure, in which case the half-copied target is removed.
+    pub fn copy_resources_to_new_profile(
+        &self,
+        from_harness: &dyn HarnessConfig,
+        from: &ProfileName,
+        to_harness: &Harness,
+        to: &ProfileName,
+    ) -> Result<PathBuf> {
+        let source = self.profile_path(from_harness, from);
+        if !source.is_dir() {
+            return Err(Error::ProfileNotFound(from.as_str().to_string()));
+        }
+        let target = self.create_profile(to_harness, to)?;
+
+        let copied = ["skills", "agents", "commands"]
+            .iter()
+            .try_for_each(|kind| {
+                let singular = kind.trim_end_matches('s');
+                let Some(src) = [kind, singular]
+                    .iter()
+                    .map(|d| source.join(d))
+                    .find(|d| d.is_dir())
+                else {
+                    return Ok(());
+                };
+                let dest = target.join(profile_links::live_subdir(Some(to_harness), kind));
+                files::copy_dir_filtered(&src, &dest)
+            });
+        if let Err(e) = copied {
+            let _ = std::fs::remove_dir_all(&target);
+            return Err(e);
+        }
+        Ok(target)
+    }
+
+    /// Moves profile `name` from `from_harness` to `to_harness`, keeping the
+    /// same name.
+    ///
+    /// Only what [`copy_resources_to_new_profile`](Self::copy_resources_to_new_profile)
+    /// carries over survives, plus the profile's description, tags, and
+    /// history. If the profile was active for `from_harness`, the live config
+    /// is synced into it first, so unsaved edits to its resources move too,
+    /// and that harness is left with no active profile and no marker file.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the source is missing,
+    /// [`Error::ProfileLocked`] if it is locked, and [`Error::ProfileExists`]
+    /// if `to_harness` already has a profile `name`.
+    pub fn move_profile(
+        &self,
+        from_harness: &dyn HarnessConfig,
+        name: &ProfileName,
+        to_harness: &Harness,
+    ) -> Result<PathBuf> {
+        let source = self.profile_path(from_harness, name);
+        let mut meta = ProfileMeta::load(&source)?;
+        if source.is_dir() && meta.locked {
+            return Err(Error::ProfileLocked(name.as_str().to_string()));
+        }
+        let mut config = BridleConfig::load()?;
+        let active_key = from_harness.active_key();
+        let was_active = config.active_profile_for(&active_key) == Some(name.as_str());
+        if was_active {
+            self.save_to_profile(from_harness, from_harness.resource_harness(), name)?;
+        }
+        let target = self.copy_resources_to_new_profile(from_harness, name, to_harness, name)?;
+
+        // A version of the old harness says nothing about the new one.
+        meta.captured_with = None;
+        meta.save(&target)?;
+        let history = source.join(profile_history::PROFILE_HISTORY_FILE);
+        if history.exists() {
+            std::fs::copy(&history, target.join(profile_history::PROFILE_HISTORY_FILE))?;
+        }
+        std::fs::remove_dir_all(&source)?;
+
+        if was_active {
+            config.clear_active_profile(&active_key);
+            config.save()?;
+            Self::update_marker_file(from_harness, None, false)?;
+        }
+        Ok(target)
+    }
+
     /// Creates a "default" profile from current harness config if it doesn't exist.
     ///
     /// Returns `Ok(true)` if profile was created, `Ok(false)` if it already existed
@@ -188,19 +433,98 @@ impl ProfileManager {
         Ok(true)
     }
 
+    /// Fails the way [`delete_profile`](Self::delete_profile) would, without
+    /// deleting anything, so a dry run can't promise a deletion the real run
+    /// refuses. Returns whether the profile is compressed.
+    pub fn ensure_deletable(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<bool> {
+        let compressed = self.is_compressed(harness, name);
+        if !compressed && !self.profile_path(harness, name).exists() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        if !compressed && self.is_locked(harness, name) {
+            return Err(Error::ProfileLocked(name.as_str().to_string()));
+        }
+        Ok(compressed)
+    }
+
     /// Deletes a profile and all its contents.
     ///
     /// # Errors
-    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist.
+    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist,
+    /// [`Error::ProfileLocked`] if its metadata locks it, and
+    /// [`Error::Config`] if the profiles directory is read-only.
     pub fn delete_profile(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> Result<()> {
         let path = self.profile_path(harness, name);
+        let compressed = self.ensure_deletable(harness, name)?;
+
+        self.ensure_writable()?;
+        if compressed {
+            std::fs::remove_file(self.compressed_path(harness, name))?;
+        } else {
+            std::fs::remove_dir_all(&path)?;
+        }
+        Ok(())
+    }
+
+    /// What has happened to profile `name`, oldest first.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist.
+    pub fn profile_history(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<Vec<HistoryEntry>> {
+        let path = self.profile_path(harness, name);
+        if !path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        profile_history::load(&path)
+    }
 
+    /// Where profile `name` is stored and how many files and bytes deleting
+    /// it would remove. A compressed profile counts as its one archive.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist.
+    pub fn profile_usage(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<(PathBuf, u64, u64)> {
+        if self.is_compressed(harness, name) {
+            let archive = self.compressed_path(harness, name);
+            let bytes = std::fs::metadata(&archive)?.len();
+            return Ok((archive, 1, bytes));
+        }
+        let path = self.profile_path(harness, name);
         if !path.exists() {
             return Err(Error::ProfileNotFound(name.as_str().to_string()));
         }
+        let (files, bytes) = files::dir_usage(&path)?;
+        Ok((path, files, bytes))
+    }
 
-        std::fs::remove_dir_all(&path)?;
-        Ok(())
+    /// Profiles whose names match `pattern` (`temp-*`, `*-old`, or an exact
+    /// name), sorted, leaving out the active profile so bulk deletes cannot
+    /// remove it.
+    pub fn inactive_profiles_matching(
+        &self,
+        harness: &dyn HarnessConfig,
+        pattern: &str,
+    ) -> Result<Vec<ProfileName>> {
+        let config = BridleConfig::load().unwrap_or_default();
+        let active = config.active_profile_for(&harness.active_key());
+        Ok(self
+            .list_profiles(harness)?
+            .into_iter()
+            .filter(|name| extraction::matches_pattern(Some(name.as_str()), pattern))
+            .filter(|name| Some(name.as_str()) != active)
+            .collect())
     }
 
     /// Extracts and returns detailed information about a profile.
@@ -211,217 +535,1409 @@ impl ProfileManager {
     /// # Errors
     /// Returns [`Error::ProfileNotFound`] if profile doesn't exist.
     pub fn show_profile(&self, harness: &Harness, name: &ProfileName) -> Result<ProfileInfo> {
-        let profile_path = self.profile_path(harness, name);
+        let config = BridleConfig::load().unwrap_or_default();
+        self.show_builtin_profile(harness, harness, name, true, &config)
+    }
+
+    /// Like [`show_profile`](Self::show_profile), for a profile of a live
+    /// config resolved for `harness`'s scope.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist.
+    pub fn show_scoped_profile(
+        &self,
+        harness: &ScopedHarness,
+        name: &ProfileName,
+    ) -> Result<ProfileInfo> {
+        let config = BridleConfig::load().unwrap_or_default();
+        self.show_builtin_profile(harness, harness.harness(), name, true, &config)
+    }
+
+    /// Like [`show_profile`](Self::show_profile), but always reads the stored
+    /// profile directory, even for the active profile, so the result doesn't
+    /// depend on edits made to the live config.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist.
+    pub fn show_stored_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<ProfileInfo> {
+        let config = BridleConfig::load().unwrap_or_default();
+        if let Some(resources) = harness.resource_harness() {
+            return self.show_builtin_profile(harness, resources, name, false, &config);
+        }
+        self.build_profile_info(harness, None, name, false, &config, |path, info| {
+            if let Some(custom) = harness.as_custom() {
+                extraction::extract_custom_resources(custom, path, info);
+            }
+        })
+    }
+
+    /// Shows profile `name` of `harness`, finding its resources where
+    /// `resources` keeps them for the harness's scope.
+    fn show_builtin_profile(
+        &self,
+        harness: &dyn HarnessConfig,
+        resources: &Harness,
+        name: &ProfileName,
+        read_live: bool,
+        config: &BridleConfig,
+    ) -> Result<ProfileInfo> {
+        let skill_depth = config.profile.skill_depth;
+        let scope = harness.scope();
+        self.build_profile_info(
+            harness,
+            Some(resources),
+            name,
+            read_live,
+            config,
+            |path, info| {
+                extraction::extract_builtin_resources(resources, &scope, path, skill_depth, info)
+            },
+        )
+    }
 
-        if !profile_path.exists() {
+    /// MCP servers of profile `name`, sorted per `profile.mcp_order`, plus
+    /// warnings about servers defined differently in several files.
+    ///
+    /// Read from the live config when the profile is active, as
+    /// [`show_profile`](Self::show_profile) does.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist.
+    pub fn profile_mcp_servers(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<(Vec<McpServerInfo>, Vec<String>)> {
+        if !self.profile_exists(harness, name) {
             return Err(Error::ProfileNotFound(name.as_str().to_string()));
         }
+        let config = BridleConfig::load().unwrap_or_default();
+        let order = config.profile.mcp_order;
+        if config.active_profile_for(&harness.active_key()) == Some(name.as_str()) {
+            return extraction::extract_mcp_servers(harness, &harness.config_dir()?, order);
+        }
+        if !self.is_compressed(harness, name) {
+            return extraction::extract_mcp_servers(
+                harness,
+                &self.profile_path(harness, name),
+                order,
+            );
+        }
+        let staged = self.stage_compressed(harness, name)?;
+        let servers = extraction::extract_mcp_servers(harness, &staged, order);
+        let _ = std::fs::remove_dir_all(&staged);
+        servers
+    }
+
+    /// Like [`show_profile`](Self::show_profile), for a harness defined by a
+    /// descriptor file.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist.
+    pub fn show_custom_profile(
+        &self,
+        harness: &CustomHarness,
+        name: &ProfileName,
+    ) -> Result<ProfileInfo> {
+        let config = BridleConfig::load().unwrap_or_default();
+        self.show_custom_with(harness, name, &config)
+    }
+
+    fn show_custom_with(
+        &self,
+        harness: &CustomHarness,
+        name: &ProfileName,
+        config: &BridleConfig,
+    ) -> Result<ProfileInfo> {
+        self.build_profile_info(harness, None, name, true, config, |path, info| {
+            extraction::extract_custom_resources(harness, path, info)
+        })
+    }
+
+    /// [`show_profile`](Self::show_profile) for every profile of `harness`,
+    /// in name order.
+    ///
+    /// With `keep_going`, a profile that fails to load is listed as a
+    /// placeholder carrying the error in `extraction_errors` instead of
+    /// aborting the whole listing.
+    ///
+    /// # Errors
+    /// Returns an error if the profiles directory cannot be read, or the first
+    /// load failure when `keep_going` is off.
+    pub fn show_profiles(&self, harness: &Harness, keep_going: bool) -> Result<Vec<ProfileInfo>> {
+        self.show_each(harness, keep_going, |name, config| {
+            self.show_builtin_profile(harness, harness, name, true, config)
+        })
+    }
+
+    /// Like [`show_profiles`](Self::show_profiles), for a harness defined by a
+    /// descriptor file.
+    pub fn show_custom_profiles(
+        &self,
+        harness: &CustomHarness,
+        keep_going: bool,
+    ) -> Result<Vec<ProfileInfo>> {
+        self.show_each(harness, keep_going, |name, config| {
+            self.show_custom_with(harness, name, config)
+        })
+    }
+
+    /// Loads every profile with `show`, several at a time, against one read
+    /// of bridle's config.
+    fn show_each(
+        &self,
+        harness: &dyn HarnessConfig,
+        keep_going: bool,
+        show: impl Fn(&ProfileName, &BridleConfig) -> Result<ProfileInfo> + Sync,
+    ) -> Result<Vec<ProfileInfo>> {
+        let names = self.list_profiles(harness)?;
+        let config = BridleConfig::load().unwrap_or_default();
+        let loaded =
+            parallel::map_in_order(&names, parallel::worker_cap(), |name| show(name, &config));
+        let mut infos = Vec::new();
+        for (name, result) in names.iter().zip(loaded) {
+            match result {
+                Ok(info) => infos.push(info),
+                Err(e) if keep_going => infos.push(ProfileInfo {
+                    name: name.as_str().to_string(),
+                    harness_id: harness.id().to_string(),
+                    path: self.profile_path(harness, name),
+                    extraction_errors: vec![format!("Failed to load profile: {}", e)],
+                    ..Default::default()
+                }),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(infos)
+    }
+
+    fn build_profile_info(
+        &self,
+        harness: &dyn HarnessConfig,
+        resource_harness: Option<&Harness>,
+        name: &ProfileName,
+        read_live: bool,
+        config: &BridleConfig,
+        extract_resources: impl FnOnce(&std::path::Path, &mut ProfileInfo),
+    ) -> Result<ProfileInfo> {
+        let mut stored_path = self.profile_path(harness, name);
+        let staged = if self.is_compressed(harness, name) {
+            stored_path = self.compressed_path(harness, name);
+            Some(self.stage_compressed(harness, name)?)
+        } else if stored_path.exists() {
+            None
+        } else {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        };
+        let profile_path = staged.clone().unwrap_or_else(|| stored_path.clone());
 
         let harness_id = harness.id().to_string();
-        let is_active = BridleConfig::load()
-            .map(|c| c.active_profile_for(&harness_id) == Some(name.as_str()))
-            .unwrap_or(false);
+        let is_active = config.active_profile_for(&harness.active_key()) == Some(name.as_str());
 
         let live_harness_path = harness.config_dir().unwrap_or(profile_path.clone());
-        let extraction_path = if is_active {
+        let extraction_path = if is_active && read_live {
             live_harness_path
         } else {
             profile_path.clone()
         };
 
-        let theme = extraction::extract_theme(harness, &extraction_path);
-        let model = extraction::extract_model(harness, &extraction_path);
+        let mut info = ProfileInfo {
+            name: name.as_str().to_string(),
+            harness_id,
+            is_active,
+            path: stored_path,
+            theme: extraction::extract_theme(harness, &extraction_path),
+            model: extraction::extract_model(harness, &extraction_path),
+            ..Default::default()
+        };
 
-        let mut extraction_errors = Vec::new();
+        if is_active && read_live {
+            info.effective_model =
+                extraction::effective_model(harness.id(), info.model.as_deref(), |var| {
+                    std::env::var(var).ok()
+                });
+        }
+
+        let meta = ProfileMeta::load(&profile_path).unwrap_or_else(|e| {
+            info.extraction_errors
+                .push(format!("Profile metadata: {}", e));
+            ProfileMeta::default()
+        });
+        info.description = meta.description;
+        info.tags = meta.tags;
+        info.created_at = meta.created_at;
+        info.captured_with = meta.captured_with;
+        info.locked = meta.locked;
+        info.base = layers::layering(&profile_path).unwrap_or_else(|e| {
+            info.extraction_errors.push(format!("Base profile: {}", e));
+            None
+        });
 
-        let mcp_servers = match extraction::extract_mcp_servers(harness, &extraction_path) {
-            Ok(servers) => servers,
-            Err(e) => {
-                extraction_errors.push(format!("MCP config: {}", e));
-                Vec::new()
+        match extraction::extract_mcp_servers(harness, &extraction_path, config.profile.mcp_order) {
+            Ok((servers, warnings)) => {
+                info.mcp_servers = servers;
+                info.extraction_warnings = warnings;
             }
-        };
+            Err(e) => info.extraction_errors.push(format!("MCP config: {}", e)),
+        }
+
+        extract_resources(&extraction_path, &mut info);
+        let (env_file_keys, err) = extraction::extract_env_file_keys(&extraction_path);
+        info.env_file_keys = env_file_keys;
+        info.extraction_errors.extend(err);
+
+        let links = ProfileLinks::load(&profile_path).unwrap_or_else(|e| {
+            info.extraction_errors.push(format!("Profile links: {}", e));
+            ProfileLinks::default()
+        });
+        let linked =
+            |kind| links.linked_items(&profile_path, &extraction_path, resource_harness, kind);
+        info.skills.mark_linked(linked("skills"));
+        info.commands.mark_linked(linked("commands"));
+        if let Some(agents) = info.agents.as_mut() {
+            agents.mark_linked(linked("agents"));
+        }
+
+        if let Some(staging) = staged {
+            let _ = std::fs::remove_dir_all(staging);
+        }
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extraction::{
+        DirectoryStructure, extract_resource_summary, extract_resource_summary_with_depth,
+        list_files_matching, list_subdirs_with_file,
+    };
+    use super::*;
+    use crate::config::test_env::setup_test_env;
+    use harness_locate::{HarnessKind, Scope};
+    use std::fs;
+    use tempfile::TempDir;
+
+    struct MockHarness {
+        id: String,
+        config_dir: PathBuf,
+        mcp_path: Option<PathBuf>,
+        scope: Scope,
+        active_key: Option<String>,
+    }
+
+    impl MockHarness {
+        fn new(id: &str, config_dir: PathBuf) -> Self {
+            Self {
+                id: id.to_string(),
+                config_dir,
+                mcp_path: None,
+                scope: Scope::Global,
+                active_key: None,
+            }
+        }
+
+        fn with_mcp(mut self, mcp_path: PathBuf) -> Self {
+            self.mcp_path = Some(mcp_path);
+            self
+        }
 
-        let (skills, err) = extraction::extract_skills(harness, &extraction_path);
-        if let Some(e) = err {
-            extraction_errors.push(e);
+        /// Resolves resource directories under `root` instead of the user's
+        /// home.
+        fn with_resources_in(mut self, root: PathBuf) -> Self {
+            self.scope = Scope::Custom(root);
+            self
         }
 
-        let (commands, err) = extraction::extract_commands(harness, &extraction_path);
-        if let Some(e) = err {
-            extraction_errors.push(e);
+        /// Records the active profile under `key`, as a project-scoped
+        /// harness does.
+        fn with_active_key(mut self, key: &str) -> Self {
+            self.active_key = Some(key.to_string());
+            self
         }
+    }
 
-        let (plugins, err) = extraction::extract_plugins(harness, &extraction_path);
-        if let Some(e) = err {
-            extraction_errors.push(e);
+    impl HarnessConfig for MockHarness {
+        fn id(&self) -> &str {
+            &self.id
         }
 
-        let (agents, err) = extraction::extract_agents(harness, &extraction_path);
-        if let Some(e) = err {
-            extraction_errors.push(e);
+        fn active_key(&self) -> String {
+            self.active_key.clone().unwrap_or_else(|| self.id.clone())
         }
 
-        let (rules_file, err) = extraction::extract_rules_file(harness, &extraction_path);
-        if let Some(e) = err {
-            extraction_errors.push(e);
+        fn scope(&self) -> Scope {
+            self.scope.clone()
         }
 
-        Ok(ProfileInfo {
-            name: name.as_str().to_string(),
-            harness_id,
-            is_active,
-            path: profile_path,
-            mcp_servers,
-            skills,
-            commands,
-            plugins,
-            agents,
-            rules_file,
-            theme,
-            model,
-            extraction_errors,
-        })
+        fn config_dir(&self) -> Result<PathBuf> {
+            Ok(self.config_dir.clone())
+        }
+
+        fn installation_status(&self) -> Result<InstallationStatus> {
+            Ok(InstallationStatus::FullyInstalled {
+                binary_path: PathBuf::from("/bin/mock"),
+                config_path: self.config_dir.clone(),
+            })
+        }
+
+        fn mcp_filename(&self) -> Option<String> {
+            None
+        }
+
+        fn mcp_config_path(&self) -> Option<PathBuf> {
+            self.mcp_path.clone()
+        }
+
+        fn parse_mcp_servers(
+            &self,
+            _content: &str,
+            _filename: &str,
+        ) -> Result<Vec<(String, bool)>> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn saving_the_live_config_refreshes_captured_with() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "{}").unwrap();
+        let harness = MockHarness::new("test-captured-with", live_config);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        let path = manager.create_profile(&harness, &name).unwrap();
+        let mut meta = ProfileMeta::load(&path).unwrap();
+        meta.captured_with = Some("0.1.0".to_string());
+        meta.save(&path).unwrap();
+
+        manager.save_to_profile(&harness, None, &name).unwrap();
+
+        // `/bin/mock` doesn't run, so the stale version is dropped.
+        assert_eq!(ProfileMeta::load(&path).unwrap().captured_with, None);
+    }
+
+    #[test]
+    fn switch_profile_preserves_edits() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let profiles_dir = temp.path().join("profiles");
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+
+        let harness = MockHarness::new("test-preserves-edits", live_config.clone());
+        let manager = ProfileManager::new(profiles_dir);
+
+        let profile_a = ProfileName::new("profile-a").unwrap();
+        let profile_b = ProfileName::new("profile-b").unwrap();
+
+        fs::write(live_config.join("initial.txt"), "initial").unwrap();
+        manager.create_from_current(&harness, &profile_a).unwrap();
+
+        fs::write(live_config.join("initial.txt"), "different").unwrap();
+        manager.create_from_current(&harness, &profile_b).unwrap();
+
+        manager.switch_profile(&harness, &profile_a).unwrap();
+        assert_eq!(
+            fs::read_to_string(live_config.join("initial.txt")).unwrap(),
+            "initial"
+        );
+
+        fs::write(live_config.join("edited.txt"), "user edit").unwrap();
+
+        manager.switch_profile(&harness, &profile_b).unwrap();
+        assert_eq!(
+            fs::read_to_string(live_config.join("initial.txt")).unwrap(),
+            "different"
+        );
+
+        manager.switch_profile(&harness, &profile_a).unwrap();
+
+        assert!(
+            live_config.join("edited.txt").exists(),
+            "Edit should be preserved"
+        );
+        assert_eq!(
+            fs::read_to_string(live_config.join("edited.txt")).unwrap(),
+            "user edit"
+        );
+    }
+
+    #[test]
+    fn switching_appends_to_the_history_of_both_profiles() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "{}").unwrap();
+
+        let harness = MockHarness::new("test-history", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let (home, work) = (
+            ProfileName::new("home").unwrap(),
+            ProfileName::new("work").unwrap(),
+        );
+        manager.create_from_current(&harness, &home).unwrap();
+        manager.create_profile(&harness, &work).unwrap();
+
+        manager.switch_profile(&harness, &work).unwrap();
+
+        let events = |name| -> Vec<HistoryEvent> {
+            let history = manager.profile_history(&harness, name).unwrap();
+            history.into_iter().map(|e| e.event).collect()
+        };
+        assert_eq!(events(&home), [HistoryEvent::Created, HistoryEvent::Synced]);
+        assert_eq!(
+            events(&work),
+            [HistoryEvent::Created, HistoryEvent::Switched]
+        );
+        assert!(
+            !live_config
+                .join(crate::config::PROFILE_HISTORY_FILE)
+                .exists()
+        );
+    }
+
+    #[test]
+    fn locked_profile_blocks_switch_rollback_delete_and_compress_without_force() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "home").unwrap();
+
+        let harness = MockHarness::new("test-locked", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let (home, work) = (
+            ProfileName::new("home").unwrap(),
+            ProfileName::new("work").unwrap(),
+        );
+        manager.create_from_current(&harness, &home).unwrap();
+        manager.create_profile(&harness, &work).unwrap();
+        let home_path = manager.profile_path(&harness, &home);
+        let mut meta = ProfileMeta::load(&home_path).unwrap();
+        meta.locked = true;
+        meta.save(&home_path).unwrap();
+
+        let err = manager.switch_profile(&harness, &work).unwrap_err();
+        assert!(
+            matches!(&err, Error::ProfileLocked(name) if name == "home"),
+            "{err}"
+        );
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "home"
+        );
+        assert!(matches!(
+            manager.delete_profile(&harness, &home),
+            Err(Error::ProfileLocked(_))
+        ));
+        assert!(matches!(
+            manager.compress_profile(&harness, &home),
+            Err(Error::ProfileLocked(_))
+        ));
+        assert!(home_path.is_dir());
+
+        manager.backup_current(&harness, None).unwrap();
+        fs::write(live_config.join("config.json"), "edited").unwrap();
+        assert!(matches!(
+            manager.rollback(&harness, None),
+            Err(Error::ProfileLocked(_))
+        ));
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "edited"
+        );
+
+        manager
+            .ignoring_locks()
+            .switch_profile(&harness, &work)
+            .unwrap();
+        let config = BridleConfig::load().unwrap();
+        assert_eq!(
+            config.active_profile_for(&harness.active_key()),
+            Some("work")
+        );
+        assert!(home_path.exists());
+    }
+
+    #[test]
+    fn live_config_diffs_against_a_backup() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "before").unwrap();
+        fs::write(live_config.join("rules.md"), "same").unwrap();
+
+        let harness = MockHarness::new("test-backup-diff", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        manager
+            .create_from_current(&harness, &ProfileName::new("a").unwrap())
+            .unwrap();
+        let backup = manager.backup_current(&harness, None).unwrap();
+        assert!(backup.join(".bridle_backup_active").exists());
+
+        fs::write(live_config.join("config.json"), "after").unwrap();
+        fs::write(live_config.join("added.md"), "new").unwrap();
+
+        let name = backup.file_name().unwrap().to_str().unwrap();
+        assert_eq!(manager.find_backup(&harness, name).unwrap(), backup);
+        assert_eq!(manager.find_backup(&harness, "latest").unwrap(), backup);
+        assert!(matches!(
+            manager.find_backup(&harness, "20000101_000000"),
+            Err(Error::InvalidValue(_))
+        ));
+        assert_eq!(
+            manager
+                .changed_files_since_backup(&harness, &backup, None, false)
+                .unwrap(),
+            ["added.md", "config.json"]
+        );
+    }
+
+    #[test]
+    fn backup_includes_resource_directories() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "{}").unwrap();
+        let resources = temp.path().join("resources");
+        let skill = resources.join("skills").join("review");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Review").unwrap();
+
+        let harness =
+            MockHarness::new("test-backup-resources", live_config).with_resources_in(resources);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        let backup = manager.backup_current(&harness, Some(&claude)).unwrap();
+
+        assert!(backup.join("settings.json").is_file());
+        assert_eq!(
+            fs::read_to_string(backup.join("skills/review/SKILL.md")).unwrap(),
+            "# Review"
+        );
+    }
+
+    #[test]
+    fn rollback_puts_skills_back_where_the_harness_keeps_them() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "{}").unwrap();
+        let skills = temp.path().join("resources/skills");
+        fs::create_dir_all(skills.join("review")).unwrap();
+        fs::write(skills.join("review/SKILL.md"), "# Review").unwrap();
+
+        let harness = MockHarness::new("test-rollback-skills", live_config.clone())
+            .with_resources_in(temp.path().join("resources"));
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        manager.backup_current(&harness, Some(&claude)).unwrap();
+        fs::remove_dir_all(skills.join("review")).unwrap();
+        fs::create_dir_all(skills.join("added")).unwrap();
+
+        manager.rollback(&harness, Some(&claude)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(skills.join("review/SKILL.md")).unwrap(),
+            "# Review"
+        );
+        assert!(!skills.join("added").exists());
+        assert!(live_config.join("settings.json").is_file());
+        assert!(!live_config.join("skills").exists());
+    }
+
+    #[test]
+    fn without_resources_recreate_keeps_stored_skills() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "old").unwrap();
+        let skills = temp.path().join("resources/skills");
+        fs::create_dir_all(skills.join("review")).unwrap();
+        fs::write(skills.join("review/SKILL.md"), "# Review").unwrap();
+
+        let harness = MockHarness::new("test-recreate-lean", live_config.clone())
+            .with_resources_in(temp.path().join("resources"));
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        let work = ProfileName::new("work").unwrap();
+        ProfileManager::new(temp.path().join("profiles"))
+            .create_from_current_with_resources(&harness, Some(&claude), &work)
+            .unwrap();
+        fs::remove_dir_all(&skills).unwrap();
+        fs::write(live_config.join("settings.json"), "new").unwrap();
+
+        let (path, _) = ProfileManager::new(temp.path().join("profiles"))
+            .without_resources()
+            .recreate_from_current(&harness, Some(&claude), &work)
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(path.join("settings.json")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("skills/review/SKILL.md")).unwrap(),
+            "# Review"
+        );
+    }
+
+    #[test]
+    fn without_resources_create_leaves_out_live_skills() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "{}").unwrap();
+        let resources = temp.path().join("resources");
+        let skill = resources.join("skills").join("review");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Review").unwrap();
+        fs::create_dir_all(live_config.join("skills/local")).unwrap();
+        fs::write(live_config.join("skills/local/SKILL.md"), "# Local").unwrap();
+
+        let harness =
+            MockHarness::new("test-no-resources", live_config).with_resources_in(resources);
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let full = manager
+            .create_from_current_with_resources(
+                &harness,
+                Some(&claude),
+                &ProfileName::new("full").unwrap(),
+            )
+            .unwrap();
+        let lean = ProfileManager::new(temp.path().join("profiles"))
+            .without_resources()
+            .create_from_current_with_resources(
+                &harness,
+                Some(&claude),
+                &ProfileName::new("lean").unwrap(),
+            )
+            .unwrap();
+
+        assert!(full.join("skills/review/SKILL.md").is_file());
+        assert!(full.join("skills/local/SKILL.md").is_file());
+        assert!(lean.join("settings.json").is_file());
+        assert!(!lean.join("skills").exists());
+        assert!(ProfileMeta::load(&lean).unwrap().capture_resources);
+    }
+
+    #[test]
+    fn reset_discards_live_edits_and_restores_stored_files() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        let harness = MockHarness::new("test-reset", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        assert!(matches!(
+            manager.reset_to_stored(&harness, None),
+            Err(Error::NoActiveProfile)
+        ));
+
+        fs::write(live_config.join("config.json"), "stored").unwrap();
+        let name = ProfileName::new("work").unwrap();
+        manager.create_from_current(&harness, &name).unwrap();
+        fs::write(live_config.join("config.json"), "broken").unwrap();
+        fs::write(live_config.join("scratch.txt"), "live only").unwrap();
+
+        let (reset, backup) = manager.reset_to_stored(&harness, None).unwrap();
+
+        assert_eq!(reset, name);
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "stored"
+        );
+        assert!(!live_config.join("scratch.txt").exists());
+        let backup = backup.unwrap();
+        assert_eq!(
+            fs::read_to_string(backup.join("config.json")).unwrap(),
+            "broken"
+        );
+        let stored = manager.profile_path(&harness, &name);
+        assert_eq!(
+            fs::read_to_string(stored.join("config.json")).unwrap(),
+            "stored"
+        );
+    }
+
+    #[test]
+    fn rollback_restores_pre_switch_live_config() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "a").unwrap();
+
+        let harness = MockHarness::new("test-rollback", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let profile_a = ProfileName::new("a").unwrap();
+        let profile_b = ProfileName::new("b").unwrap();
+        manager.create_from_current(&harness, &profile_a).unwrap();
+        let b_path = manager.create_profile(&harness, &profile_b).unwrap();
+        fs::write(b_path.join("config.json"), "b").unwrap();
+
+        assert!(matches!(
+            manager.rollback(&harness, None),
+            Err(Error::NoBackup(_))
+        ));
+
+        manager.backup_current(&harness, None).unwrap();
+        manager.switch_profile(&harness, &profile_b).unwrap();
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "b"
+        );
+
+        let (_, active) = manager.rollback(&harness, None).unwrap();
+        assert_eq!(active.as_deref(), Some("a"));
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "a"
+        );
+        assert!(!live_config.join(".bridle_backup_active").exists());
+        assert_eq!(
+            BridleConfig::load()
+                .unwrap()
+                .active_profile_for("test-rollback"),
+            Some("a")
+        );
+    }
+
+    #[test]
+    fn project_backups_are_kept_apart_from_global_ones() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let global_dir = temp.path().join("global");
+        let project_dir = temp.path().join("project");
+        fs::create_dir_all(&global_dir).unwrap();
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(global_dir.join("config.json"), "global").unwrap();
+        fs::write(project_dir.join("config.json"), "project").unwrap();
+
+        let global = MockHarness::new("test-scoped-backup", global_dir.clone());
+        let project = MockHarness::new("test-scoped-backup", project_dir.clone())
+            .with_active_key("test-scoped-backup@/work/app");
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let global_backup = manager.backup_current(&global, None).unwrap();
+        let project_backup = manager.backup_current(&project, None).unwrap();
+
+        assert_ne!(global_backup.parent(), project_backup.parent());
+        assert_eq!(manager.latest_backup(&global).unwrap(), Some(global_backup));
+        assert_eq!(
+            manager.latest_backup(&project).unwrap(),
+            Some(project_backup)
+        );
+
+        fs::write(global_dir.join("config.json"), "edited").unwrap();
+        manager.rollback(&global, None).unwrap();
+        assert_eq!(
+            fs::read_to_string(global_dir.join("config.json")).unwrap(),
+            "global"
+        );
+    }
+
+    #[test]
+    fn switch_overlays_two_level_base_chain() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        let harness = MockHarness::new("test-base", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let names = ["common", "team", "work"].map(|n| ProfileName::new(n).unwrap());
+        let [common, team, work] = names
+            .each_ref()
+            .map(|n| manager.create_profile(&harness, n).unwrap());
+        fs::write(common.join("settings.json"), "common").unwrap();
+        fs::write(common.join("rules.md"), "rules").unwrap();
+        fs::write(team.join("settings.json"), "team").unwrap();
+        fs::write(
+            team.join(crate::config::PROFILE_BASE_FILE),
+            "base = \"common\"",
+        )
+        .unwrap();
+        fs::write(work.join("model.json"), "work").unwrap();
+        fs::write(
+            work.join(crate::config::PROFILE_BASE_FILE),
+            "base = \"team\"",
+        )
+        .unwrap();
+
+        manager.switch_profile(&harness, &names[2]).unwrap();
+        let live = |f: &str| fs::read_to_string(live_config.join(f)).ok();
+        assert_eq!(live("settings.json").as_deref(), Some("team"));
+        assert_eq!(live("rules.md").as_deref(), Some("rules"));
+        assert_eq!(live("model.json").as_deref(), Some("work"));
+        assert!(live(crate::config::PROFILE_BASE_FILE).is_none());
+        assert!(!manager.backups_dir().join("test-base/layered").exists());
+
+        fs::write(live_config.join("model.json"), "edited").unwrap();
+        manager.switch_profile(&harness, &names[0]).unwrap();
+        let mut saved: Vec<String> = fs::read_dir(&work)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|n| !n.starts_with(".bridle"))
+            .collect();
+        saved.sort();
+        assert_eq!(saved, ["model.json"]);
+        assert_eq!(
+            fs::read_to_string(work.join("model.json")).unwrap(),
+            "edited"
+        );
+    }
+
+    #[test]
+    fn switch_report_records_outgoing_save() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "live").unwrap();
+        let harness = MockHarness::new("test-report", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let a = ProfileName::new("a").unwrap();
+        let b = ProfileName::new("b").unwrap();
+        manager.create_profile(&harness, &a).unwrap();
+        manager.create_profile(&harness, &b).unwrap();
+
+        let first = manager
+            .switch_profile_reporting(&harness, None, &a, &mut |_| {})
+            .unwrap();
+        assert!(first.switched);
+        assert_eq!(first.saved_outgoing, None);
+        assert!(
+            first
+                .backed_up
+                .is_some_and(|p| p.join("config.json").exists())
+        );
+
+        let second = manager
+            .switch_profile_reporting(&harness, None, &b, &mut |_| {})
+            .unwrap();
+        assert_eq!(second.saved_outgoing, Some(a.clone()));
+        assert_eq!(second.backed_up, None);
+        assert_eq!(second.target_dir, live_config);
+
+        let again = manager
+            .switch_profile_reporting(&harness, None, &b, &mut |_| {})
+            .unwrap();
+        assert!(!again.switched);
+    }
+
+    #[test]
+    fn glob_matches_inactive_profiles_only() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = MockHarness::new("test-glob", temp.path().join("live_config"));
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        for name in ["temp-a", "temp-b", "temp-live", "keep", "attempt"] {
+            manager
+                .create_profile(&harness, &ProfileName::new(name).unwrap())
+                .unwrap();
+        }
+        manager
+            .switch_profile(&harness, &ProfileName::new("temp-live").unwrap())
+            .unwrap();
+
+        let names: Vec<String> = manager
+            .inactive_profiles_matching(&harness, "temp-*")
+            .unwrap()
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        assert_eq!(names, ["temp-a", "temp-b"]);
+    }
+
+    #[test]
+    fn create_from_current_skips_live_marker_file() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "{}").unwrap();
+        ProfileManager::create_marker_file(&live_config, "old").unwrap();
+
+        let harness = MockHarness::new("test-marker", live_config);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        let profile_path = manager.create_from_current(&harness, &name).unwrap();
+
+        assert!(profile_path.join("config.json").exists());
+        let markers: Vec<_> = fs::read_dir(&profile_path)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(MARKER_PREFIX))
+            .collect();
+        assert!(markers.is_empty(), "{markers:?}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_only_profiles_dir_fails_create_clearly_and_still_lists() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let harness = MockHarness::new("test-ro", temp.path().join("live"));
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        manager
+            .create_profile(&harness, &ProfileName::new("home").unwrap())
+            .unwrap();
+        let profiles = manager.profiles_dir().clone();
+        fs::set_permissions(&profiles, fs::Permissions::from_mode(0o555)).unwrap();
+        // Permission bits don't bind root, so there is nothing to observe.
+        let root = fs::write(profiles.join("probe"), "").is_ok();
+
+        let created = manager.create_profile(&harness, &ProfileName::new("work").unwrap());
+        let listed = manager.list_profiles(&harness);
+        fs::set_permissions(&profiles, fs::Permissions::from_mode(0o755)).unwrap();
+        if root {
+            return;
+        }
+
+        let err = created.unwrap_err();
+        assert!(matches!(err, Error::Config(_)), "{err}");
+        assert!(
+            err.to_string()
+                .contains("profiles directory is not writable"),
+            "{err}"
+        );
+        assert_eq!(listed.unwrap(), [ProfileName::new("home").unwrap()]);
+    }
+
+    #[test]
+    fn create_profile_leaves_live_config_uncaptured() {
+        let temp = TempDir::new().unwrap();
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(live_config.join("skill")).unwrap();
+        fs::write(live_config.join("opencode.jsonc"), "{}").unwrap();
+
+        let harness = MockHarness::new("test-empty", live_config);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+
+        let name = ProfileName::new("scratch").unwrap();
+        let profile_path = manager.create_profile(&harness, &name).unwrap();
+
+        assert!(profile_path.is_dir());
+        let mut entries: Vec<_> = fs::read_dir(&profile_path)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            [
+                crate::config::PROFILE_HISTORY_FILE,
+                crate::config::PROFILE_META_FILE
+            ]
+        );
+        assert!(
+            ProfileMeta::load(&profile_path)
+                .unwrap()
+                .created_at
+                .is_some()
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::extraction::{
-        DirectoryStructure, extract_resource_summary, list_files_matching, list_subdirs_with_file,
-    };
-    use super::*;
-    use std::ffi::OsString;
-    use std::fs;
-    use std::sync::{Mutex, OnceLock};
-    use tempfile::TempDir;
+    #[test]
+    fn minimal_template_writes_opencode_config() {
+        let temp = TempDir::new().unwrap();
+        let harness = MockHarness::new("opencode", temp.path().join("live"));
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("starter").unwrap();
 
-    static TEST_ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+        let path = manager
+            .create_from_template(&harness, &name, ProfileTemplate::Minimal)
+            .unwrap();
 
-    struct TestEnvGuard {
-        _lock: std::sync::MutexGuard<'static, ()>,
-        prev: Option<OsString>,
+        assert_eq!(
+            fs::read_to_string(path.join("opencode.jsonc")).unwrap(),
+            "{\n  \"$schema\": \"https://opencode.ai/config.json\"\n}\n"
+        );
+        let droid = MockHarness::new("droid", temp.path().join("droid"));
+        assert!(
+            manager
+                .create_from_template(&droid, &name, ProfileTemplate::Minimal)
+                .is_err()
+        );
     }
 
-    impl Drop for TestEnvGuard {
-        fn drop(&mut self) {
-            if let Some(prev) = &self.prev {
-                unsafe { std::env::set_var("BRIDLE_CONFIG_DIR", prev) };
-            } else {
-                unsafe { std::env::remove_var("BRIDLE_CONFIG_DIR") };
-            }
-        }
-    }
+    #[test]
+    fn recreate_from_current_replaces_contents_and_keeps_backup() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("config.json"), "old").unwrap();
 
-    struct MockHarness {
-        id: String,
-        config_dir: PathBuf,
-        mcp_path: Option<PathBuf>,
-    }
+        let harness = MockHarness::new("test-recreate", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        manager.create_from_current(&harness, &name).unwrap();
 
-    impl MockHarness {
-        fn new(id: &str, config_dir: PathBuf) -> Self {
-            Self {
-                id: id.to_string(),
-                config_dir,
-                mcp_path: None,
-            }
-        }
+        fs::write(live_config.join("config.json"), "new").unwrap();
+        let (profile_path, backup) = manager
+            .recreate_from_current(&harness, None, &name)
+            .unwrap();
 
-        fn with_mcp(mut self, mcp_path: PathBuf) -> Self {
-            self.mcp_path = Some(mcp_path);
-            self
-        }
+        assert_eq!(
+            fs::read_to_string(profile_path.join("config.json")).unwrap(),
+            "new"
+        );
+        let backup = backup.expect("existing profile should be backed up");
+        assert!(backup.starts_with(manager.backups_dir()));
+        assert_eq!(
+            fs::read_to_string(backup.join("config.json")).unwrap(),
+            "old"
+        );
     }
 
-    impl HarnessConfig for MockHarness {
-        fn id(&self) -> &str {
-            &self.id
-        }
-
-        fn config_dir(&self) -> Result<PathBuf> {
-            Ok(self.config_dir.clone())
-        }
+    #[test]
+    fn save_skips_resources_when_capture_disabled() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(live_config.join("skills/my-skill")).unwrap();
+        fs::write(live_config.join("config.json"), "v1").unwrap();
+        fs::write(live_config.join("skills/my-skill/SKILL.md"), "# Skill").unwrap();
 
-        fn installation_status(&self) -> Result<InstallationStatus> {
-            Ok(InstallationStatus::FullyInstalled {
-                binary_path: PathBuf::from("/bin/mock"),
-                config_path: self.config_dir.clone(),
-            })
+        let harness = MockHarness::new("test-no-resources-save", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("config-only").unwrap();
+        let profile_path = manager.create_from_current(&harness, &name).unwrap();
+        ProfileMeta {
+            capture_resources: false,
+            ..Default::default()
         }
+        .save(&profile_path)
+        .unwrap();
 
-        fn mcp_filename(&self) -> Option<String> {
-            None
-        }
+        fs::write(live_config.join("config.json"), "v2").unwrap();
+        manager.save_to_profile(&harness, None, &name).unwrap();
 
-        fn mcp_config_path(&self) -> Option<PathBuf> {
-            self.mcp_path.clone()
-        }
+        assert_eq!(
+            fs::read_to_string(profile_path.join("config.json")).unwrap(),
+            "v2"
+        );
+        assert!(!profile_path.join("skills").exists());
+        assert!(profile_path.join(crate::config::PROFILE_META_FILE).exists());
+    }
 
-        fn parse_mcp_servers(
-            &self,
-            _content: &str,
-            _filename: &str,
-        ) -> Result<Vec<(String, bool)>> {
-            Ok(vec![])
+    #[test]
+    fn switch_leaves_live_resources_when_capture_disabled() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(live_config.join("skills/global-skill")).unwrap();
+        fs::write(live_config.join("skills/global-skill/SKILL.md"), "# Skill").unwrap();
+        fs::write(live_config.join("config.json"), "live").unwrap();
+
+        let harness = MockHarness::new("test-no-resources-switch", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("config-only").unwrap();
+        let profile_path = manager.create_profile(&harness, &name).unwrap();
+        fs::write(profile_path.join("config.json"), "profile").unwrap();
+        ProfileMeta {
+            capture_resources: false,
+            ..Default::default()
         }
-    }
+        .save(&profile_path)
+        .unwrap();
 
-    fn setup_test_env(temp: &TempDir) -> TestEnvGuard {
-        let lock = TEST_ENV_LOCK.get_or_init(|| Mutex::new(())).lock().unwrap();
+        manager.switch_profile(&harness, &name).unwrap();
 
-        let prev = std::env::var_os("BRIDLE_CONFIG_DIR");
-        let bridle_config_dir = temp.path().join("bridle_config");
-        fs::create_dir_all(&bridle_config_dir).unwrap();
-        unsafe { std::env::set_var("BRIDLE_CONFIG_DIR", &bridle_config_dir) };
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "profile"
+        );
+        assert!(live_config.join("skills/global-skill/SKILL.md").exists());
+        assert!(!live_config.join(crate::config::PROFILE_META_FILE).exists());
+    }
 
-        TestEnvGuard { _lock: lock, prev }
+    fn write_skills_link(profile_path: &std::path::Path, shared: &std::path::Path) {
+        fs::create_dir_all(shared.join("review")).unwrap();
+        fs::write(shared.join("review/SKILL.md"), "shared").unwrap();
+        fs::write(
+            profile_path.join(crate::config::PROFILE_LINKS_FILE),
+            format!("skills = {:?}\n", shared),
+        )
+        .unwrap();
     }
 
+    #[cfg(unix)]
     #[test]
-    fn switch_profile_preserves_edits() {
+    fn switch_links_shared_skills_without_capturing_them() {
         let temp = TempDir::new().unwrap();
         let _env = setup_test_env(&temp);
-        let profiles_dir = temp.path().join("profiles");
         let live_config = temp.path().join("live_config");
         fs::create_dir_all(&live_config).unwrap();
 
-        let harness = MockHarness::new("test-preserves-edits", live_config.clone());
-        let manager = ProfileManager::new(profiles_dir);
+        let harness = MockHarness::new("test-links", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let work = ProfileName::new("work").unwrap();
+        let other = ProfileName::new("other").unwrap();
+        let work_path = manager.create_profile(&harness, &work).unwrap();
+        manager.create_profile(&harness, &other).unwrap();
+        write_skills_link(&work_path, &temp.path().join("shared"));
+
+        manager.switch_profile(&harness, &work).unwrap();
+        assert!(live_config.join("skills/review").is_symlink());
+        assert!(!live_config.join(crate::config::PROFILE_LINKS_FILE).exists());
+
+        manager.switch_profile(&harness, &other).unwrap();
+        assert!(!work_path.join("skills/review").exists());
+        assert!(work_path.join(crate::config::PROFILE_LINKS_FILE).exists());
+    }
 
-        let profile_a = ProfileName::new("profile-a").unwrap();
-        let profile_b = ProfileName::new("profile-b").unwrap();
+    #[test]
+    fn show_profile_flags_linked_skills() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let harness = Harness::new(harness_locate::HarnessKind::ClaudeCode);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("linked").unwrap();
+        let profile_path = manager.create_profile(&harness, &name).unwrap();
+        fs::create_dir_all(profile_path.join("skills/own")).unwrap();
+        fs::write(profile_path.join("skills/own/SKILL.md"), "own").unwrap();
+        write_skills_link(&profile_path, &temp.path().join("shared"));
+
+        let info = manager.show_profile(&harness, &name).unwrap();
+        assert!(info.skills.items.contains(&"review".to_string()));
+        assert!(info.skills.is_linked("review"));
+        assert!(!info.skills.is_linked("own"));
+    }
 
-        fs::write(live_config.join("initial.txt"), "initial").unwrap();
-        manager.create_from_current(&harness, &profile_a).unwrap();
+    #[test]
+    fn custom_harness_profiles_list_and_show() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("acme");
+        let harness = CustomHarness::from_toml(&format!(
+            "id = \"acme\"\nconfig_dir = {:?}\nmcp_path = \"servers.yaml\"\nformat = \"yaml\"\nmcp_key = \"servers\"\nskills_dir = \"skills\"\n",
+            live_config
+        ))
+        .unwrap();
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        let profile_path = manager.create_profile(&harness, &name).unwrap();
+        fs::write(
+            profile_path.join("servers.yaml"),
+            "servers:\n  search:\n    command: s\n",
+        )
+        .unwrap();
+        fs::create_dir_all(profile_path.join("skills/review")).unwrap();
+        fs::write(profile_path.join("skills/review/SKILL.md"), "# Review").unwrap();
+
+        assert_eq!(manager.list_profiles(&harness).unwrap(), vec![name.clone()]);
+        let info = manager.show_custom_profile(&harness, &name).unwrap();
+        assert_eq!(info.harness_id, "acme");
+        assert_eq!(info.mcp_servers.len(), 1);
+        assert_eq!(info.mcp_servers[0].name, "search");
+        assert_eq!(info.skills.items, ["review"]);
+        assert!(info.agents.is_none());
+        assert!(
+            info.extraction_errors.is_empty(),
+            "{:?}",
+            info.extraction_errors
+        );
+    }
 
-        fs::write(live_config.join("initial.txt"), "different").unwrap();
-        manager.create_from_current(&harness, &profile_b).unwrap();
+    #[test]
+    fn stored_custom_profile_ignores_live_edits() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("acme");
+        fs::create_dir_all(&live_config).unwrap();
+        let harness = CustomHarness::from_toml(&format!(
+            "id = \"acme\"\nconfig_dir = {:?}\nmcp_path = \"mcp.json\"\n",
+            live_config
+        ))
+        .unwrap();
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        let profile_path = manager.create_profile(&harness, &name).unwrap();
+        let servers = r#"{"mcpServers": {"search": {"command": "s"}}}"#;
+        fs::write(profile_path.join("mcp.json"), servers).unwrap();
+        let mut config = BridleConfig::default();
+        config.set_active_profile("acme", "work");
+        config.save().unwrap();
+        fs::write(
+            live_config.join("mcp.json"),
+            r#"{"mcpServers": {"search": {"command": "s"}, "extra": {"command": "e"}}}"#,
+        )
+        .unwrap();
 
-        manager.switch_profile(&harness, &profile_a).unwrap();
+        let names = |info: ProfileInfo| -> Vec<String> {
+            info.mcp_servers.into_iter().map(|s| s.name).collect()
+        };
+        let live = manager.show_custom_profile(&harness, &name).unwrap();
+        assert_eq!(names(live).len(), 2);
+        let stored = manager.show_stored_profile(&harness, &name).unwrap();
+        assert!(stored.is_active);
+        assert_eq!(names(stored), ["search"]);
+    }
+
+    #[test]
+    fn profile_that_fails_to_load_keeps_a_placeholder() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let harness = Harness::new(harness_locate::HarnessKind::OpenCode);
+        for name in ["broken", "work"] {
+            manager
+                .create_profile(&harness, &ProfileName::new(name).unwrap())
+                .unwrap();
+        }
+        let show = |name: &ProfileName, _: &BridleConfig| match name.as_str() {
+            "broken" => Err(Error::Config("unreadable".to_string())),
+            _ => manager.show_profile(&harness, name),
+        };
+
+        let infos = manager.show_each(&harness, true, show).unwrap();
+        let names: Vec<&str> = infos.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["broken", "work"]);
         assert_eq!(
-            fs::read_to_string(live_config.join("initial.txt")).unwrap(),
-            "initial"
+            infos[0].extraction_errors,
+            ["Failed to load profile: config error: unreadable"]
         );
+        assert!(infos[1].extraction_errors.is_empty());
 
-        fs::write(live_config.join("edited.txt"), "user edit").unwrap();
+        assert!(manager.show_each(&harness, false, show).is_err());
+    }
 
-        manager.switch_profile(&harness, &profile_b).unwrap();
+    #[test]
+    fn cross_harness_copy_transfers_only_resource_dirs() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let opencode = Harness::new(harness_locate::HarnessKind::OpenCode);
+        let goose = Harness::new(harness_locate::HarnessKind::Goose);
+        let source = ProfileName::new("oc-work").unwrap();
+        let source_path = manager.create_profile(&opencode, &source).unwrap();
+        fs::write(source_path.join("opencode.jsonc"), "{}").unwrap();
+        fs::create_dir_all(source_path.join("skill/review")).unwrap();
+        fs::write(source_path.join("skill/review/SKILL.md"), "# Review").unwrap();
+        fs::create_dir_all(source_path.join("agent")).unwrap();
+        fs::write(source_path.join("agent/helper.md"), "helper").unwrap();
+
+        let target = ProfileName::new("goose-work").unwrap();
+        let target_path = manager
+            .copy_resources_to_new_profile(&opencode, &source, &goose, &target)
+            .unwrap();
+
+        assert!(target_path.join("skills/review/SKILL.md").exists());
+        assert!(target_path.join("agents/helper.md").exists());
+        assert!(!target_path.join("opencode.jsonc").exists());
+        assert!(!target_path.join("skill").exists());
+        assert!(matches!(
+            manager.copy_resources_to_new_profile(&opencode, &source, &goose, &target),
+            Err(Error::ProfileExists(_))
+        ));
+    }
+
+    #[test]
+    fn move_keeps_resource_dirs_and_drops_source_config() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let opencode = Harness::new(harness_locate::HarnessKind::OpenCode);
+        let claude = Harness::new(harness_locate::HarnessKind::ClaudeCode);
+        let name = ProfileName::new("work").unwrap();
+        let source_path = manager.create_profile(&opencode, &name).unwrap();
+        fs::write(source_path.join("opencode.jsonc"), "{}").unwrap();
+        fs::create_dir_all(source_path.join("skill/review")).unwrap();
+        fs::write(source_path.join("skill/review/SKILL.md"), "# Review").unwrap();
+        let mut meta = ProfileMeta::load(&source_path).unwrap();
+        meta.description = Some("Work setup".to_string());
+        meta.save(&source_path).unwrap();
+
+        let moved = manager.move_profile(&opencode, &name, &claude).unwrap();
+
+        assert_eq!(moved, manager.profile_path(&claude, &name));
+        assert!(moved.join("skills/review/SKILL.md").exists());
+        assert!(!moved.join("opencode.jsonc").exists());
         assert_eq!(
-            fs::read_to_string(live_config.join("initial.txt")).unwrap(),
-            "different"
+            ProfileMeta::load(&moved).unwrap().description.as_deref(),
+            Some("Work setup")
         );
+        assert!(!source_path.exists());
 
-        manager.switch_profile(&harness, &profile_a).unwrap();
+        manager.create_profile(&opencode, &name).unwrap();
+        assert!(matches!(
+            manager.move_profile(&opencode, &name, &claude),
+            Err(Error::ProfileExists(_))
+        ));
+        assert!(manager.profile_path(&opencode, &name).exists());
+    }
+
+    #[test]
+    fn moving_the_active_profile_syncs_it_and_clears_the_marker() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live = temp.path().join("live");
+        let harness = MockHarness::new("test-move-active", live.clone());
+        let claude = Harness::new(harness_locate::HarnessKind::ClaudeCode);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        fs::create_dir_all(live.join("skill/review")).unwrap();
+        fs::write(live.join("skill/review/SKILL.md"), "# Review").unwrap();
+        manager.create_from_current(&harness, &name).unwrap();
+        let mut config = BridleConfig::load().unwrap_or_default();
+        config.set_active_profile(&harness.active_key(), "work");
+        config.save().unwrap();
+        ProfileManager::create_marker_file(&live, "work").unwrap();
+        fs::write(live.join("skill/review/SKILL.md"), "# Review, edited").unwrap();
+
+        let moved = manager.move_profile(&harness, &name, &claude).unwrap();
 
-        assert!(
-            live_config.join("edited.txt").exists(),
-            "Edit should be preserved"
-        );
         assert_eq!(
-            fs::read_to_string(live_config.join("edited.txt")).unwrap(),
-            "user edit"
-        );
+            fs::read_to_string(moved.join("skills/review/SKILL.md")).unwrap(),
+            "# Review, edited"
+        );
+        let config = BridleConfig::load().unwrap();
+        assert_eq!(config.active_profile_for(&harness.active_key()), None);
+        let markers = fs::read_dir(&live)
+            .unwrap()
+            .filter(|e| {
+                let name = e.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with(MARKER_PREFIX)
+            })
+            .count();
+        assert_eq!(markers, 0);
+    }
+
+    #[test]
+    fn config_dir_that_is_a_file_is_rejected() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let not_a_dir = temp.path().join("live_config");
+        fs::write(&not_a_dir, "oops").unwrap();
+
+        let harness = MockHarness::new("test-file-config-dir", not_a_dir.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let name = ProfileName::new("work").unwrap();
+        manager.create_profile(&harness, &name).unwrap();
+
+        for err in [
+            manager.backup_current(&harness, None).unwrap_err(),
+            manager.switch_profile(&harness, &name).unwrap_err(),
+        ] {
+            assert!(
+                matches!(&err, Error::Config(msg) if msg.contains("config dir is not a directory")),
+                "{err}"
+            );
+        }
+        assert_eq!(fs::read_to_string(&not_a_dir).unwrap(), "oops");
     }
 
     #[test]
@@ -612,6 +2128,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_from_current_aborts_when_capture_too_large() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let profiles_dir = temp.path().join("profiles");
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(live_config.join("cache")).unwrap();
+        fs::write(live_config.join("config.json"), "{}").unwrap();
+        fs::write(live_config.join("cache/blob.bin"), vec![0u8; 4096]).unwrap();
+
+        let mut config = BridleConfig::load().unwrap();
+        config.profile.max_capture_bytes = Some(1024);
+        config.save().unwrap();
+
+        let harness = MockHarness::new("test-capture-limit", live_config.clone());
+        let manager = ProfileManager::new(profiles_dir);
+        let profile = ProfileName::new("huge").unwrap();
+
+        let err = manager.create_from_current(&harness, &profile).unwrap_err();
+        match err {
+            Error::CaptureTooLarge { path, limit } => {
+                assert_eq!(limit, 1024);
+                assert_eq!(path, live_config.join("cache/blob.bin"));
+            }
+            other => panic!("expected CaptureTooLarge, got {other:?}"),
+        }
+        assert!(!manager.profile_exists(&harness, &profile));
+
+        let excluding_cache = ProfileManager::new(temp.path().join("profiles"))
+            .with_capture_filter(CaptureFilter::new(vec!["cache".to_string()], Vec::new()));
+        let path = excluding_cache
+            .create_from_current(&harness, &profile)
+            .unwrap();
+        assert!(path.join("config.json").is_file());
+        assert!(!path.join("cache").exists());
+    }
+
+    #[test]
+    fn switch_aborts_with_live_intact_when_outgoing_save_fails() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+
+        let harness = MockHarness::new("test-save-fails", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let work = ProfileName::new("work").unwrap();
+        let other = ProfileName::new("other").unwrap();
+        manager.create_profile(&harness, &work).unwrap();
+        let other_path = manager.create_profile(&harness, &other).unwrap();
+        fs::write(other_path.join("config.json"), "other").unwrap();
+        manager.switch_profile(&harness, &work).unwrap();
+
+        fs::write(live_config.join("config.json"), "unsaved edit").unwrap();
+        fs::write(live_config.join("big.bin"), vec![0u8; 4096]).unwrap();
+        let mut config = BridleConfig::load().unwrap();
+        config.profile.max_capture_bytes = Some(1024);
+        config.save().unwrap();
+
+        let err = manager.switch_profile(&harness, &other).unwrap_err();
+        assert!(matches!(err, Error::CaptureTooLarge { .. }), "{err:?}");
+        assert_eq!(
+            fs::read_to_string(live_config.join("config.json")).unwrap(),
+            "unsaved edit"
+        );
+        assert!(live_config.join("big.bin").exists());
+        assert_eq!(
+            BridleConfig::load()
+                .unwrap()
+                .active_profile_for(harness.id()),
+            Some("work")
+        );
+    }
+
     #[test]
     fn switch_saves_new_directories_to_old_profile() {
         let temp = TempDir::new().unwrap();
@@ -786,6 +2376,31 @@ mod tests {
         assert_eq!(result, vec!["cmd1", "cmd2"]);
     }
 
+    #[test]
+    fn nested_skills_are_found_only_with_extra_depth() {
+        let temp = TempDir::new().unwrap();
+        for skill in ["review/code", "review/docs", "deploy/k8s"] {
+            let dir = temp.path().join("skills").join(skill);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), "# Skill").unwrap();
+        }
+        fs::create_dir_all(temp.path().join("skills/top")).unwrap();
+        fs::write(temp.path().join("skills/top/SKILL.md"), "# Top").unwrap();
+        let structure = DirectoryStructure::Nested {
+            subdir_pattern: "*".to_string(),
+            file_name: "SKILL.md".to_string(),
+        };
+
+        let shallow = extract_resource_summary(temp.path(), "skills", &structure);
+        assert_eq!(shallow.items, ["top"]);
+
+        let nested = extract_resource_summary_with_depth(temp.path(), "skills", &structure, 2);
+        assert_eq!(
+            nested.items,
+            ["deploy/k8s", "review/code", "review/docs", "top"]
+        );
+    }
+
     #[test]
     fn extract_resource_summary_handles_nonexistent_dir() {
         let temp = TempDir::new().unwrap();
@@ -1251,6 +2866,133 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compressed_profiles_are_listed_and_switchable() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        let harness = MockHarness::new("compressing", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let work = ProfileName::new("work").unwrap();
+        let old = ProfileName::new("old").unwrap();
+        fs::create_dir_all(live_config.join("skills/review")).unwrap();
+        fs::write(live_config.join("settings.json"), "{\"v\":1}").unwrap();
+        fs::write(live_config.join("skills/review/SKILL.md"), "# Review").unwrap();
+        manager.create_from_current(&harness, &old).unwrap();
+        manager.create_from_current(&harness, &work).unwrap();
+        manager.switch_profile(&harness, &work).unwrap();
+        fs::write(live_config.join("settings.json"), "{\"v\":2}").unwrap();
+        fs::remove_dir_all(live_config.join("skills")).unwrap();
+
+        assert!(manager.compress_profile(&harness, &work).is_err(), "active");
+        let archive = manager.compress_profile(&harness, &old).unwrap();
+        assert!(archive.ends_with("compressing/old.tar.gz"));
+        assert!(!manager.profile_path(&harness, &old).exists());
+        assert!(manager.profile_exists(&harness, &old));
+        assert_eq!(
+            manager.list_profiles(&harness).unwrap(),
+            vec![old.clone(), work.clone()]
+        );
+
+        manager.switch_profile(&harness, &old).unwrap();
+        assert_eq!(
+            fs::read_to_string(live_config.join("settings.json")).unwrap(),
+            "{\"v\":1}"
+        );
+        assert!(live_config.join("skills/review/SKILL.md").exists());
+        assert!(manager.profile_path(&harness, &old).is_dir());
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn recreating_a_compressed_profile_expands_it_first() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "old").unwrap();
+        let harness = MockHarness::new("compressed-recreate", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let old = ProfileName::new("old").unwrap();
+        manager.create_from_current(&harness, &old).unwrap();
+        let mut config = BridleConfig::load().unwrap();
+        config.clear_active_profile("compressed-recreate");
+        config.save().unwrap();
+        let archive = manager.compress_profile(&harness, &old).unwrap();
+
+        fs::write(live_config.join("settings.json"), "new").unwrap();
+        let (path, backup) = manager.recreate_from_current(&harness, None, &old).unwrap();
+
+        assert!(!archive.exists());
+        assert!(backup.unwrap().join("settings.json").is_file());
+        assert_eq!(
+            fs::read_to_string(path.join("settings.json")).unwrap(),
+            "new"
+        );
+    }
+
+    /// Plugin contents must survive capture -> switch -> capture unchanged,
+    /// including nested directories that share a name with session data.
+    #[test]
+    fn switch_round_trips_claude_plugin_contents() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let live_config = temp.path().join("live_config");
+        let harness = MockHarness::new("claude-plugins", live_config.clone());
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+
+        let plugin_files = [
+            (
+                "plugins/bridle/.claude-plugin/plugin.json",
+                "{\"name\":\"bridle\"}",
+            ),
+            ("plugins/bridle/agents/my-agent/index.md", "# Agent"),
+            ("plugins/bridle/commands/debug/run.md", "# Debug"),
+            ("plugins/bridle/commands/projects.md", "# Projects"),
+        ];
+        fs::create_dir_all(&live_config).unwrap();
+        fs::write(live_config.join("settings.json"), "{}").unwrap();
+        for (rel, contents) in plugin_files {
+            let path = live_config.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        let work = ProfileName::new("work").unwrap();
+        let other = ProfileName::new("other").unwrap();
+        manager.create_from_current(&harness, &work).unwrap();
+        fs::remove_dir_all(live_config.join("plugins")).unwrap();
+        manager.create_from_current(&harness, &other).unwrap();
+
+        manager.switch_profile(&harness, &work).unwrap();
+        fs::write(
+            live_config.join("plugins/bridle/agents/my-agent/index.md"),
+            "# Agent v2",
+        )
+        .unwrap();
+        manager.switch_profile(&harness, &other).unwrap();
+        assert!(!live_config.join("plugins").exists());
+        manager.switch_profile(&harness, &work).unwrap();
+
+        let profile = manager.profile_path(&harness, &work);
+        for (rel, contents) in plugin_files {
+            let expected = if rel.ends_with("index.md") {
+                "# Agent v2"
+            } else {
+                contents
+            };
+            for root in [&live_config, &profile] {
+                assert_eq!(
+                    fs::read_to_string(root.join(rel)).unwrap(),
+                    expected,
+                    "{} in {}",
+                    rel,
+                    root.display()
+                );
+            }
+        }
+    }
+
     /// Test profile isolation with Goose-style directory naming.
     /// Goose uses "skills" directory.
     #[test]
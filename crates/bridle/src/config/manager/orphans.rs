@@ -0,0 +1,108 @@
+//! Profiles left behind for harnesses bridle can no longer use
+//! (`profile prune-orphans`).
+
+use harness_locate::{Harness, InstallationStatus};
+
+use super::ProfileManager;
+use crate::config::ProfileName;
+use crate::error::Result;
+use crate::harness::{CustomHarness, HarnessConfig};
+use crate::install::types::parse_harness_kind;
+
+impl ProfileManager {
+    /// Harness ids under the profiles directory whose harness is gone, with
+    /// their profiles, sorted by id.
+    ///
+    /// A built-in harness is gone when it is no longer installed; any other id
+    /// is gone unless a custom harness descriptor still defines it.
+    ///
+    /// # Errors
+    /// Returns an error if the profiles directory or a custom harness
+    /// descriptor cannot be read. Descriptors are never skipped, so a broken
+    /// one cannot make its profiles look orphaned.
+    pub fn orphaned_profiles(&self) -> Result<Vec<(String, Vec<ProfileName>)>> {
+        let custom_ids: Vec<String> = CustomHarness::load_dir(&CustomHarness::descriptors_dir()?)?
+            .iter()
+            .map(|h| h.id().to_string())
+            .collect();
+        self.orphaned_profiles_where(|id| match parse_harness_kind(id) {
+            Some(kind) => matches!(
+                Harness::new(kind).installation_status(),
+                Ok(InstallationStatus::NotInstalled)
+            ),
+            None => !custom_ids.iter().any(|c| c == id),
+        })
+    }
+
+    fn orphaned_profiles_where(
+        &self,
+        is_gone: impl Fn(&str) -> bool,
+    ) -> Result<Vec<(String, Vec<ProfileName>)>> {
+        if !self.profiles_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut orphans = Vec::new();
+        for entry in std::fs::read_dir(&self.profiles_dir)? {
+            let entry = entry?;
+            let Some(id) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if id.starts_with('.') || !entry.file_type()?.is_dir() || !is_gone(&id) {
+                continue;
+            }
+            let mut profiles: Vec<ProfileName> = std::fs::read_dir(entry.path())?
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| ProfileName::new(&e.file_name().to_string_lossy()).ok())
+                .collect();
+            profiles.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+            orphans.push((id, profiles));
+        }
+        orphans.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(orphans)
+    }
+
+    /// Deletes every stored profile for `harness_id`.
+    pub fn delete_harness_profiles(&self, harness_id: &str) -> Result<()> {
+        let dir = self.profiles_dir.join(harness_id);
+        if dir.is_dir() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_env::setup_test_env;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn unknown_harness_ids_are_orphaned_but_custom_ones_are_not() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let profiles = temp.path().join("profiles");
+        for dir in ["retired/work", "retired/home", "acme/main", "opencode/dev"] {
+            fs::create_dir_all(profiles.join(dir)).unwrap();
+        }
+        let descriptors = CustomHarness::descriptors_dir().unwrap();
+        fs::create_dir_all(&descriptors).unwrap();
+        fs::write(
+            descriptors.join("acme.toml"),
+            "id = \"acme\"\nconfig_dir = \"/opt/acme\"\n",
+        )
+        .unwrap();
+        let manager = ProfileManager::new(profiles);
+
+        let orphans = manager.orphaned_profiles().unwrap();
+        let retired = orphans.iter().find(|(id, _)| id == "retired").unwrap();
+        let names: Vec<&str> = retired.1.iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["home", "work"]);
+        assert!(!orphans.iter().any(|(id, _)| id == "acme"));
+
+        manager.delete_harness_profiles("retired").unwrap();
+        assert!(!manager.profiles_dir().join("retired").exists());
+    }
+}
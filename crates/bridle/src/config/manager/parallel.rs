@@ -0,0 +1,91 @@
+//! Loading many profiles at once on a small pool of threads.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::diagnostics;
+
+/// `items.iter().map(f).collect()`, spread over up to `max_workers` threads.
+/// Results are in the order of `items`, however the work was scheduled.
+///
+/// Workers report diagnostics to the caller's scoped logger, if it has one.
+pub(super) fn map_in_order<T, R>(
+    items: &[T],
+    max_workers: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = max_workers.min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let done = Mutex::new(Vec::with_capacity(items.len()));
+    let logger = diagnostics::scoped_logger();
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                let work = || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = f(item);
+                    done.lock().unwrap().push((index, result));
+                };
+                match logger.clone() {
+                    Some(logger) => diagnostics::with_logger(logger, work),
+                    None => work(),
+                }
+            });
+        }
+    });
+
+    let mut done = done.into_inner().unwrap();
+    done.sort_by_key(|(index, _)| *index);
+    done.into_iter().map(|(_, result)| result).collect()
+}
+
+/// One worker per CPU, so reading dozens of profiles doesn't turn into
+/// dozens of threads fighting over the disk.
+pub(super) fn worker_cap() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_results_match_sequential_order() {
+        let items: Vec<u64> = (0..200).collect();
+        // Uneven work so workers finish out of order.
+        let slow_square = |n: &u64| {
+            std::thread::sleep(std::time::Duration::from_micros((n * 7919) % 300));
+            n * n
+        };
+        let sequential: Vec<u64> = items.iter().map(slow_square).collect();
+
+        for workers in [1, 4, 64] {
+            assert_eq!(map_in_order(&items, workers, slow_square), sequential);
+        }
+        assert!(map_in_order(&[] as &[u64], 4, slow_square).is_empty());
+    }
+
+    #[test]
+    fn workers_log_to_the_callers_scoped_logger() {
+        let seen = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = std::sync::Arc::clone(&seen);
+        diagnostics::with_logger(
+            std::sync::Arc::new(move |m| sink.lock().unwrap().push(m.to_string())),
+            || map_in_order(&[1, 2, 3], 3, |n| diagnostics::warn(n.to_string())),
+        );
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, ["1", "2", "3"]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Retrying destructive filesystem operations that fail while another process
+//! briefly holds a file open, as antivirus scanners and indexers do on Windows.
+
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Wait before the first retry; doubled for each one after.
+const BASE_DELAY: Duration = Duration::from_millis(50);
+
+pub(super) fn remove_dir_all(path: &Path, retries: u32) -> io::Result<()> {
+    with_backoff(on_this_platform(retries), BASE_DELAY, || {
+        std::fs::remove_dir_all(path)
+    })
+}
+
+pub(super) fn remove_file(path: &Path, retries: u32) -> io::Result<()> {
+    with_backoff(on_this_platform(retries), BASE_DELAY, || {
+        std::fs::remove_file(path)
+    })
+}
+
+pub(super) fn rename(from: &Path, to: &Path, retries: u32) -> io::Result<()> {
+    with_backoff(on_this_platform(retries), BASE_DELAY, || {
+        std::fs::rename(from, to)
+    })
+}
+
+/// `retries` on Windows and none elsewhere: a permission error on Unix is
+/// never a scanner holding the file and won't go away by waiting.
+fn on_this_platform(retries: u32) -> u32 {
+    if cfg!(windows) { retries } else { 0 }
+}
+
+/// Runs `op`, retrying up to `retries` times with exponential backoff while it
+/// fails with an error that may clear on its own.
+fn with_backoff<T>(
+    retries: u32,
+    base_delay: Duration,
+    mut op: impl FnMut() -> io::Result<T>,
+) -> io::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match op() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                sleep(base_delay * 2u32.saturating_pow(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient(err: &io::Error) -> bool {
+    /// `ERROR_SHARING_VIOLATION`: another process has the file open.
+    #[cfg(windows)]
+    const SHARING_VIOLATION: i32 = 32;
+    #[cfg(windows)]
+    if err.raw_os_error() == Some(SHARING_VIOLATION) {
+        return true;
+    }
+    matches!(
+        err.kind(),
+        ErrorKind::PermissionDenied | ErrorKind::ResourceBusy
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn failing_times(
+        failures: u32,
+        kind: ErrorKind,
+        calls: &Cell<u32>,
+    ) -> impl FnMut() -> io::Result<u32> + '_ {
+        move || {
+            calls.set(calls.get() + 1);
+            if calls.get() <= failures {
+                Err(io::Error::from(kind))
+            } else {
+                Ok(calls.get())
+            }
+        }
+    }
+
+    #[test]
+    fn transient_errors_are_retried_until_success() {
+        let calls = Cell::new(0);
+        let op = failing_times(2, ErrorKind::PermissionDenied, &calls);
+        assert_eq!(with_backoff(3, Duration::ZERO, op).unwrap(), 3);
+    }
+
+    #[test]
+    fn only_windows_retries() {
+        assert_eq!(on_this_platform(3), if cfg!(windows) { 3 } else { 0 });
+    }
+
+    #[test]
+    fn retries_are_bounded_and_skip_other_errors() {
+        let calls = Cell::new(0);
+        let op = failing_times(5, ErrorKind::ResourceBusy, &calls);
+        assert!(with_backoff(3, Duration::ZERO, op).is_err());
+        assert_eq!(calls.get(), 4);
+
+        let calls = Cell::new(0);
+        let op = failing_times(1, ErrorKind::NotFound, &calls);
+        assert_eq!(
+            with_backoff(3, Duration::ZERO, op).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(calls.get(), 1);
+    }
+}
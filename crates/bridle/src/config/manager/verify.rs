@@ -0,0 +1,386 @@
+//! Integrity checks for a stored profile (`profile verify`).
+
+use std::path::Path;
+
+use harness_locate::{Harness, Scope};
+use serde::Serialize;
+
+use super::ProfileManager;
+use super::extraction;
+use crate::config::edit_backup::{is_structured, validate_file};
+use crate::config::jsonc::strip_jsonc_comments;
+use crate::config::{BridleConfig, McpOrder, ProfileName, SchemaViolation, schema_for};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+use crate::install::manifest::{InstallManifest, manifest_path};
+use crate::install::types::ComponentType;
+
+/// How serious a [`VerifyIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The harness will likely fail to load this part of the profile.
+    Error,
+    /// Suspicious but usable.
+    Warning,
+}
+
+/// One problem found by [`ProfileManager::verify_profile`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerifyIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Outcome of verifying a profile.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    pub name: String,
+    pub harness_id: String,
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    /// Whether any issue has [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        self.issues.push(VerifyIssue {
+            severity,
+            message: message.into(),
+        });
+    }
+}
+
+/// Outcome of checking a profile's main config file against the schema
+/// bundled for its harness.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigValidation {
+    pub name: String,
+    pub harness_id: String,
+    /// The config file checked, or `None` if the profile has none.
+    pub file: Option<String>,
+    pub schema_version: u32,
+    pub violations: Vec<SchemaViolation>,
+}
+
+/// Main config file each built-in harness reads, when it has one.
+fn main_config_files(harness_id: &str) -> &'static [&'static str] {
+    match harness_id {
+        "opencode" => &["opencode.jsonc", "opencode.json"],
+        "claude-code" | "amp-code" => &["settings.json"],
+        "goose" => &["config.yaml"],
+        "copilot-cli" => &["config.json"],
+        "crush" => &["crush.json"],
+        _ => &[],
+    }
+}
+
+impl ProfileManager {
+    /// Checks a stored profile's config files, MCP servers, skills, and
+    /// install manifest.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if profile doesn't exist, or an IO
+    /// error if the profile directory cannot be read.
+    pub fn verify_profile(&self, harness: &Harness, name: &ProfileName) -> Result<VerifyReport> {
+        let profile_path = self.profile_path(harness, name);
+        if !profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        let mut report = VerifyReport {
+            name: name.as_str().to_string(),
+            harness_id: harness.id().to_string(),
+            issues: Vec::new(),
+        };
+
+        let expected = main_config_files(harness.id());
+        if !expected.is_empty() && !expected.iter().any(|f| profile_path.join(f).is_file()) {
+            report.push(
+                Severity::Warning,
+                format!("no {} in profile", expected.join(" or ")),
+            );
+        }
+        check_structured_files(&profile_path, &mut report)?;
+
+        match extraction::extract_mcp_servers(harness, &profile_path, McpOrder::default()) {
+            Ok((_, warnings)) => {
+                for warning in warnings {
+                    report.push(Severity::Warning, warning);
+                }
+            }
+            Err(e) => report.push(Severity::Error, format!("MCP config: {}", e)),
+        }
+
+        let skill_depth = BridleConfig::load()
+            .map(|c| c.profile.skill_depth)
+            .unwrap_or(1);
+        check_skills(harness, &profile_path, skill_depth, &mut report);
+        check_manifest(&profile_path, &mut report);
+        Ok(report)
+    }
+}
+
+impl ProfileManager {
+    /// Validates the main config file of a stored profile against the schema
+    /// bundled for its harness. A file that doesn't parse is reported as a
+    /// single violation at the document root.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileNotFound`] if the profile doesn't exist, and
+    /// [`Error::InvalidValue`] if no schema is bundled for the harness.
+    pub fn validate_profile_config(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<ConfigValidation> {
+        let schema = schema_for(harness.id()).ok_or_else(|| {
+            Error::InvalidValue(format!("no config schema is bundled for {}", harness.id()))
+        })?;
+        let profile_path = self.profile_path(harness, name);
+        if !profile_path.is_dir() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+        let file = main_config_files(harness.id())
+            .iter()
+            .find(|f| profile_path.join(f).is_file());
+        let mut validation = ConfigValidation {
+            name: name.as_str().to_string(),
+            harness_id: harness.id().to_string(),
+            file: file.map(|f| f.to_string()),
+            schema_version: schema.version,
+            violations: Vec::new(),
+        };
+        let Some(file) = file else {
+            return Ok(validation);
+        };
+
+        let contents = std::fs::read_to_string(profile_path.join(file))?;
+        match serde_json::from_str(&strip_jsonc_comments(&contents)) {
+            Ok(config) => validation.violations = schema.validate(&config),
+            Err(e) => validation.violations.push(SchemaViolation {
+                path: String::new(),
+                message: format!("does not parse: {}", e),
+            }),
+        }
+        Ok(validation)
+    }
+}
+
+fn check_structured_files(profile_path: &Path, report: &mut VerifyReport) -> Result<()> {
+    let mut files: Vec<_> = std::fs::read_dir(profile_path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_structured(p))
+        .collect();
+    files.sort();
+    for file in files {
+        if let Err(e) = validate_file(&file) {
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            report.push(Severity::Error, format!("{} does not parse: {}", name, e));
+        }
+    }
+    Ok(())
+}
+
+/// Flags skill directories without a SKILL.md. With a `skill_depth` above 1 a
+/// directory without one is a category and is searched like extraction does;
+/// only leaves, and categories holding no skills, are reported.
+fn check_skills(harness: &Harness, profile_path: &Path, depth: usize, report: &mut VerifyReport) {
+    let subdir = match harness.skills(&Scope::Global) {
+        Ok(Some(dir)) => extraction::dir_name_from_path(&dir.path).to_string(),
+        _ => return,
+    };
+    let mut missing = Vec::new();
+    find_skills_without_file(&profile_path.join(&subdir), "", depth, &mut missing);
+    missing.sort();
+    for skill in missing {
+        report.push(
+            Severity::Error,
+            format!("skill '{}' has no SKILL.md ({}/{})", skill, subdir, skill),
+        );
+    }
+}
+
+/// Collects the `prefix`-qualified names of directories under `dir` that
+/// hold no skill within `depth` levels. Returns whether any skill was found.
+fn find_skills_without_file(
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    missing: &mut Vec<String>,
+) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    let mut found = false;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let nested = !path.join("SKILL.md").is_file()
+            && depth > 1
+            && find_skills_without_file(&path, &format!("{}/", name), depth - 1, missing);
+        if path.join("SKILL.md").is_file() || nested {
+            found = true;
+        } else if depth <= 1 || !has_subdirs(&path) {
+            missing.push(name);
+        }
+    }
+    found
+}
+
+fn has_subdirs(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).any(|e| e.path().is_dir()))
+        .unwrap_or(false)
+}
+
+fn check_manifest(profile_path: &Path, report: &mut VerifyReport) {
+    let manifest = match InstallManifest::load(&manifest_path(profile_path)) {
+        Ok(manifest) => manifest,
+        Err(e) => return report.push(Severity::Error, e.to_string()),
+    };
+    for entry in &manifest.entries {
+        let dir = profile_path.join(entry.component_type.dir_name());
+        let installed = match entry.component_type {
+            ComponentType::Skill => dir.join(&entry.name).join("SKILL.md"),
+            ComponentType::Agent | ComponentType::Command => dir.join(format!("{}.md", entry.name)),
+        };
+        if !installed.is_file() {
+            report.push(
+                Severity::Warning,
+                format!(
+                    "manifest lists '{}' but {} is missing",
+                    entry.name,
+                    installed.display()
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_env::setup_test_env;
+    use harness_locate::HarnessKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn claude_profile(
+        temp: &TempDir,
+    ) -> (ProfileManager, Harness, ProfileName, std::path::PathBuf) {
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let name = ProfileName::new("work").unwrap();
+        let path = manager.create_profile(&harness, &name).unwrap();
+        fs::write(path.join("settings.json"), "{}").unwrap();
+        (manager, harness, name, path)
+    }
+
+    #[test]
+    fn skill_without_skill_md_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let (manager, harness, name, path) = claude_profile(&temp);
+        fs::create_dir_all(path.join("skills/good")).unwrap();
+        fs::write(path.join("skills/good/SKILL.md"), "# Good").unwrap();
+        fs::create_dir_all(path.join("skills/broken")).unwrap();
+
+        let report = manager.verify_profile(&harness, &name).unwrap();
+        assert!(report.has_errors());
+        assert_eq!(report.issues.len(), 1, "{:?}", report.issues);
+        assert!(report.issues[0].message.contains("skill 'broken'"));
+    }
+
+    #[test]
+    fn nested_skills_are_checked_up_to_the_configured_depth() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let mut config = BridleConfig::default();
+        config.profile.skill_depth = 2;
+        config.save().unwrap();
+        let (manager, harness, name, path) = claude_profile(&temp);
+        fs::create_dir_all(path.join("skills/writing/good")).unwrap();
+        fs::write(path.join("skills/writing/good/SKILL.md"), "# Good").unwrap();
+        fs::create_dir_all(path.join("skills/writing/broken/notes")).unwrap();
+        fs::create_dir_all(path.join("skills/empty")).unwrap();
+
+        let report = manager.verify_profile(&harness, &name).unwrap();
+        let messages: Vec<_> = report.issues.iter().map(|i| &i.message).collect();
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages[0].contains("skill 'empty'"));
+        assert!(messages[1].contains("skill 'writing/broken'"));
+    }
+
+    #[test]
+    fn config_breaking_the_bundled_schema_is_reported_with_its_path() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let manager = ProfileManager::new(temp.path().join("profiles"));
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let name = ProfileName::new("work").unwrap();
+        let path = manager.create_profile(&harness, &name).unwrap();
+        fs::write(
+            path.join("opencode.jsonc"),
+            "{\n  // numeric by mistake\n  \"theme\": 7,\n  \"model\": \"x\"\n}",
+        )
+        .unwrap();
+
+        let validation = manager.validate_profile_config(&harness, &name).unwrap();
+
+        assert_eq!(validation.file.as_deref(), Some("opencode.jsonc"));
+        assert_eq!(
+            validation.violations,
+            [SchemaViolation {
+                path: "/theme".to_string(),
+                message: "expected string, found number".to_string(),
+            }]
+        );
+        assert!(matches!(
+            manager.validate_profile_config(&Harness::new(HarnessKind::Goose), &name),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_mcp_file_is_reported() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let (manager, harness, name, path) = claude_profile(&temp);
+        fs::write(path.join(".mcp.json"), "{ \"mcpServers\": ").unwrap();
+
+        let report = manager.verify_profile(&harness, &name).unwrap();
+        let errors: Vec<&str> = report
+            .issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| i.message.as_str())
+            .collect();
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.starts_with(".mcp.json does not parse")),
+            "{errors:?}"
+        );
+        assert!(
+            errors.iter().any(|m| m.starts_with("MCP config:")),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn clean_profile_passes() {
+        let temp = TempDir::new().unwrap();
+        let _env = setup_test_env(&temp);
+        let (manager, harness, name, _) = claude_profile(&temp);
+
+        let report = manager.verify_profile(&harness, &name).unwrap();
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+}
@@ -4,12 +4,37 @@
 #![allow(unused_imports)]
 
 mod bridle;
+pub mod edit_backup;
 pub mod jsonc;
 mod manager;
+pub(crate) mod profile_base;
+pub mod profile_history;
+mod profile_links;
+mod profile_meta;
 mod profile_name;
+mod schema;
+mod templates;
+#[cfg(test)]
+pub(crate) mod test_env;
+mod text;
 mod types;
 
-pub use bridle::{BridleConfig, TuiConfig, ViewPreference};
-pub use manager::ProfileManager;
+pub use bridle::{BridleConfig, McpOrder, ProfileConfig, ThemeConfig, TuiConfig, ViewPreference};
+pub use edit_backup::EditSnapshot;
+pub use manager::{
+    BackupEntry, CaptureFilter, ConfigValidation, ProfileManager, RecoveredSwitch, RecoveryAction,
+    Severity, SwitchReport, VerifyIssue, VerifyReport,
+};
+pub use profile_base::{PROFILE_BASE_FILE, ProfileBase};
+pub use profile_history::{HistoryEntry, HistoryEvent, PROFILE_HISTORY_FILE};
+pub use profile_links::{PROFILE_LINKS_FILE, ProfileLinks};
+pub use profile_meta::{PROFILE_META_FILE, ProfileMeta};
+pub(crate) use profile_name::is_windows_reserved_name;
 pub use profile_name::{InvalidProfileName, ProfileName};
-pub use types::{McpServerInfo, ProfileInfo, ResourceSummary};
+pub use schema::{ConfigSchema, SchemaViolation, schema_for};
+pub use templates::{ProfileTemplate, TemplateFiles, template_files};
+pub use text::{is_probably_binary, is_probably_binary_file};
+pub use types::{
+    BaseLayering, EffectiveModel, FieldValue, McpServerInfo, ModelSource, ProfileInfo,
+    ResourceSummary,
+};
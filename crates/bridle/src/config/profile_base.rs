@@ -0,0 +1,121 @@
+//! Base profiles a profile inherits from (`.bridle_base.toml`).
+//!
+//! ```toml
+//! base = "common"   # another profile of the same harness
+//! ```
+//!
+//! On switch the base chain is applied first, farthest base first, and each
+//! profile's own files are laid over it, so the child wins on conflicts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProfileName;
+use crate::error::{Error, Result};
+
+/// File name of the base file inside a profile directory.
+///
+/// Never copied into the live harness config.
+pub const PROFILE_BASE_FILE: &str = ".bridle_base.toml";
+
+/// Contents of a profile's `.bridle_base.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileBase {
+    /// Name of the profile this one inherits from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+}
+
+impl ProfileBase {
+    /// Reads the base for the profile at `profile_path`, or none if absent.
+    pub fn load(profile_path: &Path) -> Result<Self> {
+        let path = profile_path.join(PROFILE_BASE_FILE);
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            Ok(toml::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+/// The profiles `profile_path` inherits from, nearest base first, as
+/// `(name, path)` pairs. Bases are siblings of the profile directory.
+///
+/// # Errors
+/// Returns [`Error::ProfileNotFound`] if a base doesn't exist, and
+/// [`Error::Config`] if a base name is invalid or the chain loops.
+pub fn base_chain(profile_path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let own_name = profile_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let mut seen = vec![own_name];
+    let mut chain = Vec::new();
+    let mut current = profile_path.to_path_buf();
+    while let Some(base) = ProfileBase::load(&current)?.base {
+        let name = ProfileName::new(&base)
+            .map_err(|e| Error::Config(format!("invalid base profile '{}': {}", base, e)))?;
+        if seen.iter().any(|s| s == name.as_str()) {
+            seen.push(name.to_string());
+            return Err(Error::Config(format!(
+                "base profile cycle: {}",
+                seen.join(" -> ")
+            )));
+        }
+        let path = profile_path.with_file_name(name.as_str());
+        if !path.is_dir() {
+            return Err(Error::ProfileNotFound(name.to_string()));
+        }
+        seen.push(name.to_string());
+        chain.push((name.to_string(), path.clone()));
+        current = path;
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(root: &Path, name: &str, base: Option<&str>) -> PathBuf {
+        let path = root.join(name);
+        fs::create_dir_all(&path).unwrap();
+        if let Some(base) = base {
+            fs::write(path.join(PROFILE_BASE_FILE), format!("base = \"{base}\"\n")).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn chain_lists_nearest_base_first() {
+        let temp = TempDir::new().unwrap();
+        profile(temp.path(), "common", None);
+        profile(temp.path(), "team", Some("common"));
+        let work = profile(temp.path(), "work", Some("team"));
+
+        let names: Vec<String> = base_chain(&work)
+            .unwrap()
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(names, ["team", "common"]);
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let temp = TempDir::new().unwrap();
+        let a = profile(temp.path(), "a", Some("b"));
+        profile(temp.path(), "b", Some("a"));
+
+        let err = base_chain(&a).unwrap_err();
+        assert!(
+            err.to_string().contains("base profile cycle: a -> b -> a"),
+            "{err}"
+        );
+    }
+}
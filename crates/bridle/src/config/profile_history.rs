@@ -0,0 +1,123 @@
+//! Append-only history of what happened to a profile
+//! (`.bridle_history.jsonl`), shown by `profile log`.
+//!
+//! One JSON object per line. Once the file passes [`MAX_HISTORY_LINES`] it
+//! is cut back to the newest entries, so it never grows without bound.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// File name of the history file inside a profile directory.
+///
+/// Never copied into the live harness config.
+pub const PROFILE_HISTORY_FILE: &str = ".bridle_history.jsonl";
+
+/// Entries kept once the history is trimmed.
+pub const MAX_HISTORY_LINES: usize = 500;
+
+/// Something that happened to a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEvent {
+    Created,
+    /// Made the harness's active profile.
+    Switched,
+    /// Opened in the editor with `profile edit`.
+    Edited,
+    /// Refreshed from the live config, e.g. when switching away from it.
+    Synced,
+}
+
+impl HistoryEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Switched => "switched",
+            Self::Edited => "edited",
+            Self::Synced => "synced",
+        }
+    }
+}
+
+/// One line of a profile's history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// RFC 3339 timestamp.
+    pub at: String,
+    pub event: HistoryEvent,
+}
+
+/// Appends `event` to the history of the profile at `profile_path`,
+/// trimming it to the newest [`MAX_HISTORY_LINES`] entries.
+pub fn record(profile_path: &Path, event: HistoryEvent) -> Result<()> {
+    let entry = HistoryEntry {
+        at: chrono::Local::now().to_rfc3339(),
+        event,
+    };
+    let path = profile_path.join(PROFILE_HISTORY_FILE);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    drop(file);
+
+    let content = fs::read_to_string(&path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() > MAX_HISTORY_LINES {
+        let kept = &lines[lines.len() - MAX_HISTORY_LINES..];
+        fs::write(&path, format!("{}\n", kept.join("\n")))?;
+    }
+    Ok(())
+}
+
+/// Like [`record`], but a failure only warns: the history must never stop
+/// the operation it describes.
+pub fn note(profile_path: &Path, event: HistoryEvent) {
+    if let Err(e) = record(profile_path, event) {
+        crate::diagnostics::warn(format!(
+            "Warning: Could not record '{}' in {}: {}",
+            event.as_str(),
+            profile_path.join(PROFILE_HISTORY_FILE).display(),
+            e
+        ));
+    }
+}
+
+/// The history of the profile at `profile_path`, oldest first. Lines that
+/// don't parse are skipped.
+pub fn load(profile_path: &Path) -> Result<Vec<HistoryEntry>> {
+    let path = profile_path.join(PROFILE_HISTORY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_to_string(&path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn history_is_trimmed_to_the_newest_entries() {
+        let temp = TempDir::new().unwrap();
+        record(temp.path(), HistoryEvent::Created).unwrap();
+        for _ in 0..MAX_HISTORY_LINES {
+            record(temp.path(), HistoryEvent::Switched).unwrap();
+        }
+
+        let history = load(temp.path()).unwrap();
+
+        assert_eq!(history.len(), MAX_HISTORY_LINES);
+        assert!(history.iter().all(|e| e.event == HistoryEvent::Switched));
+    }
+}
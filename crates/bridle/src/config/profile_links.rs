@@ -0,0 +1,289 @@
+//! Shared resource libraries a profile pulls in on switch (`.bridle_links.toml`).
+//!
+//! ```toml
+//! skills = "/path/to/shared/skills"
+//! agents = "../shared-agents"   # relative to the profile directory
+//! ```
+//!
+//! Each entry of a linked directory is symlinked into the live config's
+//! matching resource directory (copied on platforms without symlinks). Entries
+//! the profile ships itself take precedence over linked ones with the same name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{Harness, Scope};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// File name of the links file inside a profile directory.
+///
+/// Never copied into the live harness config.
+pub const PROFILE_LINKS_FILE: &str = ".bridle_links.toml";
+
+/// Contents of a profile's `.bridle_links.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileLinks {
+    /// Shared skills directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<PathBuf>,
+    /// Shared agents directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agents: Option<PathBuf>,
+    /// Shared commands directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands: Option<PathBuf>,
+}
+
+impl ProfileLinks {
+    /// Reads the links for the profile at `profile_path`, or none if absent.
+    pub fn load(profile_path: &Path) -> Result<Self> {
+        let path = profile_path.join(PROFILE_LINKS_FILE);
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            Ok(toml::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Each declared resource kind (`"skills"`, `"agents"`, `"commands"`) with
+    /// its source directory, resolved against `profile_path` when relative.
+    pub fn sources(&self, profile_path: &Path) -> Vec<(&'static str, PathBuf)> {
+        [
+            ("skills", &self.skills),
+            ("agents", &self.agents),
+            ("commands", &self.commands),
+        ]
+        .into_iter()
+        .filter_map(|(kind, dir)| dir.as_ref().map(|d| (kind, profile_path.join(d))))
+        .collect()
+    }
+
+    /// Names of the linked `kind` resources that `resource_root` (a profile or
+    /// live config dir) does not provide itself, sorted.
+    ///
+    /// An entry counts as linked when it is missing from `resource_root` or is
+    /// a symlink there. Directories are named as-is and files by their stem,
+    /// matching how extraction names resources.
+    pub fn linked_items(
+        &self,
+        profile_path: &Path,
+        resource_root: &Path,
+        harness: Option<&Harness>,
+        kind: &str,
+    ) -> Vec<String> {
+        let Some((_, source)) = self
+            .sources(profile_path)
+            .into_iter()
+            .find(|(k, _)| *k == kind)
+        else {
+            return Vec::new();
+        };
+        let dir = resource_root.join(live_subdir(harness, kind));
+        let mut names: Vec<String> = source_entries(&source)
+            .iter()
+            .filter(|path| {
+                let own = dir.join(path.file_name().unwrap_or_default());
+                own.symlink_metadata()
+                    .map_or(true, |m| m.file_type().is_symlink())
+            })
+            .map(|path| {
+                let stem = if path.is_dir() {
+                    path.file_name()
+                } else {
+                    path.file_stem()
+                };
+                stem.unwrap_or_default().to_string_lossy().into_owned()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Name of the live directory holding `kind` resources for `harness`.
+///
+/// Falls back to `kind` itself when the harness is unknown or has no such
+/// directory.
+pub(crate) fn live_subdir(harness: Option<&Harness>, kind: &str) -> String {
+    let dir = harness.and_then(|h| {
+        match kind {
+            "skills" => h.skills(&Scope::Global),
+            "agents" => h.agents(&Scope::Global),
+            _ => h.commands(&Scope::Global),
+        }
+        .ok()
+        .flatten()
+    });
+    dir.and_then(|d| d.path.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| kind.to_string())
+}
+
+/// Links every shared entry declared by the profile into `config_dir`, leaving
+/// entries that already exist there alone.
+pub(crate) fn apply(
+    profile_path: &Path,
+    config_dir: &Path,
+    harness: Option<&Harness>,
+) -> Result<()> {
+    for (kind, source) in ProfileLinks::load(profile_path)?.sources(profile_path) {
+        if !source.is_dir() {
+            crate::diagnostics::warn(format!(
+                "Warning: Linked {} directory not found: {}",
+                kind,
+                source.display()
+            ));
+            continue;
+        }
+        let dest_dir = config_dir.join(live_subdir(harness, kind));
+        fs::create_dir_all(&dest_dir)?;
+        for entry in source_entries(&source) {
+            let dest = dest_dir.join(entry.file_name().unwrap_or_default());
+            if dest.symlink_metadata().is_ok() {
+                continue;
+            }
+            link_entry(&entry, &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes symlinks into the profile's shared directories from a freshly
+/// saved profile, so linked resources are not captured as its own.
+pub(crate) fn prune(profile_path: &Path, harness: Option<&Harness>) -> Result<()> {
+    for (kind, source) in ProfileLinks::load(profile_path)?.sources(profile_path) {
+        let dir = profile_path.join(live_subdir(harness, kind));
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Ok(target) = fs::read_link(entry.path())
+                && target.starts_with(&source)
+            {
+                fs::remove_file(entry.path())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn source_entries(source: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(source) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect()
+}
+
+#[cfg(unix)]
+fn link_entry(source: &Path, dest: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(source, dest)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_entry(source: &Path, dest: &Path) -> Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            link_entry(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, dest)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn relative_sources_resolve_against_profile() {
+        let temp = TempDir::new().unwrap();
+        let profile = temp.path().join("work");
+        fs::create_dir_all(&profile).unwrap();
+        fs::write(
+            profile.join(PROFILE_LINKS_FILE),
+            "skills = \"../shared\"\ncommands = \"/abs/commands\"\n",
+        )
+        .unwrap();
+
+        let links = ProfileLinks::load(&profile).unwrap();
+        assert_eq!(
+            links.sources(&profile),
+            vec![
+                ("skills", profile.join("../shared")),
+                ("commands", PathBuf::from("/abs/commands")),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_links_shared_entries_without_overriding_profile() {
+        let temp = TempDir::new().unwrap();
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(shared.join("review")).unwrap();
+        fs::create_dir_all(shared.join("deploy")).unwrap();
+        fs::write(shared.join("review/SKILL.md"), "shared").unwrap();
+        let profile = temp.path().join("work");
+        fs::create_dir_all(&profile).unwrap();
+        fs::write(
+            profile.join(PROFILE_LINKS_FILE),
+            format!("skills = {:?}\n", shared),
+        )
+        .unwrap();
+        let live = temp.path().join("live");
+        fs::create_dir_all(live.join("skills/deploy")).unwrap();
+        fs::write(live.join("skills/deploy/SKILL.md"), "own").unwrap();
+
+        apply(&profile, &live, None).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(live.join("skills/review/SKILL.md")).unwrap(),
+            "shared"
+        );
+        assert_eq!(
+            fs::read_to_string(live.join("skills/deploy/SKILL.md")).unwrap(),
+            "own"
+        );
+        let links = ProfileLinks::load(&profile).unwrap();
+        assert_eq!(
+            links.linked_items(&profile, &profile, None, "skills"),
+            ["deploy", "review"]
+        );
+        assert_eq!(
+            links.linked_items(&profile, &live, None, "skills"),
+            ["review"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prune_drops_only_links_into_shared_dir() {
+        let temp = TempDir::new().unwrap();
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(shared.join("review")).unwrap();
+        let profile = temp.path().join("work");
+        fs::create_dir_all(profile.join("skills/own")).unwrap();
+        std::os::unix::fs::symlink(shared.join("review"), profile.join("skills/review")).unwrap();
+        fs::write(
+            profile.join(PROFILE_LINKS_FILE),
+            format!("skills = {:?}\n", shared),
+        )
+        .unwrap();
+
+        prune(&profile, None).unwrap();
+
+        assert!(profile.join("skills/own").exists());
+        assert!(profile.join("skills/review").symlink_metadata().is_err());
+    }
+}
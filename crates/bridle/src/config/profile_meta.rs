@@ -0,0 +1,111 @@
+//! Per-profile settings stored alongside the captured config.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the metadata file inside a profile directory.
+///
+/// Never copied into the live harness config.
+pub const PROFILE_META_FILE: &str = ".bridle_profile.toml";
+
+/// Contents of a profile's `.bridle_profile.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileMeta {
+    /// Whether skills/agents/commands/plugins are captured and switched with
+    /// this profile. When false, the live resource directories are left alone.
+    #[serde(default = "default_true")]
+    pub capture_resources: bool,
+    /// Free-form note shown by `profile show`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Labels for grouping and filtering profiles.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// RFC 3339 timestamp of when the profile was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// Harness version the live config was last captured from, if it could
+    /// be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captured_with: Option<String>,
+    /// Pins the profile: while it is active, switching away needs `--force`,
+    /// and it can't be deleted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ProfileMeta {
+    fn default() -> Self {
+        Self {
+            capture_resources: true,
+            description: None,
+            tags: Vec::new(),
+            created_at: None,
+            captured_with: None,
+            locked: false,
+        }
+    }
+}
+
+impl ProfileMeta {
+    /// Reads the metadata for the profile at `profile_path`, or defaults if absent.
+    pub fn load(profile_path: &Path) -> crate::error::Result<Self> {
+        let path = profile_path.join(PROFILE_META_FILE);
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            Ok(toml::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Writes the metadata into the profile directory.
+    pub fn save(&self, profile_path: &Path) -> crate::error::Result<()> {
+        let content =
+            toml::to_string_pretty(self).map_err(|e| crate::error::Error::Config(e.to_string()))?;
+        std::fs::write(profile_path.join(PROFILE_META_FILE), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn missing_file_defaults_to_capturing_resources() {
+        let temp = TempDir::new().unwrap();
+        assert!(ProfileMeta::load(temp.path()).unwrap().capture_resources);
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let temp = TempDir::new().unwrap();
+        let meta = ProfileMeta {
+            capture_resources: false,
+            description: Some("Client work".to_string()),
+            tags: vec!["work".to_string(), "minimal".to_string()],
+            created_at: Some("2026-01-02T03:04:05+00:00".to_string()),
+            captured_with: Some("1.0.3".to_string()),
+            locked: true,
+        };
+        meta.save(temp.path()).unwrap();
+        assert_eq!(ProfileMeta::load(temp.path()).unwrap(), meta);
+    }
+
+    #[test]
+    fn reads_partial_file() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join(PROFILE_META_FILE), "tags = [\"work\"]\n").unwrap();
+
+        let meta = ProfileMeta::load(temp.path()).unwrap();
+        assert!(meta.capture_resources);
+        assert_eq!(meta.tags, ["work"]);
+        assert_eq!(meta.description, None);
+    }
+}
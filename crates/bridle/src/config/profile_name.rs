@@ -1,6 +1,7 @@
 //! Profile name validation and type.
 
 use std::fmt;
+use std::str::FromStr;
 
 /// A validated profile name.
 ///
@@ -9,7 +10,11 @@ use std::fmt;
 /// - Lowercase alphanumeric with hyphens
 /// - No leading or trailing hyphens
 /// - No consecutive hyphens
-#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize)]
+/// - Not a Windows reserved device name (`con`, `nul`, `com1`, ...)
+///
+/// Serializes as the bare name; deserializing validates it like [`Self::new`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String")]
 pub struct ProfileName(String);
 
 impl ProfileName {
@@ -25,6 +30,31 @@ impl ProfileName {
         Ok(Self(name.to_lowercase()))
     }
 
+    /// Build a valid profile name from arbitrary input.
+    ///
+    /// Lowercases, replaces anything that isn't ASCII alphanumeric with `-`,
+    /// collapses repeated hyphens, trims them from the ends, and truncates to
+    /// [`Self::MAX_LENGTH`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nothing valid remains (e.g. `"!!!"`).
+    pub fn sanitize(input: &str) -> Result<Self, InvalidProfileName> {
+        let mut sanitized = input
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+
+        sanitized.truncate(Self::MAX_LENGTH);
+        let sanitized = sanitized.trim_end_matches('-');
+        Self::new(sanitized)
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
@@ -52,16 +82,45 @@ impl ProfileName {
             }
         }
 
+        if is_windows_reserved_name(name) {
+            return Err(InvalidProfileName::Reserved(name.to_lowercase()));
+        }
+
         Ok(())
     }
 }
 
+/// Device names Windows refuses as file names, with or without an extension.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Whether `name` is unusable as a file name on Windows because it names a
+/// device (`CON`, `nul.txt`, `COM1`, ...).
+///
+/// Checked on every platform so profiles and installs stay portable.
+pub(crate) fn is_windows_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|r| stem.eq_ignore_ascii_case(r))
+}
+
 impl fmt::Display for ProfileName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
+impl FromStr for ProfileName {
+    type Err = InvalidProfileName;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
 impl TryFrom<&str> for ProfileName {
     type Error = InvalidProfileName;
 
@@ -97,6 +156,8 @@ pub enum InvalidProfileName {
     ConsecutiveHyphens,
     /// Profile name contains an invalid character.
     InvalidCharacter(char),
+    /// Profile name is a Windows reserved device name.
+    Reserved(String),
 }
 
 impl fmt::Display for InvalidProfileName {
@@ -120,6 +181,9 @@ impl fmt::Display for InvalidProfileName {
                     "invalid character '{c}': only lowercase alphanumeric and hyphens allowed"
                 )
             }
+            Self::Reserved(name) => {
+                write!(f, "profile name '{name}' is reserved on Windows")
+            }
         }
     }
 }
@@ -199,6 +263,59 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn rejects_windows_reserved_names() {
+        assert_eq!(
+            ProfileName::new("CON"),
+            Err(InvalidProfileName::Reserved("con".to_string()))
+        );
+        assert!(matches!(
+            ProfileName::new("lpt9"),
+            Err(InvalidProfileName::Reserved(_))
+        ));
+        assert!(matches!(
+            ProfileName::new("a."),
+            Err(InvalidProfileName::InvalidCharacter('.'))
+        ));
+        assert!(ProfileName::new("console").is_ok());
+        assert!(ProfileName::new("com10").is_ok());
+    }
+
+    #[test]
+    fn sanitize_replaces_spaces_and_slashes() {
+        assert_eq!(
+            ProfileName::sanitize("My Work Profile").unwrap().as_str(),
+            "my-work-profile"
+        );
+        assert_eq!(
+            ProfileName::sanitize("client/acme  //prod")
+                .unwrap()
+                .as_str(),
+            "client-acme-prod"
+        );
+        assert_eq!(
+            ProfileName::sanitize("  -already-valid- ")
+                .unwrap()
+                .as_str(),
+            "already-valid"
+        );
+    }
+
+    #[test]
+    fn sanitize_truncates_without_trailing_hyphen() {
+        let input = format!("{}-b", "a".repeat(63));
+        let name = ProfileName::sanitize(&input).unwrap();
+        assert_eq!(name.as_str(), "a".repeat(63));
+    }
+
+    #[test]
+    fn sanitize_rejects_all_symbols() {
+        assert_eq!(
+            ProfileName::sanitize("!@#$ %/"),
+            Err(InvalidProfileName::Empty)
+        );
+    }
+
     #[test]
     fn try_from_str() {
         let name: Result<ProfileName, _> = "valid-name".try_into();
@@ -210,4 +327,27 @@ mod tests {
         let name: Result<ProfileName, _> = String::from("valid-name").try_into();
         assert!(name.is_ok());
     }
+
+    #[test]
+    fn from_str_validates() {
+        assert_eq!(
+            "Work".parse::<ProfileName>().unwrap(),
+            ProfileName::new("work").unwrap()
+        );
+        assert_eq!(
+            "my--profile".parse::<ProfileName>(),
+            Err(InvalidProfileName::ConsecutiveHyphens)
+        );
+    }
+
+    #[test]
+    fn serde_round_trips_and_rejects_invalid_names() {
+        let name = ProfileName::new("work").unwrap();
+        let json = serde_json::to_string(&name).unwrap();
+        assert_eq!(json, r#""work""#);
+        assert_eq!(serde_json::from_str::<ProfileName>(&json).unwrap(), name);
+
+        let err = serde_json::from_str::<ProfileName>(r#""my_profile""#).unwrap_err();
+        assert!(err.to_string().contains("invalid character '_'"), "{err}");
+    }
 }
@@ -0,0 +1,258 @@
+//! Minimal JSON Schemas for the main config files of some built-in
+//! harnesses, bundled for `profile validate-config`.
+//!
+//! Only the keywords the bundled schemas use are understood: `type`, `enum`,
+//! `oneOf`, `properties`, `required`, `additionalProperties`, and `items`. Keys a
+//! schema doesn't mention are allowed, so a newer harness setting is never
+//! reported as a mistake.
+
+use std::sync::LazyLock;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// A bundled schema. `version` goes up whenever the schema changes, so a
+/// report can say what it was checked against.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigSchema {
+    pub harness_id: &'static str,
+    pub version: u32,
+    source: &'static str,
+}
+
+const SCHEMAS: &[ConfigSchema] = &[
+    ConfigSchema {
+        harness_id: "opencode",
+        version: 2,
+        source: include_str!("schemas/opencode.v2.json"),
+    },
+    ConfigSchema {
+        harness_id: "claude-code",
+        version: 1,
+        source: include_str!("schemas/claude-code.v1.json"),
+    },
+    ConfigSchema {
+        harness_id: "amp-code",
+        version: 1,
+        source: include_str!("schemas/amp-code.v1.json"),
+    },
+];
+
+/// [`SCHEMAS`] parsed, in the same order, once per process.
+static PARSED: LazyLock<Vec<Result<Value, String>>> = LazyLock::new(|| {
+    SCHEMAS
+        .iter()
+        .map(|s| serde_json::from_str(s.source).map_err(|e| e.to_string()))
+        .collect()
+});
+
+/// The schema bundled for `harness_id`, if there is one.
+pub fn schema_for(harness_id: &str) -> Option<ConfigSchema> {
+    SCHEMAS.iter().copied().find(|s| s.harness_id == harness_id)
+}
+
+/// One place where a config breaks its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value; empty for the document itself.
+    pub path: String,
+    pub message: String,
+}
+
+impl ConfigSchema {
+    /// Every violation in `config`, with object keys visited in sorted order.
+    pub fn validate(&self, config: &Value) -> Vec<SchemaViolation> {
+        let parsed = SCHEMAS
+            .iter()
+            .position(|s| s.harness_id == self.harness_id)
+            .map(|i| &PARSED[i]);
+        let mut violations = Vec::new();
+        match parsed {
+            Some(Ok(schema)) => check(schema, config, "", &mut violations),
+            Some(Err(e)) => violations.push(SchemaViolation {
+                path: String::new(),
+                message: format!("bundled schema is invalid: {e}"),
+            }),
+            None => {}
+        }
+        violations
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let mut violation = |message: String| {
+        out.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            return violation(format!(
+                "expected {}, found {}",
+                types.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+    if let Some(alternatives) = schema.get("oneOf").and_then(Value::as_array) {
+        let matching = alternatives
+            .iter()
+            .filter(|alt| {
+                let mut found = Vec::new();
+                check(alt, value, path, &mut found);
+                found.is_empty()
+            })
+            .count();
+        if matching != 1 {
+            return violation(format!(
+                "expected exactly one of the allowed forms to match, {matching} did"
+            ));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return violation(format!(
+            "expected one of {}, found {}",
+            allowed.join(", "),
+            value
+        ));
+    }
+
+    match value {
+        Value::Object(map) => {
+            for key in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !map.contains_key(key) {
+                    violation(format!("missing required property \"{key}\""));
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            for (key, child) in entries {
+                let child_path = format!("{path}/{}", escape_pointer(key));
+                match (
+                    properties.and_then(|p| p.get(key)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(child_schema), _) => check(child_schema, child, &child_path, out),
+                    (None, Some(Value::Bool(false))) => out.push(SchemaViolation {
+                        path: child_path,
+                        message: "property is not allowed".to_string(),
+                    }),
+                    (None, Some(extra @ Value::Object(_))) => check(extra, child, &child_path, out),
+                    (None, _) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{path}/{i}"), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escapes `~` and `/` in a key as JSON Pointer requires.
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn violations_carry_json_pointer_paths() {
+        let schema = schema_for("opencode").unwrap();
+        let config = json!({
+            "theme": 3,
+            "share": "sometimes",
+            "mcp": {"files": {"type": "local", "command": ["npx", 1]}, "web": {}},
+            "unknown_setting": true,
+        });
+
+        let violations = schema.validate(&config);
+
+        let found: Vec<(&str, &str)> = violations
+            .iter()
+            .map(|v| (v.path.as_str(), v.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("/mcp/files/command/1", "expected string, found number"),
+                ("/mcp/web", "missing required property \"type\""),
+                (
+                    "/share",
+                    "expected one of \"manual\", \"auto\", \"disabled\", found \"sometimes\""
+                ),
+                ("/theme", "expected string, found number"),
+            ]
+        );
+    }
+
+    #[test]
+    fn one_of_takes_a_boolean_or_notify_for_autoupdate() {
+        let schema = schema_for("opencode").unwrap();
+        for autoupdate in [json!(true), json!("notify")] {
+            assert_eq!(schema.validate(&json!({ "autoupdate": autoupdate })), []);
+        }
+        let violations = schema.validate(&json!({"autoupdate": "always"}));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "/autoupdate");
+    }
+
+    #[test]
+    fn bundled_schemas_parse_and_accept_the_full_templates() {
+        for schema in SCHEMAS {
+            let files = crate::config::template_files(
+                schema.harness_id,
+                crate::config::ProfileTemplate::Full,
+            )
+            .unwrap();
+            let (_, contents) = files[0];
+            let config: Value =
+                serde_json::from_str(&crate::config::jsonc::strip_jsonc_comments(contents))
+                    .unwrap();
+            assert_eq!(schema.validate(&config), [], "{}", schema.harness_id);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Built-in starter profiles for `profile create --template`, keyed by
+//! harness id.
+
+/// A built-in starter profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProfileTemplate {
+    /// Just enough config for the harness to start.
+    Minimal,
+    /// A commented config with a model, an example MCP server, and rules.
+    Full,
+}
+
+impl ProfileTemplate {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Files of a template as `(path relative to the profile, contents)` pairs.
+pub type TemplateFiles = &'static [(&'static str, &'static str)];
+
+const OPENCODE_MINIMAL: TemplateFiles = &[(
+    "opencode.jsonc",
+    r#"{
+  "$schema": "https://opencode.ai/config.json"
+}
+"#,
+)];
+
+const OPENCODE_FULL: TemplateFiles = &[
+    (
+        "opencode.jsonc",
+        r#"{
+  "$schema": "https://opencode.ai/config.json",
+  // Model used unless an agent overrides it.
+  "model": "anthropic/claude-sonnet-4-5",
+  "theme": "opencode",
+  "mcp": {
+    // Example local server; set "enabled" to true once it suits you.
+    "filesystem": {
+      "type": "local",
+      "command": ["npx", "-y", "@modelcontextprotocol/server-filesystem", "."],
+      "enabled": false
+    }
+  }
+}
+"#,
+    ),
+    ("AGENTS.md", RULES),
+];
+
+const CLAUDE_CODE_MINIMAL: TemplateFiles = &[(
+    "settings.json",
+    r#"{
+  "$schema": "https://json.schemastore.org/claude-code-settings.json"
+}
+"#,
+)];
+
+const CLAUDE_CODE_FULL: TemplateFiles = &[
+    (
+        "settings.json",
+        r#"{
+  "$schema": "https://json.schemastore.org/claude-code-settings.json",
+  "model": "sonnet",
+  "permissions": {
+    "allow": ["Bash(git status)", "Bash(git diff:*)"],
+    "deny": ["Read(./.env)"]
+  }
+}
+"#,
+    ),
+    (
+        ".mcp.json",
+        r#"{
+  "mcpServers": {
+    "filesystem": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "."],
+      "disabled": true
+    }
+  }
+}
+"#,
+    ),
+    ("CLAUDE.md", RULES),
+];
+
+const GOOSE_MINIMAL: TemplateFiles = &[("config.yaml", "extensions: {}\n")];
+
+const GOOSE_FULL: TemplateFiles = &[(
+    "config.yaml",
+    r#"GOOSE_PROVIDER: anthropic
+GOOSE_MODEL: claude-sonnet-4-5
+extensions:
+  developer:
+    type: builtin
+    name: developer
+    enabled: true
+  filesystem:
+    type: stdio
+    cmd: npx
+    args: ["-y", "@modelcontextprotocol/server-filesystem", "."]
+    enabled: false
+"#,
+)];
+
+const AMP_CODE_MINIMAL: TemplateFiles = &[("settings.json", "{}\n")];
+
+const AMP_CODE_FULL: TemplateFiles = &[
+    (
+        "settings.json",
+        r#"{
+  "amp.model.default": "claude-sonnet-4-5",
+  "amp.mcpServers": {
+    "filesystem": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "."]
+    }
+  }
+}
+"#,
+    ),
+    ("AGENTS.md", RULES),
+];
+
+const CRUSH_MINIMAL: TemplateFiles = &[(
+    "crush.json",
+    r#"{
+  "$schema": "https://charm.land/crush.json"
+}
+"#,
+)];
+
+const CRUSH_FULL: TemplateFiles = &[(
+    "crush.json",
+    r#"{
+  "$schema": "https://charm.land/crush.json",
+  "mcp": {
+    "filesystem": {
+      "type": "stdio",
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "."],
+      "disabled": true
+    }
+  }
+}
+"#,
+)];
+
+const COPILOT_CLI_MINIMAL: TemplateFiles = &[("config.json", "{}\n")];
+
+const COPILOT_CLI_FULL: TemplateFiles = &[
+    (
+        "config.json",
+        r#"{
+  "model": "claude-sonnet-4.5"
+}
+"#,
+    ),
+    (
+        "mcp-config.json",
+        r#"{
+  "mcpServers": {
+    "filesystem": {
+      "type": "local",
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "."],
+      "tools": ["*"]
+    }
+  }
+}
+"#,
+    ),
+];
+
+/// Starter rules file shared by the full templates.
+const RULES: &str = "# Project rules\n\n\
+- Describe the project and how to build and test it here.\n\
+- List conventions the agent should follow.\n";
+
+/// The files of `template` for the harness `harness_id`, or `None` if bridle
+/// ships no starter profile for that harness.
+pub fn template_files(harness_id: &str, template: ProfileTemplate) -> Option<TemplateFiles> {
+    use ProfileTemplate::{Full, Minimal};
+    Some(match (harness_id, template) {
+        ("opencode", Minimal) => OPENCODE_MINIMAL,
+        ("opencode", Full) => OPENCODE_FULL,
+        ("claude-code", Minimal) => CLAUDE_CODE_MINIMAL,
+        ("claude-code", Full) => CLAUDE_CODE_FULL,
+        ("goose", Minimal) => GOOSE_MINIMAL,
+        ("goose", Full) => GOOSE_FULL,
+        ("amp-code", Minimal) => AMP_CODE_MINIMAL,
+        ("amp-code", Full) => AMP_CODE_FULL,
+        ("crush", Minimal) => CRUSH_MINIMAL,
+        ("crush", Full) => CRUSH_FULL,
+        ("copilot-cli", Minimal) => COPILOT_CLI_MINIMAL,
+        ("copilot-cli", Full) => COPILOT_CLI_FULL,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::jsonc::strip_jsonc_comments;
+
+    #[test]
+    fn template_config_files_parse() {
+        for id in [
+            "opencode",
+            "claude-code",
+            "goose",
+            "amp-code",
+            "crush",
+            "copilot-cli",
+        ] {
+            for template in [ProfileTemplate::Minimal, ProfileTemplate::Full] {
+                for (path, contents) in template_files(id, template).unwrap() {
+                    if path.ends_with(".json") || path.ends_with(".jsonc") {
+                        serde_json::from_str::<serde_json::Value>(&strip_jsonc_comments(contents))
+                            .unwrap_or_else(|e| panic!("{id}/{path}: {e}"));
+                    } else if path.ends_with(".yaml") {
+                        serde_yaml::from_str::<serde_yaml::Value>(contents)
+                            .unwrap_or_else(|e| panic!("{id}/{path}: {e}"));
+                    }
+                }
+            }
+        }
+        assert!(template_files("droid", ProfileTemplate::Minimal).is_none());
+    }
+}
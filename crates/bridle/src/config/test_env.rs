@@ -0,0 +1,43 @@
+//! Shared `BRIDLE_CONFIG_DIR` isolation for tests.
+//!
+//! Every test that touches [`BridleConfig`](super::BridleConfig) must hold the
+//! same lock, otherwise tests in different modules race on the process-wide
+//! environment.
+
+use std::ffi::OsString;
+use std::fs;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use tempfile::TempDir;
+
+static TEST_ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+pub(crate) struct TestEnvGuard {
+    _lock: MutexGuard<'static, ()>,
+    prev: Option<OsString>,
+}
+
+impl Drop for TestEnvGuard {
+    fn drop(&mut self) {
+        if let Some(prev) = &self.prev {
+            unsafe { std::env::set_var("BRIDLE_CONFIG_DIR", prev) };
+        } else {
+            unsafe { std::env::remove_var("BRIDLE_CONFIG_DIR") };
+        }
+    }
+}
+
+/// Points `BRIDLE_CONFIG_DIR` at `<temp>/bridle_config` until the guard drops.
+pub(crate) fn setup_test_env(temp: &TempDir) -> TestEnvGuard {
+    let lock = TEST_ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let prev = std::env::var_os("BRIDLE_CONFIG_DIR");
+    let bridle_config_dir = temp.path().join("bridle_config");
+    fs::create_dir_all(&bridle_config_dir).unwrap();
+    unsafe { std::env::set_var("BRIDLE_CONFIG_DIR", &bridle_config_dir) };
+
+    TestEnvGuard { _lock: lock, prev }
+}
@@ -0,0 +1,51 @@
+//! Telling text files from binary ones before bridle transforms them.
+
+use std::io::Read;
+use std::path::Path;
+
+/// How many leading bytes are sniffed when deciding a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// Whether `bytes` look like a binary file: a NUL byte or invalid UTF-8 in
+/// the first few kilobytes.
+///
+/// Text transforms (comment stripping, line-ending normalization, rewriting
+/// settings) must skip such files so they are only ever copied byte for byte.
+pub fn is_probably_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if head.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        // A character cut in half by the sniff limit is still text.
+        Err(e) => e.error_len().is_some() || head.len() == bytes.len(),
+    }
+}
+
+/// [`is_probably_binary`] for the file at `path`, reading only its first
+/// few kilobytes.
+pub fn is_probably_binary_file(path: &Path) -> std::io::Result<bool> {
+    // One byte past the sniff limit tells a cut-off character from the end of the file.
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES + 1);
+    std::fs::File::open(path)?
+        .take(BINARY_SNIFF_BYTES as u64 + 1)
+        .read_to_end(&mut head)?;
+    Ok(is_probably_binary(&head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nul_bytes_and_invalid_utf8_are_binary() {
+        assert!(is_probably_binary(b"PK\x03\x04\0\0"));
+        assert!(is_probably_binary(b"\xff\xfe not utf-8"));
+        assert!(!is_probably_binary("{\"theme\": \"café\"}\r\n".as_bytes()));
+
+        let mut long = vec![b'a'; BINARY_SNIFF_BYTES - 1];
+        long.extend_from_slice("é".as_bytes());
+        assert!(!is_probably_binary(&long));
+    }
+}
@@ -2,10 +2,10 @@
 
 use std::path::PathBuf;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// MCP server info with enabled status and connection details.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct McpServerInfo {
     pub name: String,
     pub enabled: bool,
@@ -13,6 +13,23 @@ pub struct McpServerInfo {
     pub command: Option<String>,
     pub args: Option<Vec<String>>,
     pub url: Option<String>,
+    /// Names of the environment variables the server is given; values are
+    /// left out since they often hold secrets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_keys: Vec<String>,
+}
+
+impl McpServerInfo {
+    /// The server's transport: `sse`, `http` or `stdio`.
+    ///
+    /// A server with a URL and no type or command is taken to be remote.
+    pub fn transport(&self) -> &'static str {
+        match (self.server_type.as_deref(), &self.command, &self.url) {
+            (Some("sse"), ..) => "sse",
+            (Some("http" | "streamable_http" | "remote"), ..) | (None, None, Some(_)) => "http",
+            _ => "stdio",
+        }
+    }
 }
 
 /// Summary of directory-based resources (skills, commands, etc.).
@@ -22,6 +39,99 @@ pub struct ResourceSummary {
     pub items: Vec<String>,
     /// Whether the resource directory exists.
     pub directory_exists: bool,
+    /// Items pulled in from a shared library via `.bridle_links.toml`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub linked: Vec<String>,
+}
+
+impl ResourceSummary {
+    /// Records `names` as linked, adding any the summary doesn't list yet.
+    pub fn mark_linked(&mut self, names: Vec<String>) {
+        for name in &names {
+            if !self.items.contains(name) {
+                self.items.push(name.clone());
+            }
+        }
+        if !names.is_empty() {
+            self.directory_exists = true;
+        }
+        self.linked = names;
+    }
+
+    /// Whether `item` comes from a linked shared library.
+    pub fn is_linked(&self, item: &str) -> bool {
+        self.linked.iter().any(|l| l == item)
+    }
+}
+
+/// A setting read from a harness config file.
+///
+/// Serializes as the value, or `null` when there is none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FieldValue {
+    /// The config file the setting lives in doesn't exist.
+    Missing,
+    /// The config file doesn't set it, or the harness has no such setting.
+    #[default]
+    Unset,
+    Set(String),
+}
+
+impl FieldValue {
+    /// The value, if set.
+    pub fn as_deref(&self) -> Option<&str> {
+        match self {
+            Self::Set(value) => Some(value),
+            Self::Missing | Self::Unset => None,
+        }
+    }
+}
+
+impl Serialize for FieldValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_deref().serialize(serializer)
+    }
+}
+
+/// Where an [`EffectiveModel`] was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelSource {
+    /// An environment variable the harness reads at startup.
+    Env,
+    /// The harness config file.
+    Config,
+}
+
+impl ModelSource {
+    /// Short label shown next to the model, e.g. `from env`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Env => "from env",
+            Self::Config => "from config",
+        }
+    }
+}
+
+/// The model a running harness would actually use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EffectiveModel {
+    pub model: String,
+    pub source: ModelSource,
+    /// Variable that supplied the model when `source` is [`ModelSource::Env`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_var: Option<String>,
+}
+
+/// How a profile with a `.bridle_base.toml` relates to its bases.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BaseLayering {
+    /// Base profile names, nearest first.
+    pub chain: Vec<String>,
+    /// Files only the bases provide.
+    pub inherited: Vec<String>,
+    /// Base files the profile replaces with its own.
+    pub overridden: Vec<String>,
 }
 
 /// Information about a profile for display purposes.
@@ -49,12 +159,55 @@ pub struct ProfileInfo {
     pub agents: Option<ResourceSummary>,
     /// Path to rules file if it exists.
     pub rules_file: Option<PathBuf>,
+    /// Keys declared in the profile's `.env` file. Values are never read.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub env_file_keys: Vec<String>,
     /// Theme setting (OpenCode only).
-    pub theme: Option<String>,
+    pub theme: FieldValue,
     /// Model setting.
-    pub model: Option<String>,
+    pub model: FieldValue,
+    /// Model after environment overrides, for the active profile only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_model: Option<EffectiveModel>,
     /// Errors encountered during extraction.
     pub extraction_errors: Vec<String>,
+    /// Non-fatal issues found during extraction, such as conflicting MCP definitions.
+    pub extraction_warnings: Vec<String>,
+    /// Description from the profile's metadata file.
+    pub description: Option<String>,
+    /// Tags from the profile's metadata file.
+    pub tags: Vec<String>,
+    /// Creation time from the profile's metadata file.
+    pub created_at: Option<String>,
+    /// Harness version the profile was last captured from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captured_with: Option<String>,
+    /// Whether the profile's metadata pins it against switching away and deletion.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    /// Base profiles this one inherits from, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<BaseLayering>,
+}
+
+impl ProfileInfo {
+    /// Whether the profile carries `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// A warning when the profile was captured with a different harness
+    /// version than the `installed` one. Nothing to compare is no warning.
+    pub fn version_mismatch(&self, installed: Option<&str>) -> Option<String> {
+        let captured = self.captured_with.as_deref()?;
+        let installed = installed?;
+        (captured != installed).then(|| {
+            format!(
+                "Captured with {} v{}, but v{} is installed",
+                self.harness_id, captured, installed
+            )
+        })
+    }
 }
 
 #[cfg(test)]
@@ -84,6 +237,45 @@ mod tests {
         assert!(info.mcp_servers.is_empty());
     }
 
+    #[test]
+    fn mark_linked_adds_missing_items() {
+        let mut summary = ResourceSummary {
+            items: vec!["own".to_string()],
+            directory_exists: true,
+            linked: Vec::new(),
+        };
+        summary.mark_linked(vec!["shared".to_string()]);
+        assert_eq!(summary.items, ["own", "shared"]);
+        assert!(summary.is_linked("shared"));
+        assert!(!summary.is_linked("own"));
+    }
+
+    #[test]
+    fn has_tag_matches_exactly() {
+        let info = ProfileInfo {
+            tags: vec!["work".to_string()],
+            ..Default::default()
+        };
+        assert!(info.has_tag("work"));
+        assert!(!info.has_tag("wor"));
+    }
+
+    #[test]
+    fn version_mismatch_warns_only_when_versions_differ() {
+        let info = ProfileInfo {
+            harness_id: "opencode".to_string(),
+            captured_with: Some("0.5.1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            info.version_mismatch(Some("0.6.0")).as_deref(),
+            Some("Captured with opencode v0.5.1, but v0.6.0 is installed")
+        );
+        assert_eq!(info.version_mismatch(Some("0.5.1")), None);
+        assert_eq!(info.version_mismatch(None), None);
+        assert_eq!(ProfileInfo::default().version_mismatch(Some("0.6.0")), None);
+    }
+
     #[test]
     fn types_serialize_to_json() {
         let info = ProfileInfo {
@@ -0,0 +1,73 @@
+//! Non-fatal warnings emitted by library operations.
+//!
+//! Helpers like the profile copy routines keep going past individual failures
+//! and report them here instead of printing directly. By default messages go to
+//! stderr; the binary installs a no-op logger for `--quiet`, and tests can
+//! capture messages with [`with_logger`].
+
+use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
+
+/// Receives each diagnostic message, without a trailing newline.
+pub type Logger = Arc<dyn Fn(&str) + Send + Sync>;
+
+static GLOBAL: RwLock<Option<Logger>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED: RefCell<Option<Logger>> = const { RefCell::new(None) };
+}
+
+/// Replaces the process-wide logger. `None` restores printing to stderr.
+pub fn set_logger(logger: Option<Logger>) {
+    if let Ok(mut global) = GLOBAL.write() {
+        *global = logger;
+    }
+}
+
+/// A logger that discards every message.
+pub fn quiet() -> Logger {
+    Arc::new(|_| {})
+}
+
+/// Runs `f` with `logger` handling diagnostics emitted on this thread.
+pub fn with_logger<R>(logger: Logger, f: impl FnOnce() -> R) -> R {
+    let previous = SCOPED.with(|s| s.replace(Some(logger)));
+    let result = f();
+    SCOPED.with(|s| *s.borrow_mut() = previous);
+    result
+}
+
+/// The logger installed by [`with_logger`] on this thread, so work handed to
+/// other threads can report to it too.
+pub(crate) fn scoped_logger() -> Option<Logger> {
+    SCOPED.with(|s| s.borrow().clone())
+}
+
+/// Sends `message` to the current logger.
+pub fn warn(message: impl AsRef<str>) {
+    let message = message.as_ref();
+    if let Some(logger) = scoped_logger() {
+        return logger(message);
+    }
+    match GLOBAL.read().ok().and_then(|g| g.clone()) {
+        Some(logger) => logger(message),
+        None => eprintln!("{message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn scoped_logger_captures_messages() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        with_logger(
+            Arc::new(move |m| sink.lock().unwrap().push(m.to_string())),
+            || warn("Warning: something"),
+        );
+        assert_eq!(*seen.lock().unwrap(), ["Warning: something"]);
+    }
+}
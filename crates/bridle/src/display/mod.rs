@@ -5,11 +5,47 @@
 //! according to their output format (flat text vs styled lines with tree branches).
 
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Color, Style},
     text::{Line, Span},
 };
 
-use crate::config::{McpServerInfo, ProfileInfo, ResourceSummary};
+use harness_locate::{HarnessKind, HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+
+use crate::config::{FieldValue, McpServerInfo, ProfileInfo, ResourceSummary};
+use crate::install::types::parse_harness_kind;
+
+mod theme;
+
+pub use theme::Theme;
+
+/// Marker for an enabled MCP server in styled output.
+pub const MCP_ENABLED_SYMBOL: &str = "✓";
+/// Marker for a disabled MCP server in styled output.
+pub const MCP_DISABLED_SYMBOL: &str = "✗";
+/// Marker in front of extraction errors and warnings.
+pub const WARNING_SYMBOL: &str = "⚠";
+/// Marks a `bridle doctor` check that passed.
+pub const CHECK_OK_SYMBOL: &str = "✓";
+/// Marks a `bridle doctor` check that failed.
+pub const CHECK_FAIL_SYMBOL: &str = "✗";
+/// Marks a `bridle doctor` check of something that doesn't exist yet.
+pub const CHECK_MISSING_SYMBOL: &str = "○";
+/// Marks a locked profile.
+pub const LOCK_SYMBOL: &str = "🔒";
+/// Suffix on resources pulled in through `.bridle_links.toml`.
+pub const LINKED_SUFFIX: &str = "(linked)";
+
+/// Shown for theme/model when the harness config file itself is absent.
+const CONFIG_MISSING: &str = "(config file missing)";
+
+/// The profile's name, followed by [`LOCK_SYMBOL`] when it is locked.
+pub fn profile_label(info: &ProfileInfo) -> String {
+    if info.locked {
+        format!("{} {}", info.name, LOCK_SYMBOL)
+    } else {
+        info.name.clone()
+    }
+}
 
 /// Semantic section types for profile display.
 ///
@@ -77,53 +113,157 @@ impl ProfileNode {
 /// Format MCP server detail string.
 ///
 /// Produces a string like `(stdio): npx server-name args` from server info.
+/// A leading `~` and `$VAR`/`${VAR}` references are resolved for display, and
+/// the string is suffixed with `[expanded]` when that changed anything. The
+/// server info itself is never modified.
 pub fn format_mcp_detail(server: &McpServerInfo) -> String {
+    format_mcp_detail_with(server, true)
+}
+
+/// Like [`format_mcp_detail`], with path/env expansion made optional.
+pub fn format_mcp_detail_with(server: &McpServerInfo, expand: bool) -> String {
+    let mut expanded = false;
+    let mut show = |value: &str| {
+        if !expand {
+            return value.to_string();
+        }
+        let resolved = expand_for_display(value);
+        expanded |= resolved != value;
+        resolved
+    };
+
     let args_str = server
         .args
         .as_ref()
-        .map(|a| a.join(" "))
+        .map(|a| a.iter().map(|arg| show(arg)).collect::<Vec<_>>().join(" "))
         .unwrap_or_default();
+    let command = server.command.as_deref().map(&mut show);
+    let url = server.url.as_deref().map(&mut show);
 
-    match (&server.server_type, &server.command, &server.url) {
+    let detail = match (&server.server_type, &command, &url) {
         (Some(t), Some(cmd), _) if args_str.is_empty() => format!("({t}): {cmd}"),
         (Some(t), Some(cmd), _) => format!("({t}): {cmd} {args_str}"),
         (Some(t), None, Some(url)) => format!("({t}): {url}"),
         (Some(t), None, None) => format!("({t})"),
         _ => String::new(),
+    };
+
+    if expanded && !detail.is_empty() {
+        format!("{detail} [expanded]")
+    } else {
+        detail
     }
 }
 
+/// Expand a leading `~` and `$VAR`/`${VAR}` references. Unset variables are left as-is.
+fn expand_for_display(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    let rest = match (value.strip_prefix('~'), std::env::home_dir()) {
+        (Some(tail), Some(home)) if tail.is_empty() || tail.starts_with('/') => {
+            out.push_str(&home.to_string_lossy());
+            tail
+        }
+        _ => value,
+    };
+
+    let mut chars = rest.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        let braced = matches!(chars.peek(), Some((_, '{')));
+        let start = if braced { i + 2 } else { i + 1 };
+        let name_len = rest[start..]
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+            .unwrap_or(rest.len() - start);
+        let name = &rest[start..start + name_len];
+        let end = start + name_len + usize::from(braced);
+        let well_formed = !name.is_empty() && (!braced || rest[end - 1..].starts_with('}'));
+
+        match std::env::var(name) {
+            Ok(val) if well_formed => {
+                out.push_str(&val);
+                while chars.peek().is_some_and(|(j, _)| *j < end) {
+                    chars.next();
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
 /// Build semantic display tree from ProfileInfo.
 ///
 /// The returned nodes can be rendered by CLI (`nodes_to_text`) or TUI (`nodes_to_lines`).
 pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
     let mut nodes = Vec::new();
 
+    let mut header_fields = vec![
+        ProfileNode::new(SectionKind::Field, "Harness").with_text(&info.harness_id),
+        ProfileNode::new(SectionKind::Field, "Status").with_text(if info.is_active {
+            "Active"
+        } else {
+            "Inactive"
+        }),
+        ProfileNode::new(SectionKind::Field, "Path").with_text(info.path.display().to_string()),
+    ];
+    if let Some(description) = &info.description {
+        header_fields
+            .push(ProfileNode::new(SectionKind::Field, "Description").with_text(description));
+    }
+    if !info.tags.is_empty() {
+        header_fields
+            .push(ProfileNode::new(SectionKind::Field, "Tags").with_text(info.tags.join(", ")));
+    }
+    if let Some(created_at) = &info.created_at {
+        header_fields.push(ProfileNode::new(SectionKind::Field, "Created").with_text(created_at));
+    }
+    if info.locked {
+        header_fields.push(ProfileNode::new(SectionKind::Field, "Locked").with_text("yes"));
+    }
+    if let Some(version) = &info.captured_with {
+        header_fields.push(
+            ProfileNode::new(SectionKind::Field, "Captured With")
+                .with_text(format!("{} v{}", info.harness_id, version)),
+        );
+    }
+    if let Some(base) = &info.base {
+        header_fields
+            .push(ProfileNode::new(SectionKind::Field, "Base").with_text(base.chain.join(" -> ")));
+        for (label, files) in [
+            ("Inherited", &base.inherited),
+            ("Overridden", &base.overridden),
+        ] {
+            if !files.is_empty() {
+                header_fields
+                    .push(ProfileNode::new(SectionKind::Field, label).with_text(files.join(", ")));
+            }
+        }
+    }
     nodes.push(
         ProfileNode::new(SectionKind::Header, "Profile")
             .with_text(&info.name)
-            .with_children(vec![
-                ProfileNode::new(SectionKind::Field, "Harness").with_text(&info.harness_id),
-                ProfileNode::new(SectionKind::Field, "Status").with_text(if info.is_active {
-                    "Active"
-                } else {
-                    "Inactive"
-                }),
-                ProfileNode::new(SectionKind::Field, "Path")
-                    .with_text(info.path.display().to_string()),
-            ]),
+            .with_children(header_fields),
     );
 
     let theme_text = match &info.theme {
-        Some(theme) => theme.clone(),
-        None if info.harness_id == "opencode" => "(not set)".to_string(),
-        None => "(not supported)".to_string(),
+        FieldValue::Set(theme) => theme.clone(),
+        FieldValue::Missing => CONFIG_MISSING.to_string(),
+        FieldValue::Unset if info.harness_id == "opencode" => "(not set)".to_string(),
+        FieldValue::Unset => "(not supported)".to_string(),
     };
     nodes.push(ProfileNode::new(SectionKind::Field, "Theme").with_text(theme_text));
 
-    let model_text = match &info.model {
-        Some(model) => model.clone(),
-        None => "(not set)".to_string(),
+    let model_text = match (&info.effective_model, &info.model) {
+        (Some(effective), _) => format!("{} ({})", effective.model, effective.source.label()),
+        (None, FieldValue::Set(model)) => model.clone(),
+        (None, FieldValue::Missing) => CONFIG_MISSING.to_string(),
+        (None, FieldValue::Unset) => "(not set)".to_string(),
     };
     nodes.push(ProfileNode::new(SectionKind::Field, "Model").with_text(model_text));
 
@@ -169,6 +309,13 @@ pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
         .with_text(rules_text),
     );
 
+    if !info.env_file_keys.is_empty() {
+        nodes.push(
+            ProfileNode::new(SectionKind::Field, "Env File")
+                .with_text(info.env_file_keys.join(", ")),
+        );
+    }
+
     if !info.extraction_errors.is_empty() {
         let error_children: Vec<ProfileNode> = info
             .extraction_errors
@@ -178,14 +325,62 @@ pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
         nodes.push(ProfileNode::new(SectionKind::Error, "Errors").with_children(error_children));
     }
 
+    if !info.extraction_warnings.is_empty() {
+        let warning_children: Vec<ProfileNode> = info
+            .extraction_warnings
+            .iter()
+            .map(|warn| ProfileNode::new(SectionKind::Error, "").with_text(warn.clone()))
+            .collect();
+        nodes
+            .push(ProfileNode::new(SectionKind::Error, "Warnings").with_children(warning_children));
+    }
+
     nodes
 }
 
+/// Why `server` won't work on harness `kind`, judged from its transport, or
+/// `None` if it is compatible.
+///
+/// Only the transport is checked: the enabled flag was read from the harness's
+/// own config, and timeouts and OAuth are not extracted.
+fn mcp_compat_warning(server: &McpServerInfo, kind: HarnessKind) -> Option<String> {
+    let url = server.url.clone().unwrap_or_default();
+    let normalized = match server.transport() {
+        "sse" => McpServer::Sse(SseMcpServer {
+            url,
+            headers: Default::default(),
+            enabled: true,
+            timeout_ms: None,
+        }),
+        "http" => McpServer::Http(HttpMcpServer {
+            url,
+            headers: Default::default(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        }),
+        _ => McpServer::Stdio(StdioMcpServer {
+            command: server.command.clone().unwrap_or_default(),
+            args: Vec::new(),
+            env: Default::default(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        }),
+    };
+    match normalized.validate_capabilities(kind) {
+        Ok(()) => None,
+        Err(harness_locate::Error::UnsupportedMcpConfig { reason, .. }) => Some(reason),
+        Err(e) => Some(e.to_string()),
+    }
+}
+
 fn build_mcp_node(info: &ProfileInfo) -> ProfileNode {
     if info.mcp_servers.is_empty() {
         return ProfileNode::new(SectionKind::McpGroup, "MCP Servers").with_text("(none)");
     }
 
+    let kind = parse_harness_kind(&info.harness_id);
     let children: Vec<ProfileNode> = info
         .mcp_servers
         .iter()
@@ -197,13 +392,20 @@ fn build_mcp_node(info: &ProfileInfo) -> ProfileNode {
             } else {
                 format!("{} {}{}", server.name, detail, disabled_suffix)
             };
-            ProfileNode::new(
+            let node = ProfileNode::new(
                 SectionKind::McpServer {
                     enabled: server.enabled,
                 },
                 "",
             )
-            .with_text(text)
+            .with_text(text);
+            match kind.and_then(|kind| mcp_compat_warning(server, kind)) {
+                Some(reason) => node.with_children(vec![
+                    ProfileNode::new(SectionKind::Error, "Compatibility")
+                        .with_text(format!("{} by {}", reason, info.harness_id)),
+                ]),
+                None => node,
+            }
         })
         .collect();
 
@@ -227,11 +429,21 @@ fn build_resource_node(
             .with_text("(none)");
     }
 
-    let items_text = summary.items.join(", ");
-    let children: Vec<ProfileNode> = summary
+    let labels: Vec<String> = summary
         .items
         .iter()
-        .map(|item| ProfileNode::new(SectionKind::ResourceItem, "").with_text(item.clone()))
+        .map(|item| {
+            if summary.is_linked(item) {
+                format!("{} {}", item, LINKED_SUFFIX)
+            } else {
+                item.clone()
+            }
+        })
+        .collect();
+    let items_text = labels.join(", ");
+    let children: Vec<ProfileNode> = labels
+        .into_iter()
+        .map(|label| ProfileNode::new(SectionKind::ResourceItem, "").with_text(label))
         .collect();
 
     ProfileNode::new(SectionKind::ResourceGroup { exists: true }, label)
@@ -239,15 +451,272 @@ fn build_resource_node(
         .with_children(children)
 }
 
-/// Render profile nodes to flat CLI text output.
-pub fn nodes_to_text(nodes: &[ProfileNode]) -> String {
+/// A profile section that can be shown on its own with `profile show --resource`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProfileSection {
+    Skills,
+    Agents,
+    Commands,
+    Mcp,
+}
+
+impl ProfileSection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Skills => "Skills",
+            Self::Agents => "Agents",
+            Self::Commands => "Commands",
+            Self::Mcp => "MCP Servers",
+        }
+    }
+
+    /// Field name of this section in the serialized [`ProfileInfo`].
+    pub fn json_key(self) -> &'static str {
+        match self {
+            Self::Skills => "skills",
+            Self::Agents => "agents",
+            Self::Commands => "commands",
+            Self::Mcp => "mcp_servers",
+        }
+    }
+}
+
+/// Keep only the selected sections, plus the header and any errors/warnings.
+///
+/// An empty selection keeps every node.
+pub fn filter_nodes(nodes: Vec<ProfileNode>, sections: &[ProfileSection]) -> Vec<ProfileNode> {
+    if sections.is_empty() {
+        return nodes;
+    }
+    nodes
+        .into_iter()
+        .filter(|node| {
+            matches!(node.kind, SectionKind::Header | SectionKind::Error)
+                || sections.iter().any(|s| s.label() == node.label)
+        })
+        .collect()
+}
+
+/// Layout of [`nodes_to_text`] output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextStyle {
+    /// Grouped sections with counts and indented entries.
+    #[default]
+    Tree,
+    /// One `key: value` line per entry, for `grep`/`awk` pipelines.
+    Flat,
+}
+
+/// A one-line overview of a profile for text output, e.g.
+/// `opencode/work · model=gpt-4 · 3 MCP · 5 skills · 2 agents · active`.
+///
+/// Agents are left out for harnesses without them.
+pub fn profile_summary_line(info: &ProfileInfo) -> String {
+    let count = |n: usize, noun: &str| format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+    let mut parts = vec![format!("{}/{}", info.harness_id, info.name)];
+    if let Some(model) = info.model.as_deref() {
+        parts.push(format!("model={}", model));
+    }
+    parts.push(format!("{} MCP", info.mcp_servers.len()));
+    parts.push(count(info.skills.items.len(), "skill"));
+    if let Some(agents) = &info.agents {
+        parts.push(count(agents.items.len(), "agent"));
+    }
+    parts.push(if info.is_active { "active" } else { "inactive" }.to_string());
+    parts.join(" · ")
+}
+
+/// What changed from profile `from` to profile `to`, as display nodes: a
+/// `Field` (`old → new`) per changed setting, then a `ResourceGroup` per kind
+/// of resource whose entries differ, listing them as `+ added`, `- removed`,
+/// or `~ changed`. Empty when nothing differs.
+pub fn profile_changes_to_nodes(from: &ProfileInfo, to: &ProfileInfo) -> Vec<ProfileNode> {
+    let setting = |value: &FieldValue| match value {
+        FieldValue::Set(value) => value.clone(),
+        FieldValue::Missing => CONFIG_MISSING.to_string(),
+        FieldValue::Unset => "(not set)".to_string(),
+    };
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "(none)".to_string());
+    let tags = |tags: &[String]| {
+        if tags.is_empty() {
+            "(none)".to_string()
+        } else {
+            tags.join(", ")
+        }
+    };
+
+    let mut nodes = Vec::new();
+    for (label, old, new) in [
+        ("Theme", setting(&from.theme), setting(&to.theme)),
+        ("Model", setting(&from.model), setting(&to.model)),
+        (
+            "Description",
+            optional(&from.description),
+            optional(&to.description),
+        ),
+        ("Tags", tags(&from.tags), tags(&to.tags)),
+    ] {
+        if old != new {
+            nodes.push(
+                ProfileNode::new(SectionKind::Field, label).with_text(format!("{} → {}", old, new)),
+            );
+        }
+    }
+
+    let servers = |info: &ProfileInfo| -> Vec<(String, String)> {
+        info.mcp_servers
+            .iter()
+            .map(|s| {
+                (
+                    s.name.clone(),
+                    format!("{} {}", s.enabled, format_mcp_detail(s)),
+                )
+            })
+            .collect()
+    };
+    let items = |summary: Option<&ResourceSummary>| -> Vec<(String, String)> {
+        summary
+            .map(|s| s.items.iter().map(|i| (i.clone(), String::new())).collect())
+            .unwrap_or_default()
+    };
+    for (label, old, new) in [
+        ("MCP Servers", servers(from), servers(to)),
+        ("Skills", items(Some(&from.skills)), items(Some(&to.skills))),
+        (
+            "Commands",
+            items(Some(&from.commands)),
+            items(Some(&to.commands)),
+        ),
+        (
+            "Plugins",
+            items(from.plugins.as_ref()),
+            items(to.plugins.as_ref()),
+        ),
+        (
+            "Agents",
+            items(from.agents.as_ref()),
+            items(to.agents.as_ref()),
+        ),
+    ] {
+        let changes = entry_changes(&old, &new);
+        if !changes.is_empty() {
+            nodes.push(
+                ProfileNode::new(SectionKind::ResourceGroup { exists: true }, label)
+                    .with_text(format!("({} changed)", changes.len()))
+                    .with_children(changes),
+            );
+        }
+    }
+    nodes
+}
+
+/// `+`/`-`/`~` items for entries added, removed, or whose value changed
+/// between two `(name, value)` lists, in name order.
+fn entry_changes(old: &[(String, String)], new: &[(String, String)]) -> Vec<ProfileNode> {
+    let old: std::collections::BTreeMap<_, _> = old.iter().cloned().collect();
+    let new: std::collections::BTreeMap<_, _> = new.iter().cloned().collect();
+    let names: std::collections::BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let marker = match (old.get(name), new.get(name)) {
+                (None, Some(_)) => "+",
+                (Some(_), None) => "-",
+                (Some(a), Some(b)) if a != b => "~",
+                _ => return None,
+            };
+            Some(
+                ProfileNode::new(SectionKind::ResourceItem, "Change")
+                    .with_text(format!("{} {}", marker, name)),
+            )
+        })
+        .collect()
+}
+
+/// Render profile nodes to CLI text output.
+pub fn nodes_to_text(nodes: &[ProfileNode], style: TextStyle) -> String {
     let mut output = String::new();
     for node in nodes {
-        render_node_text(&mut output, node);
+        match style {
+            TextStyle::Tree => render_node_text(&mut output, node),
+            TextStyle::Flat => render_node_flat(&mut output, node),
+        }
     }
     output
 }
 
+/// Lowercase key for a label in flat output, e.g. `Created` -> `created`.
+fn flat_key(label: &str) -> String {
+    label.to_lowercase().replace(' ', "_")
+}
+
+fn render_node_flat(out: &mut String, node: &ProfileNode) {
+    use std::fmt::Write;
+
+    let text = node.text.as_deref().unwrap_or("");
+    match &node.kind {
+        SectionKind::Header => {
+            let _ = writeln!(out, "{}: {}", flat_key(node.label), text);
+            for child in &node.children {
+                render_node_flat(out, child);
+            }
+        }
+        SectionKind::Field | SectionKind::RulesFile { .. } => {
+            let _ = writeln!(out, "{}: {}", flat_key(node.label), text);
+        }
+        SectionKind::McpGroup if node.children.is_empty() => {
+            let _ = writeln!(out, "mcp: {}", text);
+        }
+        SectionKind::McpGroup => {
+            for child in &node.children {
+                render_node_flat(out, child);
+            }
+        }
+        SectionKind::McpServer { enabled } => {
+            let entry = text.strip_suffix(" (disabled)").unwrap_or(text);
+            let (name, detail) = entry.split_once(' ').unwrap_or((entry, ""));
+            let state = if *enabled { "enabled" } else { "disabled" };
+            let _ = writeln!(
+                out,
+                "mcp.{}: {}",
+                name,
+                [state, detail].join(" ").trim_end()
+            );
+            for note in &node.children {
+                let _ = writeln!(
+                    out,
+                    "mcp.{}.warning: {}",
+                    name,
+                    note.text.as_deref().unwrap_or("")
+                );
+            }
+        }
+        SectionKind::ResourceGroup { .. } => {
+            let value = if node.children.is_empty() {
+                text.to_string()
+            } else {
+                node.children
+                    .iter()
+                    .filter_map(|c| c.text.as_deref())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let _ = writeln!(out, "{}: {}", flat_key(node.label), value);
+        }
+        SectionKind::ResourceItem => {}
+        SectionKind::Error => {
+            let key = match node.label {
+                "Errors" => "error",
+                "Warnings" => "warning",
+                _ => return,
+            };
+            for child in &node.children {
+                let _ = writeln!(out, "{}: {}", key, child.text.as_deref().unwrap_or(""));
+            }
+        }
+    }
+}
+
 fn render_node_text(out: &mut String, node: &ProfileNode) {
     use std::fmt::Write;
 
@@ -309,6 +778,14 @@ fn render_node_text(out: &mut String, node: &ProfileNode) {
                 indicator,
                 node.text.as_deref().unwrap_or("")
             );
+            for note in &node.children {
+                let _ = writeln!(
+                    out,
+                    "      {} {}",
+                    WARNING_SYMBOL,
+                    note.text.as_deref().unwrap_or("")
+                );
+            }
         }
         SectionKind::ResourceGroup { exists: _ } => {
             let text = node.text.as_deref().unwrap_or("");
@@ -335,7 +812,7 @@ fn render_node_text(out: &mut String, node: &ProfileNode) {
             );
         }
         SectionKind::Error => {
-            if node.label == "Errors" {
+            if matches!(node.label, "Errors" | "Warnings") {
                 let _ = writeln!(out);
                 let _ = writeln!(out, "{}:", node.label);
                 for child in &node.children {
@@ -378,19 +855,14 @@ impl TreeBranch {
     }
 }
 
-/// Render profile nodes to TUI lines with styling.
-pub fn nodes_to_lines(nodes: &[ProfileNode]) -> Vec<Line<'static>> {
+/// Render profile nodes to TUI lines, styled with `theme`.
+pub fn nodes_to_lines(nodes: &[ProfileNode], theme: &Theme) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
     let (name, is_active) = extract_header_info(nodes);
     let active_marker = if is_active { "● " } else { "  " };
     lines.push(Line::from(vec![
-        Span::styled(
-            format!("{}{}", active_marker, name),
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
+        Span::styled(format!("{}{}", active_marker, name), theme.profile_active()),
         Span::styled(
             " ─────────────────────────",
             Style::default().fg(Color::Gray),
@@ -417,13 +889,18 @@ pub fn nodes_to_lines(nodes: &[ProfileNode]) -> Vec<Line<'static>> {
     let total = display_nodes.len();
     for (idx, node) in display_nodes.iter().enumerate() {
         let tree = TreeBranch::for_index(idx, total);
-        render_node_lines(&mut lines, node, &tree);
+        render_node_lines(&mut lines, node, &tree, theme);
     }
 
     lines
 }
 
-fn render_node_lines(lines: &mut Vec<Line<'static>>, node: &ProfileNode, tree: &TreeBranch) {
+fn render_node_lines(
+    lines: &mut Vec<Line<'static>>,
+    node: &ProfileNode,
+    tree: &TreeBranch,
+    theme: &Theme,
+) {
     match &node.kind {
         SectionKind::Field => {
             lines.push(Line::styled(
@@ -451,7 +928,7 @@ fn render_node_lines(lines: &mut Vec<Line<'static>>, node: &ProfileNode, tree: &
             let server_count = node.children.len();
             for (i, child) in node.children.iter().enumerate() {
                 let sub_tree = TreeBranch::for_index(i, server_count);
-                render_mcp_server_line(lines, child, tree.continuation, &sub_tree);
+                render_mcp_server_line(lines, child, tree.continuation, &sub_tree, theme);
             }
         }
         SectionKind::ResourceGroup { exists: _ } => {
@@ -478,34 +955,38 @@ fn render_node_lines(lines: &mut Vec<Line<'static>>, node: &ProfileNode, tree: &
                 ));
             }
         }
-        SectionKind::RulesFile { exists } => {
-            if *exists {
-                lines.push(Line::styled(
-                    format!(
-                        "  {} Rules: {}",
-                        tree.branch,
-                        node.text.as_deref().unwrap_or("")
-                    ),
-                    Style::default().fg(Color::Gray),
-                ));
-            }
+        SectionKind::RulesFile { exists } if *exists => {
+            lines.push(Line::styled(
+                format!(
+                    "  {} Rules: {}",
+                    tree.branch,
+                    node.text.as_deref().unwrap_or("")
+                ),
+                Style::default().fg(Color::Gray),
+            ));
         }
         SectionKind::Error => {
-            if node.label == "Errors" {
+            if matches!(node.label, "Errors" | "Warnings") {
                 for child in &node.children {
                     lines.push(Line::styled(
                         format!(
-                            "  {} ⚠ {}",
+                            "  {} {} {}",
                             tree.branch,
+                            WARNING_SYMBOL,
                             child.text.as_deref().unwrap_or("")
                         ),
-                        Style::default().fg(Color::Yellow),
+                        theme.warning(),
                     ));
                 }
             } else {
                 lines.push(Line::styled(
-                    format!("  {} ⚠ {}", tree.branch, node.text.as_deref().unwrap_or("")),
-                    Style::default().fg(Color::Yellow),
+                    format!(
+                        "  {} {} {}",
+                        tree.branch,
+                        WARNING_SYMBOL,
+                        node.text.as_deref().unwrap_or("")
+                    ),
+                    theme.warning(),
                 ));
             }
         }
@@ -518,12 +999,13 @@ fn render_mcp_server_line(
     node: &ProfileNode,
     cont: &'static str,
     sub_tree: &TreeBranch,
+    theme: &Theme,
 ) {
     if let SectionKind::McpServer { enabled } = &node.kind {
-        let (marker, color) = if *enabled {
-            ("✓", Color::Green)
+        let marker = if *enabled {
+            MCP_ENABLED_SYMBOL
         } else {
-            ("✗", Color::Gray)
+            MCP_DISABLED_SYMBOL
         };
 
         let full_text = node.text.as_deref().unwrap_or("");
@@ -534,9 +1016,21 @@ fn render_mcp_server_line(
                 format!("  {} {} ", cont, sub_tree.branch),
                 Style::default().fg(Color::Gray),
             ),
-            Span::styled(format!("{} {}", marker, name), Style::default().fg(color)),
+            Span::styled(format!("{} {}", marker, name), theme.mcp_server(*enabled)),
             Span::styled(format!(" {}", detail), Style::default().fg(Color::DarkGray)),
         ]));
+        for note in &node.children {
+            lines.push(Line::styled(
+                format!(
+                    "  {} {} {} {}",
+                    cont,
+                    sub_tree.continuation,
+                    WARNING_SYMBOL,
+                    note.text.as_deref().unwrap_or("")
+                ),
+                theme.warning(),
+            ));
+        }
     }
 }
 
@@ -554,6 +1048,7 @@ mod tests {
             command: Some("npx".to_string()),
             args: Some(vec!["@server/mcp".to_string(), "--flag".to_string()]),
             url: None,
+            env_keys: Vec::new(),
         };
         assert_eq!(
             format_mcp_detail(&server),
@@ -570,6 +1065,7 @@ mod tests {
             command: Some("server-bin".to_string()),
             args: None,
             url: None,
+            env_keys: Vec::new(),
         };
         assert_eq!(format_mcp_detail(&server), "(stdio): server-bin");
     }
@@ -583,10 +1079,47 @@ mod tests {
             command: None,
             args: None,
             url: Some("http://localhost:3000".to_string()),
+            env_keys: Vec::new(),
         };
         assert_eq!(format_mcp_detail(&server), "(sse): http://localhost:3000");
     }
 
+    #[test]
+    fn test_format_mcp_detail_expands_home_for_display_only() {
+        let home = std::env::home_dir().expect("home dir");
+        let server = McpServerInfo {
+            name: "local".to_string(),
+            enabled: true,
+            server_type: Some("stdio".to_string()),
+            command: Some("~/x".to_string()),
+            args: None,
+            url: None,
+            env_keys: Vec::new(),
+        };
+
+        let detail = format_mcp_detail(&server);
+        assert_eq!(
+            detail,
+            format!("(stdio): {}/x [expanded]", home.to_string_lossy())
+        );
+        assert_eq!(server.command.as_deref(), Some("~/x"));
+        assert_eq!(format_mcp_detail_with(&server, false), "(stdio): ~/x");
+    }
+
+    #[test]
+    fn test_expand_for_display_env_vars() {
+        let path = std::env::var("PATH").expect("PATH set");
+        assert_eq!(expand_for_display("${PATH}:x"), format!("{path}:x"));
+        assert_eq!(expand_for_display("$PATH/x"), format!("{path}/x"));
+        assert_eq!(
+            expand_for_display("${BRIDLE_SURELY_UNSET_VAR}/x"),
+            "${BRIDLE_SURELY_UNSET_VAR}/x"
+        );
+        assert_eq!(expand_for_display("${PATH"), "${PATH");
+        assert_eq!(expand_for_display("cost $5"), "cost $5");
+        assert_eq!(expand_for_display("a~b"), "a~b");
+    }
+
     #[test]
     fn test_profile_to_nodes_basic() {
         let info = ProfileInfo {
@@ -600,9 +1133,18 @@ mod tests {
             plugins: None,
             agents: None,
             rules_file: None,
-            theme: Some("dark".to_string()),
-            model: Some("gpt-4".to_string()),
+            env_file_keys: vec![],
+            theme: FieldValue::Set("dark".to_string()),
+            model: FieldValue::Set("gpt-4".to_string()),
+            effective_model: None,
             extraction_errors: vec![],
+            extraction_warnings: vec![],
+            description: None,
+            tags: vec![],
+            created_at: None,
+            captured_with: None,
+            locked: false,
+            base: None,
         };
 
         let nodes = profile_to_nodes(&info);
@@ -612,6 +1154,70 @@ mod tests {
         assert_eq!(nodes[0].text.as_deref(), Some("test-profile"));
     }
 
+    #[test]
+    fn model_node_labels_env_override() {
+        let info = ProfileInfo {
+            harness_id: "goose".to_string(),
+            model: FieldValue::Set("gpt-4o".to_string()),
+            effective_model: Some(crate::config::EffectiveModel {
+                model: "claude-sonnet-4".to_string(),
+                source: crate::config::ModelSource::Env,
+                env_var: Some("GOOSE_MODEL".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let nodes = profile_to_nodes(&info);
+        let model = nodes.iter().find(|n| n.label == "Model").unwrap();
+        assert_eq!(model.text.as_deref(), Some("claude-sonnet-4 (from env)"));
+    }
+
+    #[test]
+    fn incompatible_mcp_server_gets_warning_node() {
+        let server = |name: &str, server_type: &str| McpServerInfo {
+            name: name.to_string(),
+            enabled: true,
+            server_type: Some(server_type.to_string()),
+            url: Some("https://example.com/mcp".to_string()),
+            ..Default::default()
+        };
+        let info = ProfileInfo {
+            harness_id: "amp-code".to_string(),
+            mcp_servers: vec![
+                McpServerInfo {
+                    command: Some("npx".to_string()),
+                    url: None,
+                    ..server("local", "stdio")
+                },
+                server("remote", "http"),
+            ],
+            ..Default::default()
+        };
+
+        let nodes = profile_to_nodes(&info);
+        let mcp = nodes.iter().find(|n| n.label == "MCP Servers").unwrap();
+        assert!(mcp.children[0].children.is_empty());
+        let warning = &mcp.children[1].children[0];
+        assert_eq!(warning.kind, SectionKind::Error);
+        assert_eq!(
+            warning.text.as_deref(),
+            Some("HTTP transport not supported by amp-code")
+        );
+
+        let text = nodes_to_text(&nodes, TextStyle::Tree);
+        assert!(text.contains("\u{26a0} HTTP transport not supported by amp-code"));
+        let lines = nodes_to_lines(&nodes, &Theme::default());
+        let note = lines
+            .iter()
+            .find(|l| l.to_string().contains("HTTP transport not supported"))
+            .unwrap();
+        assert_eq!(note.style.fg, Some(Color::Yellow));
+        assert!(
+            nodes_to_text(&nodes, TextStyle::Flat)
+                .contains("mcp.remote.warning: HTTP transport not supported by amp-code")
+        );
+    }
+
     #[test]
     fn test_profile_to_nodes_with_errors() {
         let info = ProfileInfo {
@@ -625,9 +1231,18 @@ mod tests {
             plugins: None,
             agents: None,
             rules_file: None,
-            theme: None,
-            model: None,
+            env_file_keys: vec![],
+            theme: FieldValue::Unset,
+            model: FieldValue::Unset,
+            effective_model: None,
             extraction_errors: vec!["Error 1".to_string(), "Error 2".to_string()],
+            extraction_warnings: vec!["Warning 1".to_string()],
+            description: None,
+            tags: vec![],
+            created_at: None,
+            captured_with: None,
+            locked: false,
+            base: None,
         };
 
         let nodes = profile_to_nodes(&info);
@@ -635,6 +1250,140 @@ mod tests {
 
         assert!(errors_node.is_some());
         assert_eq!(errors_node.unwrap().children.len(), 2);
+
+        let warnings_node = nodes.iter().find(|n| n.label == "Warnings");
+        assert_eq!(warnings_node.map(|n| n.children.len()), Some(1));
+    }
+
+    #[test]
+    fn missing_config_is_shown_apart_from_unset() {
+        let field_text = |info: &ProfileInfo, label: &str| {
+            profile_to_nodes(info)
+                .into_iter()
+                .find(|n| n.label == label)
+                .and_then(|n| n.text)
+        };
+        let mut info = ProfileInfo {
+            harness_id: "opencode".to_string(),
+            theme: FieldValue::Missing,
+            model: FieldValue::Missing,
+            ..Default::default()
+        };
+        assert_eq!(field_text(&info, "Theme").as_deref(), Some(CONFIG_MISSING));
+        assert_eq!(field_text(&info, "Model").as_deref(), Some(CONFIG_MISSING));
+
+        info.theme = FieldValue::Unset;
+        info.model = FieldValue::Unset;
+        assert_eq!(field_text(&info, "Theme").as_deref(), Some("(not set)"));
+        assert_eq!(field_text(&info, "Model").as_deref(), Some("(not set)"));
+    }
+
+    #[test]
+    fn summary_line_counts_resources_and_active_state() {
+        let items = |names: &[&str]| ResourceSummary {
+            items: names.iter().map(|n| n.to_string()).collect(),
+            directory_exists: true,
+            linked: Vec::new(),
+        };
+        let mut info = ProfileInfo {
+            name: "work".to_string(),
+            harness_id: "opencode".to_string(),
+            is_active: true,
+            model: FieldValue::Set("gpt-4".to_string()),
+            mcp_servers: vec![McpServerInfo::default(); 3],
+            skills: items(&["a", "b", "c", "d", "e"]),
+            agents: Some(items(&["planner", "reviewer"])),
+            ..Default::default()
+        };
+        assert_eq!(
+            profile_summary_line(&info),
+            "opencode/work · model=gpt-4 · 3 MCP · 5 skills · 2 agents · active"
+        );
+
+        info.is_active = false;
+        info.model = FieldValue::Unset;
+        info.skills = items(&["a"]);
+        info.agents = None;
+        assert_eq!(
+            profile_summary_line(&info),
+            "opencode/work · 3 MCP · 1 skill · inactive"
+        );
+    }
+
+    #[test]
+    fn filter_nodes_keeps_only_selected_sections() {
+        let info = ProfileInfo {
+            name: "work".to_string(),
+            mcp_servers: vec![McpServerInfo {
+                name: "fs".to_string(),
+                ..Default::default()
+            }],
+            skills: ResourceSummary {
+                items: vec!["review".to_string()],
+                directory_exists: true,
+                linked: Vec::new(),
+            },
+            ..Default::default()
+        };
+
+        let nodes = filter_nodes(profile_to_nodes(&info), &[ProfileSection::Mcp]);
+        let text = nodes_to_text(&nodes, TextStyle::Tree);
+
+        assert_eq!(nodes[0].kind, SectionKind::Header);
+        assert!(text.contains("MCP Servers"));
+        assert!(text.contains("fs"));
+        assert!(!text.contains("Skills"));
+        assert!(!text.contains("Commands"));
+        assert!(!text.contains("Theme"));
+    }
+
+    #[test]
+    fn flat_text_emits_one_key_value_per_line() {
+        let info = ProfileInfo {
+            name: "work".to_string(),
+            harness_id: "opencode".to_string(),
+            path: PathBuf::from("/profiles/opencode/work"),
+            mcp_servers: vec![
+                McpServerInfo {
+                    name: "fs".to_string(),
+                    enabled: true,
+                    server_type: Some("stdio".to_string()),
+                    command: Some("npx".to_string()),
+                    ..Default::default()
+                },
+                McpServerInfo {
+                    name: "search".to_string(),
+                    ..Default::default()
+                },
+            ],
+            skills: ResourceSummary {
+                items: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                directory_exists: true,
+                linked: Vec::new(),
+            },
+            ..Default::default()
+        };
+
+        let text = nodes_to_text(
+            &filter_nodes(
+                profile_to_nodes(&info),
+                &[ProfileSection::Mcp, ProfileSection::Skills],
+            ),
+            TextStyle::Flat,
+        );
+
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            [
+                "profile: work",
+                "harness: opencode",
+                "status: Inactive",
+                "path: /profiles/opencode/work",
+                "mcp.fs: enabled (stdio): npx",
+                "mcp.search: disabled",
+                "skills: a, b, c",
+            ]
+        );
     }
 
     #[test]
@@ -650,7 +1399,7 @@ mod tests {
             ProfileNode::new(SectionKind::Field, "Theme").with_text("dark".to_string()),
         ];
 
-        let output = nodes_to_text(&nodes);
+        let output = nodes_to_text(&nodes, TextStyle::Tree);
 
         assert!(output.contains("Profile: test"));
         assert!(output.contains("Harness: opencode"));
@@ -684,7 +1433,7 @@ mod tests {
             ]),
         ];
 
-        let lines = nodes_to_lines(&nodes);
+        let lines = nodes_to_lines(&nodes, &Theme::default());
 
         assert!(!lines.is_empty());
 
@@ -716,7 +1465,7 @@ mod tests {
             ]),
         ];
 
-        let lines = nodes_to_lines(&nodes);
+        let lines = nodes_to_lines(&nodes, &Theme::default());
 
         let disabled_line = lines
             .iter()
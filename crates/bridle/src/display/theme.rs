@@ -0,0 +1,135 @@
+//! TUI colors by semantic role, configurable in `[tui.theme]`.
+
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::config::ThemeConfig;
+
+/// The colors the TUI draws each role in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Border of the focused pane.
+    pub border_active: Color,
+    /// Marker and name of the active profile.
+    pub profile_active: Color,
+    pub mcp_enabled: Color,
+    pub mcp_disabled: Color,
+    /// Extraction warnings and status messages.
+    pub warning: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border_active: Color::Cyan,
+            profile_active: Color::Green,
+            mcp_enabled: Color::Green,
+            mcp_disabled: Color::Gray,
+            warning: Color::Yellow,
+        }
+    }
+}
+
+impl Theme {
+    /// The defaults with the roles set in `config` applied. A color name
+    /// ratatui can't parse keeps the default, with a warning.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let defaults = Self::default();
+        let pick = |role: &str, name: &Option<String>, default: Color| match name {
+            None => default,
+            Some(name) => Color::from_str(name).unwrap_or_else(|_| {
+                crate::diagnostics::warn(format!(
+                    "Warning: Unknown color '{}' for tui.theme.{}",
+                    name, role
+                ));
+                default
+            }),
+        };
+        Self {
+            border_active: pick(
+                "border_active",
+                &config.border_active,
+                defaults.border_active,
+            ),
+            profile_active: pick(
+                "profile_active",
+                &config.profile_active,
+                defaults.profile_active,
+            ),
+            mcp_enabled: pick("mcp_enabled", &config.mcp_enabled, defaults.mcp_enabled),
+            mcp_disabled: pick("mcp_disabled", &config.mcp_disabled, defaults.mcp_disabled),
+            warning: pick("warning", &config.warning, defaults.warning),
+        }
+    }
+
+    /// Border of a pane, highlighted while it has focus.
+    pub fn border(&self, focused: bool) -> Style {
+        if focused {
+            Style::default().fg(self.border_active)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    }
+
+    pub fn profile_active(&self) -> Style {
+        Style::default()
+            .fg(self.profile_active)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn mcp_server(&self, enabled: bool) -> Style {
+        let color = if enabled {
+            self.mcp_enabled
+        } else {
+            self.mcp_disabled
+        };
+        Style::default().fg(color)
+    }
+
+    pub fn warning(&self) -> Style {
+        Style::default().fg(self.warning)
+    }
+
+    // Styles that don't follow the configured theme.
+    pub fn harness_installed() -> Style {
+        Style::default()
+    }
+
+    pub fn harness_not_installed() -> Style {
+        Style::default().fg(Color::DarkGray)
+    }
+
+    pub fn text_muted() -> Style {
+        Style::default().add_modifier(Modifier::DIM)
+    }
+
+    pub fn tab_selected() -> Style {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BridleConfig;
+
+    #[test]
+    fn configured_profile_active_color_styles_the_active_profile() {
+        let config: BridleConfig =
+            toml::from_str("[tui.theme]\nprofile_active = \"blue\"\n").unwrap();
+
+        let theme = Theme::from_config(&config.tui.theme);
+
+        assert_eq!(theme.profile_active().fg, Some(Color::Blue));
+        assert_eq!(theme.border_active, Color::Cyan);
+        let nodes = crate::display::profile_to_nodes(&crate::config::ProfileInfo {
+            is_active: true,
+            ..Default::default()
+        });
+        let header = &crate::display::nodes_to_lines(&nodes, &theme)[0];
+        assert_eq!(header.spans[0].style.fg, Some(Color::Blue));
+    }
+}
@@ -26,6 +26,25 @@ pub enum Error {
     #[error("profile not found: {0}")]
     ProfileNotFound(String),
 
+    /// Profile has no MCP server with the given name.
+    #[error("MCP server not found: {0}")]
+    McpServerNotFound(String),
+
+    /// A profile prefix matched more than one profile.
+    #[error("'{prefix}' matches several profiles: {}", matches.join(", "))]
+    AmbiguousProfile {
+        /// Prefix as typed.
+        prefix: String,
+        /// Every profile it matched, sorted.
+        matches: Vec<String>,
+    },
+
+    /// The profile is locked against switching away from it and deletion.
+    #[error(
+        "profile is locked: {0}\nUnlock it with `bridle profile set-meta <harness> {0} --locked false`, or switch away with --force"
+    )]
+    ProfileLocked(String),
+
     /// Profile with given name already exists.
     #[error("profile already exists: {0}")]
     ProfileExists(String),
@@ -52,6 +71,31 @@ pub enum Error {
     #[error("unknown setting: {0}\nValid options: editor, marker_files, default_harness")]
     UnknownSetting(String),
 
+    /// Profile was read, but some of its config could not be extracted.
+    #[error("profile {0} has extraction errors")]
+    ExtractionErrors(String),
+
+    /// `profile verify` found at least one error-severity issue.
+    #[error("profile {0} failed verification")]
+    VerificationFailed(String),
+
+    /// `bridle doctor` found at least one failing check.
+    #[error("doctor found {0} failing check(s)")]
+    DoctorFailed(usize),
+
+    /// Capturing the live config would exceed `profile.max_capture_bytes`.
+    #[error("capture exceeds profile.max_capture_bytes ({limit} bytes) at {}", path.display())]
+    CaptureTooLarge {
+        /// Entry that pushed the capture over the limit.
+        path: std::path::PathBuf,
+        /// Configured limit in bytes.
+        limit: u64,
+    },
+
+    /// `profile rollback` found no backup for the harness.
+    #[error("no backup to roll back to for {0}; backups are taken by `bridle profile switch`")]
+    NoBackup(String),
+
     /// Invalid configuration value.
     #[error("invalid value: {0}")]
     InvalidValue(String),
@@ -76,3 +120,30 @@ pub enum Error {
     #[error(transparent)]
     Yaml(#[from] serde_yaml::Error),
 }
+
+/// Process exit codes reported by the `bridle` binary.
+pub mod exit_code {
+    /// Any failure without a more specific code.
+    pub const GENERIC: u8 = 1;
+    /// The requested profile does not exist.
+    pub const PROFILE_NOT_FOUND: u8 = 2;
+    /// The profile was shown, but parts of its config failed to extract.
+    pub const EXTRACTION_ERRORS: u8 = 3;
+    /// The harness has no active profile.
+    pub const NO_ACTIVE_PROFILE: u8 = 4;
+    /// The requested MCP server is not in the profile.
+    pub const MCP_SERVER_NOT_FOUND: u8 = 5;
+}
+
+impl Error {
+    /// Exit code `main` reports when this error ends the process.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::ProfileNotFound(_) => exit_code::PROFILE_NOT_FOUND,
+            Error::ExtractionErrors(_) => exit_code::EXTRACTION_ERRORS,
+            Error::NoActiveProfile => exit_code::NO_ACTIVE_PROFILE,
+            Error::McpServerNotFound(_) => exit_code::MCP_SERVER_NOT_FOUND,
+            _ => exit_code::GENERIC,
+        }
+    }
+}
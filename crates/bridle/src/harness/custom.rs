@@ -0,0 +1,323 @@
+//! User-defined harnesses described by TOML files.
+//!
+//! Descriptors live in `<bridle config dir>/harnesses/*.toml`:
+//!
+//! ```toml
+//! id = "acme"
+//! config_dir = "~/.acme"
+//! mcp_path = "mcp.json"    # relative to config_dir
+//! mcp_key = "mcpServers"   # table holding the servers
+//! format = "jsonc"         # json, jsonc, yaml or toml
+//! skills_dir = "skills"
+//! agent_dir = "subagents"  # alias of agents_dir
+//! agents_pattern = "*.md"  # files in agents_dir that are agents
+//! binary = "acme"
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use harness_locate::InstallationStatus;
+use serde::{Deserialize, Serialize};
+
+use super::HarnessConfig;
+use crate::config::jsonc::strip_jsonc_comments;
+use crate::error::{Error, Result};
+
+/// Ids of the built-in harnesses, which a descriptor may not reuse.
+const BUILTIN_IDS: &[&str] = &[
+    "claude-code",
+    "opencode",
+    "goose",
+    "amp-code",
+    "copilot-cli",
+    "crush",
+    "droid",
+];
+
+/// Encoding of a custom harness's MCP config file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Jsonc,
+    Yaml,
+    Toml,
+}
+
+/// Contents of a harness descriptor file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessDescriptor {
+    /// Harness id; also the profile directory name.
+    pub id: String,
+    /// Live config directory. A leading `~/` expands to the home directory.
+    pub config_dir: PathBuf,
+    /// MCP config file, relative to `config_dir` unless absolute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_path: Option<PathBuf>,
+    /// Key of the table holding MCP servers.
+    #[serde(default = "default_mcp_key")]
+    pub mcp_key: String,
+    /// Encoding of the MCP config file.
+    #[serde(default)]
+    pub format: ConfigFormat,
+    /// Skills directory name inside `config_dir`.
+    #[serde(default, alias = "skill_dir", skip_serializing_if = "Option::is_none")]
+    pub skills_dir: Option<String>,
+    /// File marking a skill directory inside `skills_dir`.
+    #[serde(default = "default_skill_file")]
+    pub skill_file: String,
+    /// Agents directory name inside `config_dir`.
+    #[serde(default, alias = "agent_dir", skip_serializing_if = "Option::is_none")]
+    pub agents_dir: Option<String>,
+    /// Glob for agent files inside `agents_dir`.
+    #[serde(default = "default_markdown_pattern")]
+    pub agents_pattern: String,
+    /// Commands directory name inside `config_dir`.
+    #[serde(
+        default,
+        alias = "command_dir",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub commands_dir: Option<String>,
+    /// Glob for command files inside `commands_dir`.
+    #[serde(default = "default_markdown_pattern")]
+    pub commands_pattern: String,
+    /// Executable looked up on `PATH` for installation status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+}
+
+fn default_mcp_key() -> String {
+    "mcpServers".to_string()
+}
+
+fn default_skill_file() -> String {
+    "SKILL.md".to_string()
+}
+
+fn default_markdown_pattern() -> String {
+    "*.md".to_string()
+}
+
+/// A harness defined by a [`HarnessDescriptor`] rather than built into bridle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomHarness {
+    descriptor: HarnessDescriptor,
+}
+
+impl CustomHarness {
+    /// Wraps a descriptor after checking its id.
+    ///
+    /// # Errors
+    /// Returns [`Error::Config`] if the id is empty, not a plain directory
+    /// name, or shadows a built-in harness.
+    pub fn from_descriptor(descriptor: HarnessDescriptor) -> Result<Self> {
+        let id = descriptor.id.as_str();
+        if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+            return Err(Error::Config(format!("invalid custom harness id '{}'", id)));
+        }
+        if BUILTIN_IDS.contains(&id) {
+            return Err(Error::Config(format!(
+                "custom harness id '{}' is reserved for a built-in harness",
+                id
+            )));
+        }
+        Ok(Self { descriptor })
+    }
+
+    /// Parses a descriptor from TOML.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        Self::from_descriptor(toml::from_str(content)?)
+    }
+
+    /// Loads every `*.toml` descriptor in `dir`, sorted by id.
+    ///
+    /// A missing directory yields no harnesses.
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>> {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut harnesses = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)?;
+            let harness = Self::from_toml(&content)
+                .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+            harnesses.push(harness);
+        }
+        harnesses.sort_by(|a, b| a.descriptor.id.cmp(&b.descriptor.id));
+        Ok(harnesses)
+    }
+
+    /// Directory holding the user's harness descriptors.
+    pub fn descriptors_dir() -> Result<PathBuf> {
+        crate::config::BridleConfig::config_dir().map(|d| d.join("harnesses"))
+    }
+
+    /// Finds the installed descriptor with id `id`.
+    pub fn find(id: &str) -> Result<Option<Self>> {
+        Ok(Self::load_dir(&Self::descriptors_dir()?)?
+            .into_iter()
+            .find(|h| h.descriptor.id == id))
+    }
+
+    /// The descriptor this harness was built from.
+    pub fn descriptor(&self) -> &HarnessDescriptor {
+        &self.descriptor
+    }
+}
+
+fn expand_home(path: &Path) -> Result<PathBuf> {
+    match path.strip_prefix("~") {
+        Ok(rest) => Ok(harness_locate::platform::home_dir()?.join(rest)),
+        Err(_) => Ok(path.to_path_buf()),
+    }
+}
+
+impl HarnessConfig for CustomHarness {
+    fn id(&self) -> &str {
+        &self.descriptor.id
+    }
+
+    fn as_custom(&self) -> Option<&CustomHarness> {
+        Some(self)
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        expand_home(&self.descriptor.config_dir)
+    }
+
+    fn installation_status(&self) -> Result<InstallationStatus> {
+        let config_path = self.config_dir()?;
+        let binary_path = match &self.descriptor.binary {
+            Some(binary) => harness_locate::find_binary(binary)?,
+            None => None,
+        };
+        Ok(match (binary_path, config_path.is_dir()) {
+            (Some(binary_path), true) => InstallationStatus::FullyInstalled {
+                binary_path,
+                config_path,
+            },
+            (Some(binary_path), false) => InstallationStatus::BinaryOnly { binary_path },
+            (None, true) => InstallationStatus::ConfigOnly { config_path },
+            (None, false) => InstallationStatus::NotInstalled,
+        })
+    }
+
+    fn mcp_filename(&self) -> Option<String> {
+        self.descriptor
+            .mcp_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+    }
+
+    fn mcp_config_path(&self) -> Option<PathBuf> {
+        let path = self.descriptor.mcp_path.as_ref()?;
+        let path = expand_home(path).ok()?;
+        if path.is_absolute() {
+            Some(path)
+        } else {
+            self.config_dir().ok().map(|dir| dir.join(path))
+        }
+    }
+
+    fn parse_mcp_servers(&self, content: &str, _filename: &str) -> Result<Vec<(String, bool)>> {
+        let parsed: serde_json::Value = match self.descriptor.format {
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Jsonc => serde_json::from_str(&strip_jsonc_comments(content))?,
+            ConfigFormat::Yaml => {
+                serde_json::to_value(serde_yaml::from_str::<serde_yaml::Value>(content)?)?
+            }
+            ConfigFormat::Toml => serde_json::to_value(toml::from_str::<toml::Value>(content)?)?,
+        };
+        let Some(servers) = parsed
+            .get(&self.descriptor.mcp_key)
+            .and_then(|v| v.as_object())
+        else {
+            return Ok(Vec::new());
+        };
+        let mut result: Vec<(String, bool)> = servers
+            .iter()
+            .map(|(name, server)| {
+                let enabled = server.get("enabled").and_then(|v| v.as_bool());
+                let disabled = server.get("disabled").and_then(|v| v.as_bool());
+                (name.clone(), enabled.unwrap_or(!disabled.unwrap_or(false)))
+            })
+            .collect();
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn descriptor_defaults_and_paths() {
+        let harness = CustomHarness::from_toml(
+            "id = \"acme\"\nconfig_dir = \"/opt/acme\"\nmcp_path = \"servers.json\"\n",
+        )
+        .unwrap();
+        assert_eq!(harness.id(), "acme");
+        assert_eq!(harness.descriptor().format, ConfigFormat::Json);
+        assert_eq!(harness.mcp_filename().as_deref(), Some("servers.json"));
+        assert_eq!(
+            harness.mcp_config_path(),
+            Some(PathBuf::from("/opt/acme/servers.json"))
+        );
+    }
+
+    #[test]
+    fn builtin_ids_are_rejected() {
+        let err = CustomHarness::from_toml("id = \"opencode\"\nconfig_dir = \"/x\"\n").unwrap_err();
+        assert!(err.to_string().contains("reserved"), "{err}");
+    }
+
+    #[test]
+    fn parses_mcp_servers_using_format_hint() {
+        let harness = CustomHarness::from_toml(
+            "id = \"acme\"\nconfig_dir = \"/x\"\nformat = \"toml\"\nmcp_key = \"servers\"\n",
+        )
+        .unwrap();
+        let servers = harness
+            .parse_mcp_servers(
+                "[servers.search]\ncommand = \"s\"\n\n[servers.old]\ndisabled = true\n",
+                "acme.toml",
+            )
+            .unwrap();
+        assert_eq!(
+            servers,
+            vec![("old".to_string(), false), ("search".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn load_dir_reads_toml_files_only() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("b.toml"),
+            "id = \"beta\"\nconfig_dir = \"/b\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("a.toml"),
+            "id = \"alpha\"\nconfig_dir = \"/a\"\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("notes.md"), "ignored").unwrap();
+
+        let ids: Vec<String> = CustomHarness::load_dir(temp.path())
+            .unwrap()
+            .iter()
+            .map(|h| h.id().to_string())
+            .collect();
+        assert_eq!(ids, ["alpha", "beta"]);
+    }
+}
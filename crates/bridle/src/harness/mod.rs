@@ -5,17 +5,25 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod custom;
 mod display;
 mod install_instructions;
+mod scoped;
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
-use harness_locate::{InstallationStatus, McpServer, Scope};
+use harness_locate::{Harness, HarnessKind, InstallationStatus, McpServer, Scope};
 
 use crate::error::Result;
 
+pub use custom::{ConfigFormat, CustomHarness, HarnessDescriptor};
 pub use display::DisplayInfo;
 pub use install_instructions::{get_empty_state_message, get_install_instructions};
+pub use scoped::ScopedHarness;
 
 /// Configuration interface for AI coding assistant harnesses.
 ///
@@ -25,12 +33,43 @@ pub trait HarnessConfig {
     /// Returns the harness identifier (e.g., "opencode", "claude-code", "goose").
     fn id(&self) -> &str;
 
+    /// Key under which the active profile is recorded in bridle's config.
+    ///
+    /// Defaults to [`id`](Self::id); project-scoped harnesses track their
+    /// active profile separately.
+    fn active_key(&self) -> String {
+        self.id().to_string()
+    }
+
+    /// Scope the live config and resource directories are resolved for.
+    fn scope(&self) -> Scope {
+        Scope::Global
+    }
+
+    /// Built-in harness whose resource directories this one uses, if any.
+    fn resource_harness(&self) -> Option<&Harness> {
+        None
+    }
+
+    /// The descriptor-defined harness this is, if it is one.
+    fn as_custom(&self) -> Option<&CustomHarness> {
+        None
+    }
+
     /// Returns the path to the harness's configuration directory.
     fn config_dir(&self) -> Result<PathBuf>;
 
     /// Checks whether the harness binary and config are installed.
     fn installation_status(&self) -> Result<InstallationStatus>;
 
+    /// Version the harness binary prints for `--version`, or `None` if it
+    /// isn't installed, fails to run, prints no version number, or takes
+    /// longer than [`VERSION_TIMEOUT`].
+    fn version(&self) -> Option<String> {
+        let status = self.installation_status().ok()?;
+        binary_version(status.binary_path()?, VERSION_TIMEOUT)
+    }
+
     /// Returns the MCP config filename if the harness supports MCP.
     fn mcp_filename(&self) -> Option<String>;
 
@@ -43,6 +82,90 @@ pub trait HarnessConfig {
     fn parse_mcp_servers(&self, content: &str, filename: &str) -> Result<Vec<(String, bool)>>;
 }
 
+/// How long [`HarnessConfig::version`] waits for `--version` before giving
+/// up, so a hanging binary can't hold up a capture.
+pub const VERSION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Runs `binary --version` and parses what it prints, killing it once
+/// `timeout` has passed.
+fn binary_version(binary: &Path, timeout: Duration) -> Option<String> {
+    let mut child = Command::new(binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = Instant::now() + timeout;
+    while child.try_wait().ok()?.is_none() {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let mut stdout = String::new();
+    child.stdout.take()?.read_to_string(&mut stdout).ok()?;
+    parse_version(&stdout)
+}
+
+/// The first version number in `--version` output, such as `1.0.3` from
+/// `1.0.3 (Claude Code)` or `0.5.1` from `opencode v0.5.1`.
+pub fn parse_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|word| {
+            word.trim_start_matches('v')
+                .trim_end_matches([',', ')', ';'])
+        })
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+        .map(String::from)
+}
+
+/// Harnesses whose live config dirs resolve to the same directory, as
+/// `(dir, harness ids)` sorted by dir. Switching a profile of one of them
+/// would overwrite the other's config.
+pub fn shared_config_dirs(harnesses: &[&dyn HarnessConfig]) -> Vec<(PathBuf, Vec<String>)> {
+    let mut by_dir: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    for harness in harnesses {
+        let Ok(dir) = harness.config_dir() else {
+            continue;
+        };
+        let dir = dir.canonicalize().unwrap_or(dir);
+        by_dir
+            .entry(dir)
+            .or_default()
+            .push(harness.id().to_string());
+    }
+    by_dir.retain(|_, ids| ids.len() > 1);
+    by_dir.into_iter().collect()
+}
+
+/// One warning per directory shared by the built-in harnesses and the
+/// registered custom ones.
+pub fn shared_config_dir_warnings() -> Vec<String> {
+    let builtin: Vec<Harness> = HarnessKind::ALL.iter().map(|k| Harness::new(*k)).collect();
+    let custom = CustomHarness::descriptors_dir()
+        .and_then(|dir| CustomHarness::load_dir(&dir))
+        .unwrap_or_default();
+    let all: Vec<&dyn HarnessConfig> = builtin
+        .iter()
+        .map(|h| h as &dyn HarnessConfig)
+        .chain(custom.iter().map(|h| h as &dyn HarnessConfig))
+        .collect();
+    shared_config_dirs(&all)
+        .into_iter()
+        .map(|(dir, ids)| {
+            format!(
+                "{} share the config directory {}; switching one overwrites the others",
+                ids.join(", "),
+                dir.display()
+            )
+        })
+        .collect()
+}
+
 fn mcp_server_enabled(server: &McpServer) -> bool {
     match server {
         McpServer::Stdio(s) => s.enabled,
@@ -65,6 +188,10 @@ impl HarnessConfig for harness_locate::Harness {
         }
     }
 
+    fn resource_harness(&self) -> Option<&Harness> {
+        Some(self)
+    }
+
     fn config_dir(&self) -> Result<PathBuf> {
         Ok(self.config(&Scope::Global)?)
     }
@@ -119,3 +246,69 @@ impl HarnessConfig for harness_locate::Harness {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn custom(id: &str, config_dir: &std::path::Path) -> CustomHarness {
+        CustomHarness::from_toml(&format!(
+            "id = \"{id}\"\nconfig_dir = \"{}\"\n",
+            config_dir.display()
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn harnesses_resolving_to_one_config_dir_are_reported() {
+        let temp = TempDir::new().unwrap();
+        let shared = temp.path().join("shared");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::create_dir_all(temp.path().join("other")).unwrap();
+        let (alpha, beta, gamma) = (
+            custom("alpha", &shared),
+            custom("beta", &temp.path().join("other/../shared")),
+            custom("gamma", &temp.path().join("own")),
+        );
+
+        let collisions = shared_config_dirs(&[&alpha, &beta, &gamma]);
+
+        assert_eq!(
+            collisions,
+            [(
+                shared.canonicalize().unwrap(),
+                vec!["alpha".to_string(), "beta".to_string()]
+            )]
+        );
+    }
+    #[test]
+    fn parse_version_finds_the_first_version_number() {
+        assert_eq!(
+            parse_version("1.0.3 (Claude Code)").as_deref(),
+            Some("1.0.3")
+        );
+        assert_eq!(parse_version("opencode v0.5.1\n").as_deref(), Some("0.5.1"));
+        assert_eq!(
+            parse_version("goose 1.2.0, build 7").as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(parse_version("build 7"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_hanging_version_command_gives_up() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let binary = temp.path().join("hangs");
+        std::fs::write(&binary, "#!/bin/sh\nsleep 10\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let started = Instant::now();
+        assert_eq!(binary_version(&binary, Duration::from_millis(100)), None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,100 @@
+//! Built-in harnesses pointed at a project-level config instead of the
+//! user's global one.
+
+use std::path::PathBuf;
+
+use harness_locate::{Harness, HarnessKind, InstallationStatus, Scope};
+
+use super::HarnessConfig;
+use crate::error::Result;
+
+/// A built-in harness whose live config is resolved for `scope`.
+///
+/// Profiles are shared with the global harness, but the active profile is
+/// tracked per project so switching one project never saves its config over
+/// the globally active profile.
+#[derive(Debug)]
+pub struct ScopedHarness {
+    harness: Harness,
+    scope: Scope,
+}
+
+impl ScopedHarness {
+    pub fn new(kind: HarnessKind, scope: Scope) -> Self {
+        Self {
+            harness: Harness::new(kind),
+            scope,
+        }
+    }
+
+    /// The underlying harness, for resource lookups.
+    pub fn harness(&self) -> &Harness {
+        &self.harness
+    }
+}
+
+impl HarnessConfig for ScopedHarness {
+    fn id(&self) -> &str {
+        HarnessConfig::id(&self.harness)
+    }
+
+    fn active_key(&self) -> String {
+        match &self.scope {
+            Scope::Global => self.id().to_string(),
+            Scope::Project(root) | Scope::Custom(root) => {
+                format!("{}@{}", self.id(), root.display())
+            }
+        }
+    }
+
+    fn scope(&self) -> Scope {
+        self.scope.clone()
+    }
+
+    fn resource_harness(&self) -> Option<&Harness> {
+        Some(&self.harness)
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        Ok(self.harness.config(&self.scope)?)
+    }
+
+    fn installation_status(&self) -> Result<InstallationStatus> {
+        HarnessConfig::installation_status(&self.harness)
+    }
+
+    fn mcp_filename(&self) -> Option<String> {
+        self.mcp_config_path()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+    }
+
+    fn mcp_config_path(&self) -> Option<PathBuf> {
+        self.harness.mcp(&self.scope).ok().flatten().map(|r| r.file)
+    }
+
+    fn parse_mcp_servers(&self, content: &str, filename: &str) -> Result<Vec<(String, bool)>> {
+        self.harness.parse_mcp_servers(content, filename)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_scope_resolves_its_own_config_dir() {
+        let project = tempfile::TempDir::new().unwrap();
+        let global = ScopedHarness::new(HarnessKind::ClaudeCode, Scope::Global);
+        let scoped = ScopedHarness::new(
+            HarnessKind::ClaudeCode,
+            Scope::Project(project.path().to_path_buf()),
+        );
+
+        let dir = scoped.config_dir().unwrap();
+        assert!(dir.starts_with(project.path()), "{}", dir.display());
+        assert_ne!(Some(dir), global.config_dir().ok());
+        assert_eq!(scoped.id(), "claude-code");
+        assert_ne!(scoped.active_key(), global.active_key());
+        assert_eq!(global.active_key(), "claude-code");
+    }
+}
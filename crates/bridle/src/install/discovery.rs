@@ -3,10 +3,13 @@
 //! Wraps the `skills-locate` crate to discover installable skills.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use harness_locate::McpServer;
 use skills_locate::parse_mcp_json;
-use skills_locate::{GitHubRef, extract_file, fetch_bytes, list_files, parse_skill_descriptor};
+use skills_locate::{
+    FetchOptions, GitHubRef, extract_file, fetch_bytes_with, list_files, parse_skill_descriptor,
+};
 use thiserror::Error;
 
 use super::types::{AgentInfo, CommandInfo, DiscoveryResult, SkillInfo, SourceInfo};
@@ -21,9 +24,37 @@ pub enum DiscoveryError {
 
     #[error("No skills found in repository")]
     NoSkillsFound,
+
+    #[error(
+        "GitHub rate limit reached{}. Set GITHUB_TOKEN to authenticate and raise the limit.",
+        retry_after.map(|s| format!(" (retry in {s}s)")).unwrap_or_default()
+    )]
+    RateLimited { retry_after: Option<u64> },
+}
+
+fn map_fetch_error(e: skills_locate::Error) -> DiscoveryError {
+    match e {
+        skills_locate::Error::RateLimited { retry_after } => {
+            DiscoveryError::RateLimited { retry_after }
+        }
+        other => DiscoveryError::FetchError(other),
+    }
+}
+
+/// Fetch options for GitHub: the given timeout, authenticated with `GITHUB_TOKEN` if set.
+pub fn github_fetch_options(timeout: Duration) -> FetchOptions {
+    FetchOptions {
+        timeout: Some(timeout),
+        auth_token: std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty()),
+    }
 }
 
-pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
+pub fn discover_skills(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<DiscoveryResult, DiscoveryError> {
     let github_ref =
         GitHubRef::parse(url).map_err(|e| DiscoveryError::InvalidUrl(e.to_string()))?;
 
@@ -34,7 +65,7 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
     };
 
     let archive_url = github_ref.archive_url();
-    let zip_bytes = fetch_bytes(&archive_url).map_err(DiscoveryError::FetchError)?;
+    let zip_bytes = fetch_bytes_with(&archive_url, options).map_err(map_fetch_error)?;
 
     let skill_paths = list_files(&zip_bytes, "SKILL.md").map_err(DiscoveryError::FetchError)?;
 
@@ -223,17 +254,34 @@ mod tests {
 
     #[test]
     fn discover_skills_invalid_url() {
-        let result = discover_skills("https://gitlab.com/owner/repo");
+        let result = discover_skills("https://gitlab.com/owner/repo", &FetchOptions::default());
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), DiscoveryError::InvalidUrl(_)));
     }
 
     #[test]
     fn discover_skills_missing_owner() {
-        let result = discover_skills("https://github.com/");
+        let result = discover_skills("https://github.com/", &FetchOptions::default());
         assert!(result.is_err());
     }
 
+    #[test]
+    fn rate_limit_maps_to_distinct_error() {
+        let err = map_fetch_error(skills_locate::Error::RateLimited {
+            retry_after: Some(30),
+        });
+        assert!(matches!(
+            err,
+            DiscoveryError::RateLimited {
+                retry_after: Some(30)
+            }
+        ));
+        assert!(err.to_string().contains("GITHUB_TOKEN"));
+
+        let err = map_fetch_error(skills_locate::Error::Http("HTTP 404".to_string()));
+        assert!(matches!(err, DiscoveryError::FetchError(_)));
+    }
+
     #[test]
     fn normalize_path_strips_prefix() {
         let github_ref = GitHubRef::parse("https://github.com/owner/my-repo").unwrap();
@@ -278,7 +326,10 @@ mod tests {
     #[test]
     #[ignore = "requires network access"]
     fn discover_skills_real_repo() {
-        let result = discover_skills("https://github.com/anthropics/claude-code");
+        let result = discover_skills(
+            "https://github.com/anthropics/claude-code",
+            &FetchOptions::default(),
+        );
         match result {
             Ok(discovery) => {
                 assert_eq!(discovery.source.owner, "anthropics");
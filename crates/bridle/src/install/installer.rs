@@ -14,6 +14,7 @@ use super::types::{
     parse_harness_kind,
 };
 use crate::config::BridleConfig;
+use crate::config::is_windows_reserved_name;
 use crate::harness::HarnessConfig;
 
 #[derive(Debug, Error)]
@@ -34,13 +35,20 @@ pub enum InstallError {
     InvalidComponentName(String),
 }
 
-fn validate_component_name(name: &str) -> Result<(), InstallError> {
+/// Rejects names that cannot be used as a single path component on every
+/// platform bridle supports.
+///
+/// Besides separators and `.`/`..`, this refuses Windows device names and
+/// trailing dots or spaces, which Windows silently strips.
+pub fn validate_component_name(name: &str) -> Result<(), InstallError> {
     if name.is_empty()
         || name.contains('/')
         || name.contains('\\')
         || name == "."
         || name == ".."
         || name.contains('\0')
+        || name.ends_with(['.', ' '])
+        || is_windows_reserved_name(name)
     {
         return Err(InstallError::InvalidComponentName(name.to_string()));
     }
@@ -637,6 +645,21 @@ mod tests {
         (temp, target, profiles_dir)
     }
 
+    #[test]
+    fn component_names_must_be_portable() {
+        assert!(validate_component_name("my-skill").is_ok());
+        assert!(validate_component_name("v1.2").is_ok());
+        for name in ["CON", "nul.md", "a.", "skill ", "..", "a/b"] {
+            assert!(
+                matches!(
+                    validate_component_name(name),
+                    Err(InstallError::InvalidComponentName(_))
+                ),
+                "{name:?} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn install_creates_skill_directory() {
         let (_temp, target, profiles_dir) = setup_test_env();
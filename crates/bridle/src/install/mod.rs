@@ -11,5 +11,5 @@ pub mod mcp_installer;
 pub mod types;
 pub mod uninstaller;
 
-pub use discovery::{DiscoveryError, discover_skills};
+pub use discovery::{DiscoveryError, discover_skills, github_fetch_options};
 pub use types::{parse_harness_kind, *};
@@ -0,0 +1,29 @@
+//! Profile management for AI coding harnesses.
+//!
+//! This is the library half of the `bridle` binary. It exposes profile storage,
+//! bridle's own configuration, harness abstraction, and the display IR used by
+//! the CLI and TUI. The CLI and TUI themselves live in the binary target.
+//!
+//! ```no_run
+//! use bridle::{BridleConfig, ProfileManager, ProfileName};
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+//! let harness = Harness::new(HarnessKind::OpenCode);
+//! let name = ProfileName::new("work")?;
+//! let info = manager.show_profile(&harness, &name)?;
+//! let nodes = bridle::display::profile_to_nodes(&info);
+//! print!("{}", bridle::display::nodes_to_text(&nodes, bridle::display::TextStyle::Tree));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod config;
+pub mod diagnostics;
+pub mod display;
+pub mod error;
+pub mod harness;
+pub mod install;
+
+pub use config::{BridleConfig, ProfileInfo, ProfileManager, ProfileName};
+pub use error::{Error, Result};
+pub use harness::HarnessConfig;
@@ -1,72 +1,399 @@
 mod cli;
-mod config;
-mod display;
-mod error;
-mod harness;
-mod install;
 mod tui;
 
+#[cfg(test)]
+#[path = "config/test_env.rs"]
+mod test_env;
+
+use bridle::{config, diagnostics, display, error, harness, install};
+
+use std::process::ExitCode;
+
 use clap::Parser;
 use cli::output::OutputFormat;
-use cli::{Commands, ConfigCommands, ProfileCommands};
+use cli::{
+    BackupCommands, Commands, ConfigCommands, HarnessCommands, McpCommands, ProfileCommands,
+};
+use error::exit_code;
+
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  Success
+  1  Generic failure
+  2  Profile not found
+  3  Profile shown, but some of its config failed to extract
+  4  No active profile for the harness
+  5  MCP server not found in the profile";
 
 #[derive(Parser)]
 #[command(name = "bridle")]
 #[command(version, about = "Unified AI harness configuration manager")]
+#[command(after_help = EXIT_CODES_HELP)]
 struct Cli {
     #[arg(long, short = 'o', default_value = "auto", global = true)]
     output: OutputFormat,
 
+    /// Write json/yaml output to this file instead of stdout
+    #[arg(long, global = true, value_name = "PATH")]
+    output_file: Option<std::path::PathBuf>,
+
+    /// Suppress non-fatal warnings on stderr
+    #[arg(long, short = 'q', global = true)]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
-fn main() -> color_eyre::Result<()> {
-    color_eyre::install()?;
+fn main() -> ExitCode {
+    if let Err(e) = color_eyre::install() {
+        eprintln!("Error: {e:?}");
+        return ExitCode::from(exit_code::GENERIC);
+    }
+
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(report) => {
+            eprintln!("Error: {report:?}");
+            let code = report
+                .downcast_ref::<error::Error>()
+                .map_or(exit_code::GENERIC, error::Error::exit_code);
+            ExitCode::from(code)
+        }
+    }
+}
 
-    let cli = Cli::parse();
+fn run(cli: Cli) -> color_eyre::Result<()> {
     let format = cli.output.resolve();
+    if cli.output_file.is_some() && format == cli::output::ResolvedFormat::Text {
+        return Err(color_eyre::eyre::eyre!(
+            "--output-file needs --output json or --output yaml"
+        ));
+    }
+    cli::output::set_output_file(cli.output_file);
+    if cli.quiet {
+        diagnostics::set_logger(Some(diagnostics::quiet()));
+    }
+    cli::profile::recover_interrupted_switches();
 
     match cli.command {
         None | Some(Commands::Tui) => cli::tui::run_tui()?,
-        Some(Commands::Status) => cli::status::display_status(format),
+        Some(Commands::Status) => cli::status::display_status(format)?,
+        Some(Commands::Doctor) => cli::doctor::run_doctor(format)?,
         Some(Commands::Init) => cli::init::run_init()?,
         Some(Commands::Profile(profile_cmd)) => match profile_cmd {
-            ProfileCommands::List { harness } => cli::profile::list_profiles(&harness, format)?,
-            ProfileCommands::Show { harness, name } => {
-                cli::profile::show_profile(&harness, &name, format)?
+            ProfileCommands::List {
+                harness,
+                compact: true,
+                fail_fast,
+            } => cli::profile::list_profiles_compact(&harness, !fail_fast)?,
+            ProfileCommands::List {
+                harness,
+                compact: false,
+                ..
+            } => cli::profile::list_profiles(&harness, format)?,
+            ProfileCommands::Show {
+                harness,
+                name,
+                print_path: true,
+                ..
+            } => cli::profile::print_profile_path(&harness, &name)?,
+            ProfileCommands::Show {
+                harness,
+                name,
+                resources,
+                plain,
+                print_path: false,
+                scope,
+            } => {
+                let style = if plain {
+                    display::TextStyle::Flat
+                } else {
+                    display::TextStyle::Tree
+                };
+                cli::profile::show_profile(&harness, &name, &resources, scope, style, format)?
             }
             ProfileCommands::Create {
                 harness,
                 name,
                 from_current,
+                // clap rejects --empty alongside every flag that reads the live config.
+                empty: _,
+                template,
+                force,
+                scope,
+                exclude,
+                include,
+                no_resources,
+                print_path,
             } => {
-                if from_current {
-                    cli::profile::create_profile_from_current(&harness, &name)?
-                } else {
-                    cli::profile::create_profile(&harness, &name)?
+                let filter = config::CaptureFilter::new(exclude, include);
+                let capture = cli::profile::CaptureArgs {
+                    scope,
+                    filter,
+                    no_resources,
+                };
+                match create_source(template, force, from_current) {
+                    CreateSource::Template(template) => cli::profile::create_profile_from_template(
+                        &harness, &name, template, print_path,
+                    )?,
+                    CreateSource::Recapture => cli::profile::recreate_profile_from_current(
+                        &harness, &name, capture, print_path,
+                    )?,
+                    CreateSource::LiveConfig => cli::profile::create_profile_from_current(
+                        &harness, &name, capture, print_path,
+                    )?,
+                    CreateSource::Nothing => {
+                        cli::profile::create_profile(&harness, &name, print_path)?
+                    }
                 }
             }
-            ProfileCommands::Delete { harness, name } => {
-                cli::profile::delete_profile(&harness, &name)?
+            ProfileCommands::Copy {
+                from_harness,
+                from,
+                to_harness,
+                to,
+            } => cli::profile::copy_profile(&from_harness, &from, &to_harness, &to)?,
+            ProfileCommands::Move {
+                harness,
+                name,
+                new_harness,
+            } => cli::profile::move_profile(&harness, &name, &new_harness)?,
+            ProfileCommands::Compress { harness, name } => {
+                cli::profile::compress_profile(&harness, &name)?
             }
-            ProfileCommands::Switch { harness, name } => {
-                cli::profile::switch_profile(&harness, &name)?
+            ProfileCommands::Delete {
+                harness,
+                name,
+                glob,
+                yes,
+                dry_run,
+            } => match (name, glob) {
+                (Some(name), _) => cli::profile::delete_profile(&harness, &name, dry_run)?,
+                (None, Some(glob)) => {
+                    cli::profile::delete_profiles_matching(&harness, &glob, yes, dry_run)?
+                }
+                (None, None) => unreachable!("clap requires a name or --glob"),
+            },
+            ProfileCommands::Switch {
+                harness,
+                name,
+                scope,
+                no_resources,
+                force,
+                print_path,
+            } => cli::profile::switch_profile(
+                &harness,
+                &name,
+                scope,
+                no_resources,
+                force,
+                print_path,
+            )?,
+            ProfileCommands::Reset { harness } => cli::profile::reset_profile(&harness)?,
+            ProfileCommands::Log { harness, name } => {
+                cli::profile::log_profile(&harness, &name, format)?
             }
-            ProfileCommands::Edit { harness, name } => cli::profile::edit_profile(&harness, &name)?,
+            ProfileCommands::Edit {
+                harness,
+                name,
+                file,
+            } => cli::profile::edit_profile(&harness, &name, file.as_deref())?,
+            ProfileCommands::OpenActive { harness } => cli::profile::open_active_profile(&harness)?,
             ProfileCommands::Diff {
                 harness,
                 name,
                 other,
-            } => cli::profile::diff_profiles(&harness, &name, other.as_deref())?,
+                against_backup,
+                strict,
+            } => match (against_backup, name) {
+                (Some(backup), name) => {
+                    cli::profile::diff_against_backup(&harness, &backup, name.as_deref(), strict)?
+                }
+                (None, Some(name)) => {
+                    cli::profile::diff_profiles(&harness, &name, other.as_deref(), strict)?
+                }
+                (None, None) => unreachable!("clap requires a profile without --against-backup"),
+            },
+            ProfileCommands::Compare { harness, from, to } => {
+                cli::profile::compare_profiles(&harness, &from, &to, format)?
+            }
+            ProfileCommands::Rollback {
+                harness,
+                scope,
+                force,
+            } => cli::profile::rollback_profile(&harness, scope, force)?,
+            ProfileCommands::Verify { harness, name } => {
+                cli::profile::verify_profile(&harness, &name, format)?
+            }
+            ProfileCommands::ValidateConfig { harness, name } => {
+                cli::profile::validate_profile_config(&harness, &name, format)?
+            }
+            ProfileCommands::Cat {
+                harness,
+                name,
+                file,
+                stored,
+            } => cli::profile::cat_profile_file(&harness, &name, &file, stored)?,
+            ProfileCommands::Fingerprint { harness, name } => {
+                cli::profile::fingerprint_profile(&harness, &name, format)?
+            }
+            ProfileCommands::PruneOrphans { dry_run, yes } => {
+                cli::profile::prune_orphans(dry_run, yes, format)?
+            }
+            ProfileCommands::Stats { harness, fail_fast } => {
+                cli::profile::profile_stats(&harness, !fail_fast, format)?
+            }
+            ProfileCommands::Which { harness } => cli::profile::which_profile(&harness, format)?,
+            ProfileCommands::SetMeta {
+                harness,
+                name,
+                description,
+                tags,
+                locked,
+            } => cli::profile::set_profile_meta(&harness, &name, description, tags, locked)?,
         },
         Some(Commands::Config(config_cmd)) => match config_cmd {
-            ConfigCommands::Set { key, value } => cli::config_cmd::set_config(&key, &value)?,
+            ConfigCommands::Set {
+                key,
+                value,
+                harness: Some(harness),
+            } => cli::config_cmd::set_harness_config(&harness, &key, &value)?,
+            ConfigCommands::Set { key, value, .. } => cli::config_cmd::set_config(&key, &value)?,
             ConfigCommands::Get { key } => cli::config_cmd::get_config(&key)?,
+            ConfigCommands::Edit => cli::config_cmd::edit_config()?,
+        },
+        Some(Commands::Mcp(mcp_cmd)) => match mcp_cmd {
+            McpCommands::List { harness, profile } => {
+                cli::mcp_cmd::list_mcp_servers(&harness, profile.as_deref(), format)?
+            }
+            McpCommands::Show {
+                harness,
+                name,
+                profile,
+            } => cli::mcp_cmd::show_mcp_server(&harness, &name, profile.as_deref(), format)?,
+        },
+        Some(Commands::Backup(BackupCommands::List { harness, since })) => {
+            cli::backup_cmd::list_backups(&harness, since.as_deref(), format)?
+        }
+        Some(Commands::Harness(harness_cmd)) => match harness_cmd {
+            HarnessCommands::Add { file } => cli::harness_cmd::add_harness(&file)?,
+            HarnessCommands::List => cli::harness_cmd::list_harnesses(format)?,
         },
-        Some(Commands::Install { source, force }) => cli::install::run(&source, force)?,
+        Some(Commands::Install {
+            source,
+            force,
+            harness,
+            profile,
+            create,
+            yes,
+        }) => {
+            let target = harness.as_deref().zip(profile.as_deref());
+            cli::install::run(&source, force, target, create, yes)?
+        }
         Some(Commands::Uninstall { harness, profile }) => cli::uninstall::run(&harness, &profile)?,
     }
 
     Ok(())
 }
+
+/// What `profile create` fills a new profile from.
+#[derive(Debug, PartialEq, Eq)]
+enum CreateSource {
+    /// `--template`: a built-in starter profile.
+    Template(config::ProfileTemplate),
+    /// `--force`: the live config, replacing an existing profile.
+    Recapture,
+    /// `--from-current`: the live config.
+    LiveConfig,
+    /// Nothing: the live config is never read.
+    Nothing,
+}
+
+fn create_source(
+    template: Option<config::ProfileTemplate>,
+    force: bool,
+    from_current: bool,
+) -> CreateSource {
+    match template {
+        Some(template) => CreateSource::Template(template),
+        None if force => CreateSource::Recapture,
+        None if from_current => CreateSource::LiveConfig,
+        None => CreateSource::Nothing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_empty_conflicts_with_from_current() {
+        let parsed = Cli::try_parse_from([
+            "bridle",
+            "profile",
+            "create",
+            "opencode",
+            "scratch",
+            "--empty",
+            "--from-current",
+        ]);
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn create_empty_parses() {
+        let cli = Cli::try_parse_from([
+            "bridle", "profile", "create", "opencode", "scratch", "--empty",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Profile(ProfileCommands::Create {
+                empty: true,
+                from_current: false,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn create_empty_never_reads_the_live_config() {
+        let cli = Cli::try_parse_from([
+            "bridle", "profile", "create", "opencode", "scratch", "--empty",
+        ])
+        .unwrap();
+        let Some(Commands::Profile(ProfileCommands::Create {
+            template,
+            force,
+            from_current,
+            ..
+        })) = cli.command
+        else {
+            panic!("expected profile create");
+        };
+        assert_eq!(
+            create_source(template, force, from_current),
+            CreateSource::Nothing
+        );
+
+        for live_flag in [
+            ["--force", ""],
+            ["--include", "*.json"],
+            ["--no-resources", ""],
+        ] {
+            let args = [
+                "bridle", "profile", "create", "opencode", "scratch", "--empty",
+            ]
+            .into_iter()
+            .chain(live_flag.into_iter().filter(|a| !a.is_empty()));
+            assert!(Cli::try_parse_from(args).is_err(), "{live_flag:?}");
+        }
+    }
+
+    #[test]
+    fn quiet_is_accepted_after_subcommands() {
+        let cli = Cli::try_parse_from(["bridle", "profile", "list", "opencode", "-q"]).unwrap();
+        assert!(cli.quiet);
+    }
+}
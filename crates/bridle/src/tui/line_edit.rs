@@ -0,0 +1,185 @@
+//! Readline-style editing for the TUI's one-line text inputs.
+//!
+//! The cursor is a char index into the text, from `0` to its char count.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// Applies `key` to `text`: typing, Backspace, Delete, Left/Right, Home/End,
+/// Ctrl-A/Ctrl-E, Ctrl-U (clear), and Ctrl-W (delete the word before the
+/// cursor). Returns whether the text changed.
+pub(super) fn edit(text: &mut String, cursor: &mut usize, key: KeyEvent) -> bool {
+    let len = text.chars().count();
+    *cursor = (*cursor).min(len);
+    // Windows reports AltGr as Ctrl+Alt, so that combination is plain text.
+    let ctrl =
+        key.modifiers.contains(KeyModifiers::CONTROL) && !key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Char('u') if ctrl => {
+            let changed = !text.is_empty();
+            text.clear();
+            *cursor = 0;
+            changed
+        }
+        KeyCode::Char('w') if ctrl => {
+            let chars: Vec<char> = text.chars().collect();
+            let mut start = *cursor;
+            while start > 0 && chars[start - 1].is_whitespace() {
+                start -= 1;
+            }
+            while start > 0 && !chars[start - 1].is_whitespace() {
+                start -= 1;
+            }
+            remove_chars(text, start, *cursor);
+            let changed = start < *cursor;
+            *cursor = start;
+            changed
+        }
+        KeyCode::Char('a') if ctrl => {
+            *cursor = 0;
+            false
+        }
+        KeyCode::Char('e') if ctrl => {
+            *cursor = len;
+            false
+        }
+        KeyCode::Char(c) if !ctrl => {
+            insert_str(text, cursor, c.encode_utf8(&mut [0; 4]));
+            true
+        }
+        KeyCode::Backspace if *cursor > 0 => {
+            remove_chars(text, *cursor - 1, *cursor);
+            *cursor -= 1;
+            true
+        }
+        KeyCode::Delete if *cursor < len => {
+            remove_chars(text, *cursor, *cursor + 1);
+            true
+        }
+        KeyCode::Left => {
+            *cursor = cursor.saturating_sub(1);
+            false
+        }
+        KeyCode::Right => {
+            *cursor = (*cursor + 1).min(len);
+            false
+        }
+        KeyCode::Home => {
+            *cursor = 0;
+            false
+        }
+        KeyCode::End => {
+            *cursor = len;
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Inserts `s` at the cursor, e.g. a bracketed paste, and moves the cursor
+/// past it. Line breaks are dropped, as the inputs hold a single line.
+pub(super) fn insert_str(text: &mut String, cursor: &mut usize, s: &str) {
+    let s: String = s.chars().filter(|c| !matches!(c, '\n' | '\r')).collect();
+    text.insert_str(byte_index(text, *cursor), &s);
+    *cursor += s.chars().count();
+}
+
+/// `text` with the char under the cursor shown reversed, or a block after
+/// the text when the cursor is at the end.
+pub(super) fn cursor_line(text: &str, cursor: usize) -> Line<'static> {
+    let at = byte_index(text, cursor);
+    let (before, rest) = text.split_at(at);
+    let mut rest = rest.chars();
+    let spans = match rest.next() {
+        Some(under) => vec![
+            Span::raw(before.to_string()),
+            Span::styled(
+                under.to_string(),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(rest.as_str().to_string()),
+        ],
+        None => vec![Span::raw(format!("{before}█"))],
+    };
+    Line::from(spans)
+}
+
+fn byte_index(text: &str, cursor: usize) -> usize {
+    text.char_indices()
+        .nth(cursor)
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// Removes the chars in `start..end`.
+fn remove_chars(text: &mut String, start: usize, end: usize) {
+    let range = byte_index(text, start)..byte_index(text, end);
+    text.replace_range(range, "");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    const CTRL_W: KeyEvent = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+
+    #[test]
+    fn ctrl_w_deletes_the_word_before_the_cursor() {
+        let mut text = "client work  profile".to_string();
+        let mut cursor = 13;
+
+        assert!(edit(&mut text, &mut cursor, CTRL_W));
+        assert_eq!((text.as_str(), cursor), ("client profile", 7));
+
+        assert!(edit(&mut text, &mut cursor, CTRL_W));
+        assert_eq!((text.as_str(), cursor), ("profile", 0));
+
+        assert!(!edit(&mut text, &mut cursor, CTRL_W));
+        assert_eq!((text.as_str(), cursor), ("profile", 0));
+    }
+
+    #[test]
+    fn altgr_chars_are_typed() {
+        let altgr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        let mut text = "me".to_string();
+        let mut cursor = 2;
+
+        assert!(edit(
+            &mut text,
+            &mut cursor,
+            KeyEvent::new(KeyCode::Char('@'), altgr)
+        ));
+        assert!(edit(
+            &mut text,
+            &mut cursor,
+            KeyEvent::new(KeyCode::Char('w'), altgr)
+        ));
+        assert_eq!((text.as_str(), cursor), ("me@w", 4));
+
+        let ctrl_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(!edit(&mut text, &mut cursor, ctrl_x));
+        assert_eq!(text, "me@w");
+    }
+
+    #[test]
+    fn backspace_stops_at_the_start_and_edits_mid_line() {
+        let mut text = "wörk".to_string();
+        let mut cursor = 4;
+        edit(&mut text, &mut cursor, key(KeyCode::Left));
+        edit(&mut text, &mut cursor, key(KeyCode::Left));
+        assert!(edit(&mut text, &mut cursor, key(KeyCode::Backspace)));
+        assert_eq!((text.as_str(), cursor), ("wrk", 1));
+
+        assert!(edit(&mut text, &mut cursor, key(KeyCode::Backspace)));
+        assert!(!edit(&mut text, &mut cursor, key(KeyCode::Backspace)));
+        assert_eq!((text.as_str(), cursor), ("rk", 0));
+
+        edit(&mut text, &mut cursor, key(KeyCode::End));
+        insert_str(&mut text, &mut cursor, "-a\nb");
+        assert_eq!((text.as_str(), cursor), ("rk-ab", 5));
+    }
+}
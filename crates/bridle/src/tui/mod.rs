@@ -2,16 +2,17 @@
 //!
 //! Provides an interactive TUI for browsing harnesses, profiles, and their configurations.
 
-mod theme;
+mod line_edit;
 mod views;
 mod widgets;
 
+use std::collections::HashMap;
 use std::io::{self, Stdout};
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEvent,
-        MouseEventKind,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind,
     },
     execute,
     terminal::{
@@ -30,7 +31,10 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, TableState},
 };
 
-use crate::config::{BridleConfig, ProfileInfo, ProfileManager, ProfileName};
+use crate::config::{
+    BridleConfig, EditSnapshot, ProfileInfo, ProfileManager, ProfileName, SwitchReport,
+};
+use crate::display::Theme;
 use crate::error::Error;
 use views::ViewMode;
 use widgets::{DetailPane, HarnessTabs, ProfileTable, StatusBar};
@@ -87,26 +91,94 @@ enum InputMode {
     ConfirmingDelete,
 }
 
+/// Each harness's installation status, probed once per session so drawing a
+/// frame doesn't touch the filesystem. `r` probes again.
+#[derive(Debug, Default)]
+struct InstallStatuses(HashMap<HarnessKind, InstallationStatus>);
+
+impl InstallStatuses {
+    fn probe_with(
+        kinds: &[HarnessKind],
+        probe: impl Fn(HarnessKind) -> Option<InstallationStatus>,
+    ) -> Self {
+        Self(
+            kinds
+                .iter()
+                .filter_map(|&kind| probe(kind).map(|status| (kind, status)))
+                .collect(),
+        )
+    }
+
+    fn probe(kinds: &[HarnessKind]) -> Self {
+        Self::probe_with(kinds, |kind| Harness::new(kind).installation_status().ok())
+    }
+
+    /// The cached status, or `None` if probing failed.
+    fn get(&self, kind: HarnessKind) -> Option<&InstallationStatus> {
+        self.0.get(&kind)
+    }
+
+    /// Whether the harness has a config directory.
+    fn is_installed(&self, kind: HarnessKind) -> bool {
+        matches!(
+            self.get(kind),
+            Some(InstallationStatus::FullyInstalled { .. } | InstallationStatus::ConfigOnly { .. })
+        )
+    }
+
+    fn is_fully_installed(&self, kind: HarnessKind) -> bool {
+        matches!(
+            self.get(kind),
+            Some(InstallationStatus::FullyInstalled { .. })
+        )
+    }
+
+    /// Status for empty-state messages, treating an unknown status as not installed.
+    fn status_or_missing(&self, kind: HarnessKind) -> InstallationStatus {
+        self.get(kind)
+            .cloned()
+            .unwrap_or(InstallationStatus::NotInstalled)
+    }
+}
+
 #[derive(Debug)]
 struct App {
     running: bool,
     view_mode: ViewMode,
     active_pane: Pane,
     harnesses: Vec<HarnessKind>,
+    install_statuses: InstallStatuses,
     harness_state: ListState,
     profiles: Vec<ProfileInfo>,
     profile_state: ListState,
     profile_table_state: TableState,
+    /// Scroll position of the cards view; its selection follows `profile_state`.
+    #[cfg(feature = "tui-cards")]
+    card_state: views::CardViewState,
     expanded_profile: Option<usize>,
     status_message: Option<String>,
     bridle_config: BridleConfig,
     manager: ProfileManager,
+    /// Colors from `[tui.theme]`, read once at startup.
+    theme: Theme,
     show_help: bool,
+    /// The first-run welcome screen is open.
+    show_onboarding: bool,
     input_mode: InputMode,
     input_buffer: String,
+    /// Char index of the cursor in `input_buffer`.
+    input_cursor: usize,
     create_profile_copy_current: bool,
     create_profile_focused_on_checkbox: bool,
     create_profile_error: Option<String>,
+    /// Sanitized name offered after an invalid entry; Enter accepts it.
+    create_profile_suggestion: Option<String>,
+    /// Only list profiles carrying this tag; `t` cycles through the harness's tags.
+    tag_filter: Option<String>,
+    /// Distinct tags across the selected harness's profiles, sorted.
+    available_tags: Vec<String>,
+    /// Pre-edit snapshot kept while an edited file fails to parse; `u` reverts it.
+    pending_edit_revert: Option<EditSnapshot>,
     needs_full_redraw: bool,
     detail_scroll: u16,
     detail_content_height: u16,
@@ -118,14 +190,20 @@ struct App {
 impl App {
     fn new() -> Result<Self, Error> {
         let bridle_config = BridleConfig::load()?;
+        let theme = Theme::from_config(&bridle_config.tui.theme);
         let profiles_dir = BridleConfig::profiles_dir()?;
-        let manager = ProfileManager::new(profiles_dir);
+        let manager =
+            ProfileManager::new(profiles_dir).with_fs_retries(bridle_config.profile.fs_retries);
 
         // Sort harnesses: installed first, then not installed
         let mut harnesses = HarnessKind::ALL.to_vec();
+        let install_statuses = InstallStatuses::probe(&harnesses);
         harnesses.sort_by_key(|kind| {
-            let harness = Harness::new(*kind);
-            if harness.is_installed() { 0 } else { 1 }
+            if install_statuses.is_installed(*kind) {
+                0
+            } else {
+                1
+            }
         });
 
         // If a default harness is configured, move it to position 0
@@ -136,6 +214,15 @@ impl App {
             harnesses.insert(0, kind);
         }
 
+        // Decided before any default profile is created below, which would
+        // make every first run look like a returning one.
+        let has_profiles = harnesses.iter().any(|kind| {
+            manager
+                .list_profiles(&Harness::new(*kind))
+                .is_ok_and(|profiles| !profiles.is_empty())
+        });
+        let show_onboarding = needs_onboarding(&bridle_config, has_profiles);
+
         for kind in &harnesses {
             let harness = Harness::new(*kind);
             let _ = manager.create_from_current_if_missing(&harness);
@@ -146,23 +233,33 @@ impl App {
 
         let mut app = Self {
             running: true,
-            view_mode: ViewMode::default(),
+            view_mode: ViewMode::from_config(bridle_config.tui.view),
             active_pane: Pane::Profiles,
             harnesses,
+            install_statuses,
             harness_state,
             profiles: Vec::new(),
             profile_state: ListState::default(),
             profile_table_state: TableState::default(),
+            #[cfg(feature = "tui-cards")]
+            card_state: views::CardViewState::default(),
             expanded_profile: None,
             status_message: None,
             bridle_config,
             manager,
+            theme,
             show_help: false,
+            show_onboarding,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
+            input_cursor: 0,
             create_profile_copy_current: true,
             create_profile_focused_on_checkbox: false,
             create_profile_error: None,
+            create_profile_suggestion: None,
+            tag_filter: None,
+            available_tags: Vec::new(),
+            pending_edit_revert: None,
             needs_full_redraw: false,
             detail_scroll: 0,
             detail_content_height: 0,
@@ -172,6 +269,10 @@ impl App {
         };
 
         app.refresh_profiles();
+        let collisions = crate::harness::shared_config_dir_warnings();
+        if !collisions.is_empty() {
+            app.status_message = Some(format!("Warning: {}", collisions.join("; ")));
+        }
         Ok(app)
     }
 
@@ -187,10 +288,10 @@ impl App {
             return '*';
         }
 
-        match harness.installation_status() {
-            Ok(InstallationStatus::FullyInstalled { .. }) => '+',
-            Ok(InstallationStatus::ConfigOnly { .. }) => '+',
-            Ok(InstallationStatus::BinaryOnly { .. }) => '-',
+        match self.install_statuses.get(harness.kind()) {
+            Some(InstallationStatus::FullyInstalled { .. }) => '+',
+            Some(InstallationStatus::ConfigOnly { .. }) => '+',
+            Some(InstallationStatus::BinaryOnly { .. }) => '-',
             _ => ' ',
         }
     }
@@ -219,12 +320,20 @@ impl App {
         if let Some(kind) = self.selected_harness() {
             let harness = Harness::new(kind);
 
-            if let Ok(names) = self.manager.list_profiles(&harness) {
-                for name in names {
-                    if let Ok(info) = self.manager.show_profile(&harness, &name) {
-                        self.profiles.push(info);
-                    }
-                }
+            if let Ok(infos) = self.manager.show_profiles(&harness, true) {
+                self.profiles = infos;
+            }
+
+            let mut tags: Vec<String> = self
+                .profiles
+                .iter()
+                .flat_map(|p| p.tags.iter().cloned())
+                .collect();
+            tags.sort();
+            tags.dedup();
+            self.available_tags = tags;
+            if let Some(tag) = &self.tag_filter {
+                self.profiles.retain(|p| p.has_tag(tag));
             }
 
             if !self.profiles.is_empty() {
@@ -267,10 +376,7 @@ impl App {
             Some(i) => (i + 1) % self.profiles.len(),
             None => 0,
         };
-        self.profile_state.select(Some(i));
-        self.profile_table_state.select(Some(i));
-        self.detail_scroll = 0;
-        self.update_detail_content_height();
+        self.select_profile(i);
     }
 
     fn prev_profile(&mut self) {
@@ -287,16 +393,69 @@ impl App {
             }
             None => 0,
         };
+        self.select_profile(i);
+    }
+
+    /// Moves the cards view selection with `step`, given the number of
+    /// columns the grid shows and the number of profiles.
+    #[cfg(feature = "tui-cards")]
+    fn move_card(&mut self, step: impl FnOnce(&mut widgets::CardGridState, usize, usize)) {
+        if self.profiles.is_empty() {
+            return;
+        }
+        let cols = self
+            .profile_area
+            .map_or(1, |area| widgets::CardGrid::columns(area.width));
+        let grid = &mut self.card_state.grid_state;
+        grid.selected = self.profile_state.selected().unwrap_or(0);
+        step(grid, cols, self.profiles.len());
+        let selected = grid.selected;
+        self.select_profile(selected);
+    }
+
+    /// Index of the profile drawn at (`column`, `row`) inside `area`, the
+    /// table or card grid, which may be past the last profile.
+    fn profile_index_at(&self, area: Rect, column: u16, row: u16) -> Option<usize> {
+        #[cfg(feature = "tui-cards")]
+        if self.view_mode == ViewMode::Cards {
+            return self.card_state.grid_state.card_at(area, column, row);
+        }
+        let _ = column;
+        // Table rows sit below the border and a header line.
+        Some(row.saturating_sub(area.y).saturating_sub(2) as usize)
+    }
+
+    fn select_profile(&mut self, i: usize) {
         self.profile_state.select(Some(i));
         self.profile_table_state.select(Some(i));
         self.detail_scroll = 0;
         self.update_detail_content_height();
     }
 
+    /// Moves the selection, or the detail scroll when the details pane has
+    /// focus, by `delta` pages. `isize::MIN`/`MAX` jump to the top/bottom.
+    fn page(&mut self, delta: isize) {
+        if self.active_pane == Pane::Details {
+            let page = self.detail_viewport_height().max(1) as usize;
+            let max = self.max_detail_scroll() as usize;
+            self.detail_scroll = paged_offset(self.detail_scroll as usize, max, page, delta) as u16;
+        } else if !self.profiles.is_empty() {
+            // Table rows sit below a header line inside the border.
+            let page = self
+                .profile_area
+                .map(|a| a.height.saturating_sub(3))
+                .unwrap_or(10)
+                .max(1) as usize;
+            let selected = self.profile_state.selected().unwrap_or(0);
+            let last = self.profiles.len() - 1;
+            self.select_profile(paged_offset(selected, last, page, delta));
+        }
+    }
+
     fn update_detail_content_height(&mut self) {
         self.detail_content_height = if let Some(idx) = self.profile_state.selected() {
             let profile = &self.profiles[idx];
-            let lines = widgets::render_profile_details(profile);
+            let lines = widgets::render_profile_details(profile, &self.theme);
             lines.len() as u16
         } else {
             0
@@ -308,16 +467,22 @@ impl App {
     }
 
     fn scroll_detail_down(&mut self) {
-        let viewport_height = self
-            .detail_area
-            .map(|a| a.height.saturating_sub(2))
-            .unwrap_or(10);
-        let max_scroll = self.detail_content_height.saturating_sub(viewport_height);
-        if self.detail_scroll < max_scroll {
+        if self.detail_scroll < self.max_detail_scroll() {
             self.detail_scroll = self.detail_scroll.saturating_add(1);
         }
     }
 
+    fn detail_viewport_height(&self) -> u16 {
+        self.detail_area
+            .map(|a| a.height.saturating_sub(2))
+            .unwrap_or(10)
+    }
+
+    fn max_detail_scroll(&self) -> u16 {
+        self.detail_content_height
+            .saturating_sub(self.detail_viewport_height())
+    }
+
     fn handle_mouse(&mut self, event: MouseEvent) {
         let pos = ratatui::layout::Position::new(event.column, event.row);
 
@@ -326,7 +491,7 @@ impl App {
                 if self.harness_area.is_some_and(|a| a.contains(pos)) {
                     let area = self.harness_area.unwrap();
 
-                    if self.view_mode == ViewMode::Dashboard {
+                    if self.view_mode != ViewMode::Legacy {
                         let inner_x = event.column.saturating_sub(area.x).saturating_sub(2);
                         let tab_width = 15;
                         let idx = (inner_x / tab_width) as usize;
@@ -345,9 +510,9 @@ impl App {
                 } else if self.profile_area.is_some_and(|a| a.contains(pos)) {
                     self.active_pane = Pane::Profiles;
                     let area = self.profile_area.unwrap();
-                    let inner_y = event.row.saturating_sub(area.y).saturating_sub(2);
-                    let idx = inner_y as usize;
-                    if idx < self.profiles.len() {
+                    if let Some(idx) = self.profile_index_at(area, event.column, event.row)
+                        && idx < self.profiles.len()
+                    {
                         self.profile_state.select(Some(idx));
                         self.profile_table_state.select(Some(idx));
                         self.detail_scroll = 0;
@@ -405,6 +570,7 @@ impl App {
     }
 
     fn edit_selected(&mut self) {
+        self.discard_pending_edit_revert();
         let Some(kind) = self.selected_harness() else {
             return;
         };
@@ -433,6 +599,17 @@ impl App {
         } else {
             self.manager.profile_path(&harness, &profile_name)
         };
+        if let Err(e) = self.manager.detach_hardlinks(&edit_path) {
+            self.status_message = Some(format!("Cannot unshare files before edit: {}", e));
+            return;
+        }
+        let snapshot = match EditSnapshot::take(&edit_path) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.status_message = Some(format!("Cannot back up before edit: {}", e));
+                return;
+            }
+        };
         let (program, args) = self.bridle_config.editor_command();
 
         let _ = restore_terminal_for_editor();
@@ -461,16 +638,98 @@ impl App {
         let _ = reinit_terminal_after_editor();
         self.needs_full_redraw = true;
 
+        let profile_name = profile.name.clone();
         match status {
             Ok(s) if s.success() => {
-                self.status_message = Some(format!("Edited '{}'", profile.name));
+                let invalid = snapshot.invalid_files();
+                if let Some((path, _)) = invalid.first() {
+                    let file = path.file_name().unwrap_or_default().to_string_lossy();
+                    self.status_message = Some(format!(
+                        "{} {} no longer parses — press u to revert",
+                        crate::display::WARNING_SYMBOL,
+                        file
+                    ));
+                    self.pending_edit_revert = Some(snapshot);
+                } else {
+                    let _ = snapshot.discard();
+                    self.status_message = Some(format!("Edited '{}'", profile_name));
+                }
+                self.refresh_profiles();
+            }
+            Ok(s) => {
+                let _ = snapshot.discard();
+                self.status_message = Some(format!("Editor exited: {}", s));
+            }
+            Err(e) => {
+                let _ = snapshot.discard();
+                self.status_message = Some(format!("Editor failed: {}", e));
+            }
+        }
+    }
+
+    fn open_selected_in_file_manager(&mut self) {
+        let Some(kind) = self.selected_harness() else {
+            return;
+        };
+        let Some(profile) = self
+            .profile_state
+            .selected()
+            .and_then(|i| self.profiles.get(i))
+        else {
+            self.status_message = Some("No profile selected".to_string());
+            return;
+        };
+        let Ok(profile_name) = ProfileName::new(&profile.name) else {
+            self.status_message = Some("Invalid profile name".to_string());
+            return;
+        };
+        let path = self
+            .manager
+            .profile_path(&Harness::new(kind), &profile_name);
+        let program = file_manager_command(std::env::consts::OS);
+
+        let _ = restore_terminal_for_editor();
+        let status = std::process::Command::new(program).arg(&path).status();
+        let _ = reinit_terminal_after_editor();
+        self.needs_full_redraw = true;
+
+        self.status_message = Some(match status {
+            Ok(_) => format!("Opened {}", path.display()),
+            Err(e) => format!("Cannot open file manager ({}): {}", program, e),
+        });
+    }
+
+    fn revert_last_edit(&mut self) {
+        let Some(snapshot) = self.pending_edit_revert.take() else {
+            self.status_message = Some("Nothing to revert".to_string());
+            return;
+        };
+        match snapshot.revert_invalid() {
+            Ok(reverted) => {
+                self.status_message = Some(format!("Reverted {} file(s)", reverted.len()));
                 self.refresh_profiles();
             }
-            Ok(s) => self.status_message = Some(format!("Editor exited: {}", s)),
-            Err(e) => self.status_message = Some(format!("Editor failed: {}", e)),
+            Err(e) => self.status_message = Some(format!("Revert failed: {}", e)),
         }
     }
 
+    /// Drops the pending revert, keeping the edited files as they are.
+    fn discard_pending_edit_revert(&mut self) {
+        if let Some(snapshot) = self.pending_edit_revert.take() {
+            let _ = snapshot.discard();
+        }
+    }
+
+    fn cycle_tag_filter(&mut self) {
+        self.tag_filter = next_tag_filter(self.tag_filter.as_deref(), &self.available_tags);
+        self.refresh_profiles();
+        self.status_message = Some(match &self.tag_filter {
+            Some(tag) => format!("Filter: tag '{}'", tag),
+            None if self.available_tags.is_empty() => "No tagged profiles".to_string(),
+            None => "Filter cleared".to_string(),
+        });
+    }
+
     fn toggle_expansion(&mut self) {
         let Some(idx) = self.profile_state.selected() else {
             return;
@@ -503,6 +762,13 @@ impl App {
             self.status_message = Some(format!("'{}' is already active", profile.name));
             return;
         }
+        if let Some(locked) = self.profiles.iter().find(|p| p.is_active && p.locked) {
+            self.status_message = Some(format!(
+                "'{}' is locked; unlock it or use `bridle profile switch --force`",
+                locked.name
+            ));
+            return;
+        }
 
         let harness = Harness::new(kind);
         let Ok(profile_name) = ProfileName::new(&profile.name) else {
@@ -510,13 +776,20 @@ impl App {
             return;
         };
 
-        match self
+        // Best-effort, as in `bridle profile switch`; this is what rollback restores.
+        let backed_up = self
             .manager
-            .switch_profile_with_resources(&harness, Some(&harness), &profile_name)
-        {
-            Ok(_) => {
+            .backup_current(&harness, Some(&harness))
+            .is_ok();
+        match self.manager.switch_profile_reporting(
+            &harness,
+            Some(&harness),
+            &profile_name,
+            &mut |_| {},
+        ) {
+            Ok(report) => {
                 self.bridle_config = BridleConfig::load().unwrap_or_default();
-                self.status_message = Some(format!("Switched to '{}'", profile.name));
+                self.status_message = Some(switch_summary(&report, backed_up, &profile.name));
                 let selected_idx = self.profile_state.selected();
                 self.refresh_profiles();
                 if let Some(idx) = selected_idx {
@@ -530,7 +803,15 @@ impl App {
         }
     }
 
-    fn handle_key(&mut self, key: KeyCode) {
+    fn handle_key(&mut self, event: KeyEvent) {
+        let key = event.code;
+        if self.show_onboarding {
+            self.dismiss_onboarding();
+            if key == KeyCode::Char('n') {
+                self.handle_normal_key(key);
+            }
+            return;
+        }
         if self.show_help {
             match key {
                 KeyCode::Char('?') | KeyCode::Esc | KeyCode::Char('q') => {
@@ -543,15 +824,32 @@ impl App {
 
         match self.input_mode {
             InputMode::Normal => self.handle_normal_key(key),
-            InputMode::CreatingProfile => self.handle_input_key(key),
+            InputMode::CreatingProfile => self.handle_input_key(event),
             InputMode::ConfirmingDelete => self.handle_confirm_delete_key(key),
         }
     }
 
+    /// Closes the welcome screen and records that in the config, so it is
+    /// only ever shown once.
+    fn dismiss_onboarding(&mut self) {
+        self.show_onboarding = false;
+        self.bridle_config.tui.onboarding_seen = true;
+        let mut config = BridleConfig::load().unwrap_or_default();
+        config.tui.onboarding_seen = true;
+        if let Err(e) = config.save() {
+            self.status_message = Some(format!("Could not save config: {e}"));
+        }
+    }
+
     fn handle_normal_key(&mut self, key: KeyCode) {
         match key {
-            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.discard_pending_edit_revert();
+                self.running = false;
+            }
             KeyCode::Char('?') => self.show_help = true,
+            KeyCode::Char('u') => self.revert_last_edit(),
+            KeyCode::Char('t') => self.cycle_tag_filter(),
             KeyCode::F(2) => {
                 self.view_mode.toggle();
                 self.status_message = Some(format!("View: {}", self.view_mode.name()));
@@ -576,7 +874,7 @@ impl App {
                     Pane::Details => self.scroll_detail_up(),
                 },
                 #[cfg(feature = "tui-cards")]
-                ViewMode::Cards => self.prev_profile(),
+                ViewMode::Cards => self.move_card(|grid, cols, _| grid.select_up(cols)),
             },
             KeyCode::Down | KeyCode::Char('j') => match self.view_mode {
                 ViewMode::Dashboard => {
@@ -592,17 +890,27 @@ impl App {
                     Pane::Details => self.scroll_detail_down(),
                 },
                 #[cfg(feature = "tui-cards")]
-                ViewMode::Cards => self.next_profile(),
-            },
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.view_mode == ViewMode::Dashboard {
-                    self.prev_harness();
+                ViewMode::Cards => {
+                    self.move_card(|grid, cols, total| grid.select_down(cols, total));
                 }
+            },
+            KeyCode::PageDown => self.page(1),
+            KeyCode::PageUp => self.page(-1),
+            KeyCode::Char('g') => self.page(isize::MIN),
+            KeyCode::Char('G') => self.page(isize::MAX),
+            #[cfg(feature = "tui-cards")]
+            KeyCode::Left | KeyCode::Char('h') if self.view_mode == ViewMode::Cards => {
+                self.move_card(|grid, _, _| grid.select_prev());
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.view_mode == ViewMode::Dashboard {
-                    self.next_harness();
-                }
+            #[cfg(feature = "tui-cards")]
+            KeyCode::Right | KeyCode::Char('l') if self.view_mode == ViewMode::Cards => {
+                self.move_card(|grid, _, total| grid.select_next(total));
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.view_mode == ViewMode::Dashboard => {
+                self.prev_harness();
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.view_mode == ViewMode::Dashboard => {
+                self.next_harness();
             }
             KeyCode::Enter => match self.view_mode {
                 ViewMode::Dashboard => {
@@ -622,13 +930,12 @@ impl App {
                     self.switch_to_selected();
                 }
             },
-            KeyCode::Char(' ') => {
-                if self.active_pane == Pane::Profiles {
-                    self.toggle_expansion();
-                }
+            KeyCode::Char(' ') if self.active_pane == Pane::Profiles => {
+                self.toggle_expansion();
             }
             KeyCode::Char('r') => {
                 self.sync_active_profiles();
+                self.install_statuses = InstallStatuses::probe(&self.harnesses);
                 self.refresh_profiles();
                 self.status_message = Some("Synced and refreshed".to_string());
             }
@@ -638,33 +945,36 @@ impl App {
                     return;
                 };
 
-                let harness = Harness::new(kind);
-                match harness.installation_status() {
-                    Ok(InstallationStatus::FullyInstalled { .. }) => {
-                        self.reset_create_profile_state();
-                    }
-                    _ => {
-                        self.status_message =
-                            Some("Harness not installed — profiles disabled".to_string());
-                    }
+                if self.install_statuses.is_fully_installed(kind) {
+                    self.reset_create_profile_state();
+                } else {
+                    self.status_message =
+                        Some("Harness not installed — profiles disabled".to_string());
                 }
             }
             KeyCode::Char('d') => {
-                if (matches!(self.view_mode, ViewMode::Dashboard)
-                    || self.active_pane == Pane::Profiles)
+                if (self.view_mode != ViewMode::Legacy || self.active_pane == Pane::Profiles)
                     && let Some(idx) = self.profile_state.selected()
                     && let Some(profile) = self.profiles.get(idx)
                 {
-                    self.input_buffer = profile.name.clone();
-                    self.input_mode = InputMode::ConfirmingDelete;
+                    if profile.locked {
+                        self.status_message =
+                            Some(format!("'{}' is locked and can't be deleted", profile.name));
+                    } else {
+                        self.input_buffer = profile.name.clone();
+                        self.input_mode = InputMode::ConfirmingDelete;
+                    }
                 }
             }
-            KeyCode::Char('e') => {
-                if matches!(self.view_mode, ViewMode::Dashboard)
-                    || self.active_pane == Pane::Profiles
-                {
-                    self.edit_selected();
-                }
+            KeyCode::Char('e')
+                if self.view_mode != ViewMode::Legacy || self.active_pane == Pane::Profiles =>
+            {
+                self.edit_selected();
+            }
+            KeyCode::Char('o')
+                if self.view_mode != ViewMode::Legacy || self.active_pane == Pane::Profiles =>
+            {
+                self.open_selected_in_file_manager();
             }
             KeyCode::Char('f') => {
                 if let Some(harness_kind) = self.selected_harness() {
@@ -684,8 +994,8 @@ impl App {
         }
     }
 
-    fn handle_input_key(&mut self, key: KeyCode) {
-        match key {
+    fn handle_input_key(&mut self, key: KeyEvent) {
+        match key.code {
             KeyCode::Enter => self.create_profile_from_input(),
             KeyCode::Esc => self.cancel_create_profile(),
             KeyCode::Tab => {
@@ -694,15 +1004,19 @@ impl App {
             KeyCode::Char(' ') if self.create_profile_focused_on_checkbox => {
                 self.create_profile_copy_current = !self.create_profile_copy_current;
             }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-                self.clear_create_profile_error();
-            }
-            KeyCode::Char(c) => {
-                self.input_buffer.push(c);
-                self.clear_create_profile_error();
+            _ => {
+                if line_edit::edit(&mut self.input_buffer, &mut self.input_cursor, key) {
+                    self.clear_create_profile_error();
+                }
             }
-            _ => {}
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.input_mode == InputMode::CreatingProfile && !self.create_profile_focused_on_checkbox
+        {
+            line_edit::insert_str(&mut self.input_buffer, &mut self.input_cursor, text);
+            self.clear_create_profile_error();
         }
     }
 
@@ -725,22 +1039,29 @@ impl App {
     fn reset_create_profile_state(&mut self) {
         self.input_mode = InputMode::CreatingProfile;
         self.input_buffer.clear();
+        self.input_cursor = 0;
         self.create_profile_copy_current = true;
         self.create_profile_focused_on_checkbox = false;
-        self.create_profile_error = None;
+        self.clear_create_profile_error();
     }
 
     fn clear_create_profile_error(&mut self) {
         self.create_profile_error = None;
+        self.create_profile_suggestion = None;
     }
 
     fn cancel_create_profile(&mut self) {
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
+        self.input_cursor = 0;
         self.clear_create_profile_error();
     }
 
     fn create_profile_from_input(&mut self) {
+        if let Some(suggestion) = self.create_profile_suggestion.take() {
+            self.input_buffer = suggestion;
+            self.input_cursor = self.input_buffer.chars().count();
+        }
         let name = self.input_buffer.trim().to_string();
         if name.is_empty() {
             self.create_profile_error = Some("Profile name cannot be empty".to_string());
@@ -754,20 +1075,28 @@ impl App {
 
         let harness = Harness::new(kind);
 
-        match harness.installation_status() {
-            Ok(InstallationStatus::FullyInstalled { .. }) => {}
-            _ => {
-                self.status_message = Some("Harness not installed — profiles disabled".to_string());
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
-                return;
-            }
+        if !self.install_statuses.is_fully_installed(kind) {
+            self.status_message = Some("Harness not installed — profiles disabled".to_string());
+            self.input_mode = InputMode::Normal;
+            self.input_buffer.clear();
+            return;
         }
 
         let profile_name = match ProfileName::new(&name) {
             Ok(pn) => pn,
             Err(_) => {
-                self.create_profile_error = Some("Invalid profile name".to_string());
+                match ProfileName::sanitize(&name) {
+                    Ok(sanitized) => {
+                        self.create_profile_error = Some(format!(
+                            "Invalid name — press Enter to use sanitized '{}'",
+                            sanitized
+                        ));
+                        self.create_profile_suggestion = Some(sanitized.to_string());
+                    }
+                    Err(_) => {
+                        self.create_profile_error = Some("Invalid profile name".to_string());
+                    }
+                }
                 return;
             }
         };
@@ -781,7 +1110,7 @@ impl App {
 
         match result {
             Ok(_) => {
-                self.status_message = Some(format!("Created profile '{}'", name));
+                self.status_message = Some(format!("Created profile '{}'", profile_name));
                 self.refresh_profiles();
                 self.cancel_create_profile();
             }
@@ -795,7 +1124,12 @@ impl App {
 fn init_terminal() -> io::Result<Tui> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
@@ -804,6 +1138,7 @@ fn restore_terminal(terminal: &mut Tui) -> io::Result<()> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
+        DisableBracketedPaste,
         DisableMouseCapture,
         LeaveAlternateScreen
     )?;
@@ -813,17 +1148,44 @@ fn restore_terminal(terminal: &mut Tui) -> io::Result<()> {
 
 fn restore_terminal_for_editor() -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
+    execute!(
+        io::stdout(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
     Ok(())
 }
 
+/// Offset `delta` pages of `page` rows away from `current`, clamped to
+/// `0..=last`.
+fn paged_offset(current: usize, last: usize, page: usize, delta: isize) -> usize {
+    let step = page.saturating_mul(delta.unsigned_abs());
+    if delta < 0 {
+        current.saturating_sub(step)
+    } else {
+        current.saturating_add(step).min(last)
+    }
+}
+
+/// Program that opens a directory in the desktop file manager on `os`
+/// (a [`std::env::consts::OS`] value).
+fn file_manager_command(os: &str) -> &'static str {
+    match os {
+        "macos" => "open",
+        "windows" => "explorer",
+        _ => "xdg-open",
+    }
+}
+
 fn reinit_terminal_after_editor() -> io::Result<()> {
     enable_raw_mode()?;
     execute!(
         io::stdout(),
         EnterAlternateScreen,
         crossterm::terminal::Clear(ClearType::All),
-        EnableMouseCapture
+        EnableMouseCapture,
+        EnableBracketedPaste
     )?;
     Ok(())
 }
@@ -833,12 +1195,48 @@ fn ui(frame: &mut Frame, app: &mut App) {
         ViewMode::Legacy => render_legacy_view(frame, app),
         ViewMode::Dashboard => render_dashboard_view(frame, app),
         #[cfg(feature = "tui-cards")]
-        ViewMode::Cards => render_dashboard_view(frame, app), // TODO: implement cards view
+        ViewMode::Cards => render_dashboard_view(frame, app),
     }
 
     if app.show_help {
-        render_help_modal(frame, frame.area(), app.view_mode);
+        render_help_modal(frame, frame.area(), app.view_mode, &app.theme);
     }
+    if app.show_onboarding {
+        render_modal(
+            frame,
+            frame.area(),
+            " Welcome to bridle ",
+            onboarding_lines(),
+            64,
+            &app.theme,
+        );
+    }
+}
+
+/// Whether to greet the user with the welcome screen: nothing is set up
+/// yet and it hasn't been dismissed before.
+fn needs_onboarding(config: &BridleConfig, has_profiles: bool) -> bool {
+    !config.tui.onboarding_seen && config.active.is_empty() && !has_profiles
+}
+
+fn onboarding_lines() -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    vec![
+        Line::from("bridle keeps named profiles of your coding agents' configs"),
+        Line::from("(Claude Code, OpenCode, Goose, ...) and switches them."),
+        Line::from(""),
+        Line::from(Span::styled("Getting started", bold)),
+        Line::from("  n         Create a profile for the selected harness"),
+        Line::from("  Enter     Switch to the selected profile"),
+        Line::from("  ?         Show all keys"),
+        Line::from(""),
+        Line::from(Span::styled("Harness markers", bold)),
+        Line::from("  *         Has an active profile"),
+        Line::from("  +         Installed"),
+        Line::from("  -         Binary found, but no config yet"),
+        Line::from(""),
+        Line::from("Press n to create your first profile, or any key to close."),
+    ]
 }
 
 fn render_legacy_view(frame: &mut Frame, app: &mut App) {
@@ -873,16 +1271,7 @@ fn render_dashboard_view(frame: &mut Frame, app: &mut App) {
     app.harness_area = Some(chunks[0]);
     render_harness_tabs(frame, app, chunks[0]);
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-        .split(chunks[1]);
-
-    app.profile_area = Some(content_chunks[0]);
-    app.detail_area = Some(content_chunks[1]);
-    render_profile_table(frame, app, content_chunks[0]);
-    render_detail_pane(frame, app, content_chunks[1]);
-
+    render_profiles_content(frame, app, chunks[1]);
     render_status_bar(frame, app, chunks[2]);
 
     if app.input_mode == InputMode::CreatingProfile {
@@ -893,6 +1282,33 @@ fn render_dashboard_view(frame: &mut Frame, app: &mut App) {
     }
 }
 
+/// The profile table and details side by side, or the card grid in the
+/// cards view.
+fn render_profiles_content(frame: &mut Frame, app: &mut App, area: Rect) {
+    #[cfg(feature = "tui-cards")]
+    if app.view_mode == ViewMode::Cards {
+        app.profile_area = Some(area);
+        app.detail_area = None;
+        if app.profiles.is_empty() {
+            return render_profile_table(frame, app, area);
+        }
+        app.card_state.grid_state.selected = app.profile_state.selected().unwrap_or(0);
+        let cards = views::CardView::new(&app.profiles).theme(app.theme);
+        frame.render_stateful_widget(cards, area, &mut app.card_state);
+        return;
+    }
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    app.profile_area = Some(content_chunks[0]);
+    app.detail_area = Some(content_chunks[1]);
+    render_profile_table(frame, app, content_chunks[0]);
+    render_detail_pane(frame, app, content_chunks[1]);
+}
+
 fn render_confirm_delete_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
     let popup_width = 50.min(area.width.saturating_sub(4));
@@ -977,8 +1393,17 @@ fn create_profile_popup_chunks(app: &App, inner_area: Rect) -> Vec<Rect> {
         .to_vec()
 }
 
+/// Title of the name field, naming what the new profile will start from.
+fn create_profile_title(copy_current: bool) -> &'static str {
+    if copy_current {
+        " Profile Name — from current config "
+    } else {
+        " Profile Name — empty "
+    }
+}
+
 fn render_create_profile_input_field(frame: &mut Frame, app: &App, area: Rect) {
-    let input_text = format!("{}█", app.input_buffer);
+    let input_text = line_edit::cursor_line(&app.input_buffer, app.input_cursor);
     let input_style = if app.create_profile_focused_on_checkbox {
         Style::default().fg(Color::DarkGray)
     } else {
@@ -988,7 +1413,7 @@ fn render_create_profile_input_field(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Profile Name ")
+                .title(create_profile_title(app.create_profile_copy_current))
                 .border_style(input_style),
         )
         .style(Style::default().fg(Color::White));
@@ -1049,25 +1474,29 @@ fn render_profile_table(frame: &mut Frame, app: &mut App, area: Rect) {
         let Some(kind) = app.selected_harness() else {
             let widget =
                 widgets::EmptyState::new("Profiles", vec!["No harness selected".to_string()])
-                    .focused(app.active_pane == Pane::Profiles);
+                    .focused(app.active_pane == Pane::Profiles)
+                    .theme(app.theme);
             frame.render_widget(widget, area);
             return;
         };
 
-        let harness = Harness::new(kind);
-        let status = harness
-            .installation_status()
-            .unwrap_or(InstallationStatus::NotInstalled);
+        let status = app.install_statuses.status_or_missing(kind);
         let lines = crate::harness::get_empty_state_message(kind, status, false);
 
-        let widget =
-            widgets::EmptyState::new("Profiles", lines).focused(app.active_pane == Pane::Profiles);
+        let widget = widgets::EmptyState::new("Profiles", lines)
+            .focused(app.active_pane == Pane::Profiles)
+            .theme(app.theme);
         frame.render_widget(widget, area);
         return;
     }
 
-    let table = ProfileTable::new(&app.profiles).focused(app.active_pane == Pane::Profiles);
+    let table = ProfileTable::new(&app.profiles)
+        .focused(app.active_pane == Pane::Profiles)
+        .theme(app.theme);
     frame.render_stateful_widget(table, area, &mut app.profile_table_state);
+    let selected = app.profile_table_state.selected().unwrap_or(0);
+    // The header row takes one line of the track.
+    widgets::render_vertical_scrollbar(area, frame.buffer_mut(), app.profiles.len() + 1, selected);
 }
 
 fn render_detail_pane(frame: &mut Frame, app: &App, area: Rect) {
@@ -1078,12 +1507,18 @@ fn render_detail_pane(frame: &mut Frame, app: &App, area: Rect) {
 
     let detail = DetailPane::new(selected_profile)
         .focused(app.active_pane == Pane::Details)
-        .scroll(app.detail_scroll);
+        .scroll(app.detail_scroll)
+        .theme(app.theme);
     frame.render_widget(detail, area);
 }
 
 fn render_harness_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let mut tabs = HarnessTabs::new(&app.harnesses, app.harness_state.selected().unwrap_or(0));
+    let mut tabs = HarnessTabs::new(
+        &app.harnesses,
+        app.harness_state.selected().unwrap_or(0),
+        |kind| app.install_statuses.get(kind),
+    )
+    .theme(app.theme);
 
     for kind in &app.harnesses {
         let harness = Harness::new(*kind);
@@ -1096,12 +1531,7 @@ fn render_harness_tabs(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_harness_pane(frame: &mut Frame, app: &mut App, area: Rect) {
-    let is_active = app.active_pane == Pane::Harnesses;
-    let border_style = if is_active {
-        Style::default().fg(Color::Cyan)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let border_style = app.theme.border(app.active_pane == Pane::Harnesses);
 
     let items: Vec<ListItem> = app
         .harnesses
@@ -1109,7 +1539,7 @@ fn render_harness_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         .map(|kind| {
             let harness = Harness::new(*kind);
             let indicator = app.harness_status_indicator(&harness);
-            let installed = harness.is_installed();
+            let installed = app.install_statuses.is_installed(*kind);
             let style = if installed {
                 Style::default()
             } else {
@@ -1137,11 +1567,11 @@ fn render_harness_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     frame.render_stateful_widget(list, area, &mut app.harness_state);
 }
 
-fn render_profile_compact(profile: &ProfileInfo) -> Line<'static> {
+fn render_profile_compact(profile: &ProfileInfo, theme: &Theme) -> Line<'static> {
     let active_marker = if profile.is_active { "● " } else { "  " };
 
     let mut summary_parts = Vec::new();
-    if let Some(model) = &profile.model {
+    if let Some(model) = profile.model.as_deref() {
         let short_model = model
             .split('/')
             .next_back()
@@ -1163,30 +1593,29 @@ fn render_profile_compact(profile: &ProfileInfo) -> Line<'static> {
     };
 
     let style = if profile.is_active {
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD)
+        theme.profile_active()
     } else {
         Style::default()
     };
     Line::styled(
-        format!("{}{}{}", active_marker, profile.name, summary),
+        format!(
+            "{}{}{}",
+            active_marker,
+            crate::display::profile_label(profile),
+            summary
+        ),
         style,
     )
 }
 
-fn render_profile_expanded(profile: &ProfileInfo) -> Vec<Line<'static>> {
+fn render_profile_expanded(profile: &ProfileInfo, theme: &Theme) -> Vec<Line<'static>> {
     let nodes = crate::display::profile_to_nodes(profile);
-    crate::display::nodes_to_lines(&nodes)
+    crate::display::nodes_to_lines(&nodes, theme)
 }
 
 fn render_profile_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     let is_active = app.active_pane == Pane::Profiles;
-    let border_style = if is_active {
-        Style::default().fg(Color::Cyan)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let border_style = app.theme.border(is_active);
 
     let (list_area, input_area) = if app.input_mode == InputMode::CreatingProfile {
         let chunks = Layout::default()
@@ -1202,18 +1631,18 @@ fn render_profile_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         let Some(kind) = app.selected_harness() else {
             let widget =
                 widgets::EmptyState::new("Profiles", vec!["No harness selected".to_string()])
-                    .focused(is_active);
+                    .focused(is_active)
+                    .theme(app.theme);
             frame.render_widget(widget, area);
             return;
         };
 
-        let harness = Harness::new(kind);
-        let status = harness
-            .installation_status()
-            .unwrap_or(InstallationStatus::NotInstalled);
+        let status = app.install_statuses.status_or_missing(kind);
         let lines = crate::harness::get_empty_state_message(kind, status, false);
 
-        let widget = widgets::EmptyState::new("Profiles", lines).focused(is_active);
+        let widget = widgets::EmptyState::new("Profiles", lines)
+            .focused(is_active)
+            .theme(app.theme);
         frame.render_widget(widget, area);
         return;
     }
@@ -1225,9 +1654,9 @@ fn render_profile_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         .map(|(idx, profile)| {
             let is_expanded = app.expanded_profile == Some(idx);
             if is_expanded {
-                ListItem::new(Text::from(render_profile_expanded(profile)))
+                ListItem::new(Text::from(render_profile_expanded(profile, &app.theme)))
             } else {
-                ListItem::new(render_profile_compact(profile))
+                ListItem::new(render_profile_compact(profile, &app.theme))
             }
         })
         .collect();
@@ -1252,9 +1681,15 @@ fn render_profile_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, list_area, &mut app.profile_state);
+    widgets::render_vertical_scrollbar(
+        list_area,
+        frame.buffer_mut(),
+        app.profiles.len(),
+        app.profile_state.selected().unwrap_or(0),
+    );
 
     if let Some(input_area) = input_area {
-        let input_text = format!("{}█", app.input_buffer);
+        let input_text = line_edit::cursor_line(&app.input_buffer, app.input_cursor);
         let input = Paragraph::new(input_text)
             .block(
                 Block::default()
@@ -1267,7 +1702,64 @@ fn render_profile_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
-fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode) {
+/// The tag after `current` in `tags`, wrapping back to no filter after the last one.
+fn next_tag_filter(current: Option<&str>, tags: &[String]) -> Option<String> {
+    let next = match current {
+        None => 0,
+        Some(tag) => tags.iter().position(|t| t == tag).map_or(0, |i| i + 1),
+    };
+    tags.get(next).cloned()
+}
+
+/// Status line after a switch, e.g. "Backed up, saved 'work', switched to 'home'".
+fn switch_summary(report: &SwitchReport, backed_up: bool, name: &str) -> String {
+    let mut parts = Vec::new();
+    if backed_up || report.backed_up.is_some() {
+        parts.push("backed up".to_string());
+    }
+    if let Some(outgoing) = &report.saved_outgoing {
+        parts.push(format!("saved '{}'", outgoing));
+    }
+    parts.push(format!("switched to '{}'", name));
+    let mut summary = parts.join(", ");
+    summary[..1].make_ascii_uppercase();
+    if let Some(warning) = report.warnings.first() {
+        summary.push_str(&format!(" ({})", warning));
+    }
+    summary
+}
+
+fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode, theme: &Theme) {
+    render_modal(frame, area, " Help ", help_lines(view_mode), 40, theme);
+}
+
+/// Draws `lines` in a bordered box of `width` columns centered in `area`.
+fn render_modal(
+    frame: &mut Frame,
+    area: Rect,
+    title: &'static str,
+    lines: Vec<Line<'static>>,
+    width: u16,
+    theme: &Theme,
+) {
+    let height = lines.len() as u16 + 4;
+    let x = area.width.saturating_sub(width) / 2;
+    let y = area.height.saturating_sub(height) / 2;
+    let modal_area = Rect::new(x, y, width.min(area.width), height.min(area.height));
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(theme.border(true))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+}
+
+fn help_lines(view_mode: views::ViewMode) -> Vec<Line<'static>> {
     let mut help_text = vec![Line::from(vec![Span::styled(
         "Navigation",
         Style::default().add_modifier(Modifier::BOLD),
@@ -1278,7 +1770,6 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         help_text.extend([
             Line::from("  ←/→       Move left/right"),
             Line::from("  ↑/↓       Move up/down"),
-            Line::from("  Space     View details"),
         ]);
     } else {
         help_text.extend([
@@ -1299,6 +1790,8 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
     }
 
     help_text.extend([
+        Line::from("  PgUp/PgDn Page up/down"),
+        Line::from("  g / G     Jump to top/bottom"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Actions",
@@ -1308,6 +1801,9 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         Line::from("  n         New profile"),
         Line::from("  d         Delete profile"),
         Line::from("  e         Edit profile"),
+        Line::from("  o         Open profile folder"),
+        Line::from("  u         Revert a broken edit"),
+        Line::from("  t         Filter by tag"),
         Line::from("  f         Set default harness"),
         Line::from("  r         Refresh"),
         Line::from(""),
@@ -1319,6 +1815,22 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         Line::from("  +         Has config (not tracked)"),
         Line::from("  -         Binary only (no config)"),
         Line::from("  ○         Not installed"),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "MCP / Resources",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+    ]);
+    help_text.extend(
+        [
+            (crate::display::MCP_ENABLED_SYMBOL, "MCP server enabled"),
+            (crate::display::MCP_DISABLED_SYMBOL, "MCP server disabled"),
+            (crate::display::LINKED_SUFFIX, "From a shared library"),
+            (crate::display::WARNING_SYMBOL, "Extraction error/warning"),
+        ]
+        .map(|(symbol, meaning)| Line::from(format!("  {symbol:<10}{meaning}"))),
+    );
+    help_text.extend([
         Line::from(""),
         Line::from(vec![Span::styled(
             "General",
@@ -1327,38 +1839,20 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         Line::from("  ?         Toggle help"),
         Line::from("  q / Esc   Quit"),
     ]);
-
-    let width = 40;
-    let height = help_text.len() as u16 + 4;
-    let x = area.width.saturating_sub(width) / 2;
-    let y = area.height.saturating_sub(height) / 2;
-    let modal_area = Rect::new(x, y, width.min(area.width), height.min(area.height));
-
-    frame.render_widget(Clear, modal_area);
-
-    let help_block = Block::default()
-        .title(" Help ")
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
-
-    let help_paragraph = Paragraph::new(help_text).block(help_block);
-    frame.render_widget(help_paragraph, modal_area);
+    help_text
 }
 
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let harness_status = app.selected_harness().map(|kind| {
-        let harness = Harness::new(kind);
-        match harness.installation_status() {
-            Ok(status) => StatusBar::installation_status_text(&status),
-            Err(_) => "Unknown",
-        }
+        app.install_statuses
+            .get(kind)
+            .map_or("Unknown", StatusBar::installation_status_text)
     });
 
     let status_bar = StatusBar::new(app.view_mode)
         .message(app.status_message.as_deref())
-        .harness_status(harness_status);
+        .harness_status(harness_status)
+        .theme(app.theme);
     frame.render_widget(status_bar, area);
 }
 
@@ -1368,7 +1862,12 @@ pub fn run() -> Result<(), Error> {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
+        let _ = execute!(
+            io::stdout(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        );
         hook(info);
     }));
 
@@ -1393,9 +1892,10 @@ pub fn run() -> Result<(), Error> {
                     if is_ctrl_c {
                         app.running = false;
                     } else {
-                        app.handle_key(key.code);
+                        app.handle_key(key);
                     }
                 }
+                Event::Paste(text) => app.handle_paste(&text),
                 Event::Mouse(mouse) => {
                     app.handle_mouse(mouse);
                 }
@@ -1408,3 +1908,140 @@ pub fn run() -> Result<(), Error> {
     restore_terminal(&mut terminal).map_err(Error::Io)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn onboarding_shows_only_on_a_fresh_setup_until_dismissed() {
+        let mut config = BridleConfig::default();
+        assert!(needs_onboarding(&config, false));
+        assert!(!needs_onboarding(&config, true));
+
+        config.set_active_profile("opencode", "work");
+        assert!(!needs_onboarding(&config, false));
+
+        let mut config = BridleConfig::default();
+        config.tui.onboarding_seen = true;
+        assert!(!needs_onboarding(&config, false));
+    }
+
+    #[test]
+    fn create_prompt_names_the_starting_point() {
+        assert!(create_profile_title(true).contains("from current config"));
+        assert!(create_profile_title(false).contains("empty"));
+    }
+
+    #[test]
+    fn install_statuses_probe_once_until_refreshed() {
+        let probes = Cell::new(0);
+        let mock = |kind: HarnessKind| {
+            probes.set(probes.get() + 1);
+            (kind == HarnessKind::Goose).then_some(InstallationStatus::NotInstalled)
+        };
+        let kinds = [HarnessKind::Goose, HarnessKind::Crush];
+
+        let mut statuses = InstallStatuses::probe_with(&kinds, mock);
+        for _ in 0..10 {
+            assert!(!statuses.is_installed(HarnessKind::Goose));
+            assert!(statuses.get(HarnessKind::Crush).is_none());
+        }
+        assert_eq!(probes.get(), 2);
+
+        statuses = InstallStatuses::probe_with(&kinds, mock);
+        assert_eq!(probes.get(), 4);
+        assert_eq!(
+            statuses.status_or_missing(HarnessKind::Crush),
+            InstallationStatus::NotInstalled
+        );
+    }
+
+    #[test]
+    fn switch_summary_mentions_backup_and_outgoing_profile() {
+        let report = SwitchReport {
+            saved_outgoing: Some(ProfileName::new("work").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            switch_summary(&report, true, "home"),
+            "Backed up, saved 'work', switched to 'home'"
+        );
+        assert_eq!(
+            switch_summary(&SwitchReport::default(), false, "home"),
+            "Switched to 'home'"
+        );
+    }
+
+    #[test]
+    fn help_legend_lists_mcp_symbols() {
+        let text: Vec<String> = help_lines(ViewMode::default())
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        for symbol in [
+            crate::display::MCP_ENABLED_SYMBOL,
+            crate::display::MCP_DISABLED_SYMBOL,
+        ] {
+            assert!(
+                text.iter().any(|l| l.trim_start().starts_with(symbol)),
+                "{symbol} missing from help"
+            );
+        }
+        assert!(text.iter().any(|l| l == "MCP / Resources"));
+    }
+
+    #[cfg(feature = "tui-cards")]
+    #[test]
+    fn clicks_in_the_card_grid_pick_the_card_under_them() {
+        let state = widgets::CardGridState {
+            selected: 0,
+            scroll_offset: 1,
+        };
+        let area = Rect::new(2, 4, 70, 30);
+        assert_eq!(widgets::CardGrid::columns(area.width), 2);
+        assert_eq!(state.card_at(area, 3, 5), Some(2));
+        assert_eq!(state.card_at(area, 40, 14), Some(5));
+        assert_eq!(state.card_at(area, 1, 5), None);
+        assert_eq!(state.card_at(area, 66, 5), None);
+    }
+
+    #[test]
+    fn paged_offset_clamps_to_bounds() {
+        assert_eq!(paged_offset(0, 30, 10, 1), 10);
+        assert_eq!(paged_offset(25, 30, 10, 1), 30);
+        assert_eq!(paged_offset(5, 30, 10, -1), 0);
+        assert_eq!(paged_offset(12, 30, 10, isize::MAX), 30);
+        assert_eq!(paged_offset(12, 30, 10, isize::MIN), 0);
+    }
+
+    #[test]
+    fn file_manager_command_matches_platform() {
+        assert_eq!(file_manager_command("macos"), "open");
+        assert_eq!(file_manager_command("windows"), "explorer");
+        assert_eq!(file_manager_command("linux"), "xdg-open");
+        assert_eq!(file_manager_command("freebsd"), "xdg-open");
+    }
+
+    #[test]
+    fn tag_filter_cycles_through_tags_then_clears() {
+        let tags = vec!["personal".to_string(), "work".to_string()];
+
+        let first = next_tag_filter(None, &tags);
+        assert_eq!(first.as_deref(), Some("personal"));
+        let second = next_tag_filter(first.as_deref(), &tags);
+        assert_eq!(second.as_deref(), Some("work"));
+        assert_eq!(next_tag_filter(second.as_deref(), &tags), None);
+    }
+
+    #[test]
+    fn tag_filter_resets_when_tag_disappears() {
+        let tags = vec!["work".to_string()];
+        assert_eq!(
+            next_tag_filter(Some("gone"), &tags).as_deref(),
+            Some("work")
+        );
+        assert_eq!(next_tag_filter(None, &[]), None);
+    }
+}
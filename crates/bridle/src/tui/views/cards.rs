@@ -7,8 +7,10 @@ use ratatui::{
 };
 
 use crate::config::ProfileInfo;
+use crate::display::Theme;
 use crate::tui::widgets::{CardGrid, CardGridState};
 
+#[derive(Debug)]
 pub struct CardViewState {
     pub grid_state: CardGridState,
 }
@@ -29,11 +31,20 @@ impl Default for CardViewState {
 
 pub struct CardView<'a> {
     profiles: &'a [ProfileInfo],
+    theme: Theme,
 }
 
 impl<'a> CardView<'a> {
     pub fn new(profiles: &'a [ProfileInfo]) -> Self {
-        Self { profiles }
+        Self {
+            profiles,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
     }
 }
 
@@ -43,10 +54,9 @@ impl StatefulWidget for CardView<'_> {
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let chunks = Layout::vertical([Constraint::Min(0)]).split(area);
 
-        CardGrid::new(self.profiles).show_new_card(true).render(
-            chunks[0],
-            buf,
-            &mut state.grid_state,
-        );
+        CardGrid::new(self.profiles)
+            .show_new_card(true)
+            .theme(self.theme)
+            .render(chunks[0], buf, &mut state.grid_state);
     }
 }
@@ -1,13 +1,18 @@
-mod dashboard;
+//! TUI view modes.
+//!
+//! The base build and the `tui-cards` build share one `ViewMode`; the `Cards`
+//! variant is purely additive. Both states are exercised in CI:
+//!
+//! ```text
+//! cargo test
+//! cargo test --features tui-cards
+//! ```
 
 #[cfg(feature = "tui-cards")]
 mod cards;
 
-#[allow(unused_imports)]
-pub use dashboard::DashboardView;
-
 #[cfg(feature = "tui-cards")]
-pub use cards::CardView;
+pub use cards::{CardView, CardViewState};
 
 use crate::config::ViewPreference;
 
@@ -21,26 +26,28 @@ pub enum ViewMode {
 }
 
 impl ViewMode {
-    #[allow(dead_code)]
+    /// Every available mode, in toggle order.
+    pub const ALL: &'static [ViewMode] = &[
+        ViewMode::Legacy,
+        ViewMode::Dashboard,
+        #[cfg(feature = "tui-cards")]
+        ViewMode::Cards,
+    ];
+
     pub fn from_config(pref: ViewPreference) -> Self {
         match pref {
             ViewPreference::Legacy => ViewMode::Legacy,
             ViewPreference::Dashboard => ViewMode::Dashboard,
             #[cfg(feature = "tui-cards")]
             ViewPreference::Cards => ViewMode::Cards,
+            #[cfg(not(feature = "tui-cards"))]
+            ViewPreference::Cards => ViewMode::Dashboard,
         }
     }
 
     pub fn toggle(&mut self) {
-        *self = match self {
-            ViewMode::Legacy => ViewMode::Dashboard,
-            #[cfg(feature = "tui-cards")]
-            ViewMode::Dashboard => ViewMode::Cards,
-            #[cfg(not(feature = "tui-cards"))]
-            ViewMode::Dashboard => ViewMode::Legacy,
-            #[cfg(feature = "tui-cards")]
-            ViewMode::Cards => ViewMode::Legacy,
-        };
+        let idx = Self::ALL.iter().position(|m| m == self).unwrap_or(0);
+        *self = Self::ALL[(idx + 1) % Self::ALL.len()];
     }
 
     pub fn name(&self) -> &'static str {
@@ -52,3 +59,51 @@ impl ViewMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_cycles_through_all_modes() {
+        let mut mode = ViewMode::Legacy;
+        let mut seen = vec![mode];
+        for _ in 1..ViewMode::ALL.len() {
+            mode.toggle();
+            seen.push(mode);
+        }
+        assert_eq!(seen, ViewMode::ALL);
+
+        mode.toggle();
+        assert_eq!(mode, ViewMode::Legacy);
+    }
+
+    #[cfg(not(feature = "tui-cards"))]
+    #[test]
+    fn toggle_without_cards_alternates() {
+        let mut mode = ViewMode::Dashboard;
+        mode.toggle();
+        assert_eq!(mode, ViewMode::Legacy);
+        mode.toggle();
+        assert_eq!(mode, ViewMode::Dashboard);
+    }
+
+    #[cfg(feature = "tui-cards")]
+    #[test]
+    fn toggle_with_cards_visits_cards_after_dashboard() {
+        let mut mode = ViewMode::Dashboard;
+        mode.toggle();
+        assert_eq!(mode, ViewMode::Cards);
+        mode.toggle();
+        assert_eq!(mode, ViewMode::Legacy);
+    }
+
+    #[test]
+    fn cards_preference_maps_in_both_builds() {
+        let mode = ViewMode::from_config(ViewPreference::Cards);
+        #[cfg(feature = "tui-cards")]
+        assert_eq!(mode, ViewMode::Cards);
+        #[cfg(not(feature = "tui-cards"))]
+        assert_eq!(mode, ViewMode::Dashboard);
+    }
+}
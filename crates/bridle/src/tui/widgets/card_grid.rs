@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::config::ProfileInfo;
+use crate::display::Theme;
 
 use super::profile_card::{NewProfileCard, ProfileCard};
 
@@ -14,6 +15,7 @@ const CARD_WIDTH: u16 = 30;
 const CARD_HEIGHT: u16 = 8;
 const CARD_GAP: u16 = 1;
 
+#[derive(Debug)]
 pub struct CardGridState {
     pub selected: usize,
     pub scroll_offset: usize,
@@ -51,6 +53,15 @@ impl CardGridState {
             self.selected -= cols;
         }
     }
+
+    /// Index of the card drawn at (`x`, `y`) in a grid rendered into `area`.
+    pub fn card_at(&self, area: Rect, x: u16, y: u16) -> Option<usize> {
+        let col = x.checked_sub(area.x)? / (CARD_WIDTH + CARD_GAP);
+        let row = y.checked_sub(area.y)? / (CARD_HEIGHT + CARD_GAP);
+        let cols = CardGrid::columns(area.width);
+        (usize::from(col) < cols)
+            .then(|| (self.scroll_offset + usize::from(row)) * cols + usize::from(col))
+    }
 }
 
 impl Default for CardGridState {
@@ -62,6 +73,7 @@ impl Default for CardGridState {
 pub struct CardGrid<'a> {
     profiles: &'a [ProfileInfo],
     show_new_card: bool,
+    theme: Theme,
 }
 
 impl<'a> CardGrid<'a> {
@@ -69,6 +81,7 @@ impl<'a> CardGrid<'a> {
         Self {
             profiles,
             show_new_card: true,
+            theme: Theme::default(),
         }
     }
 
@@ -77,7 +90,13 @@ impl<'a> CardGrid<'a> {
         self
     }
 
-    fn calc_columns(&self, width: u16) -> usize {
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// How many cards fit side by side in `width` columns.
+    pub fn columns(width: u16) -> usize {
         let usable = width.saturating_sub(CARD_GAP);
         let card_with_gap = CARD_WIDTH + CARD_GAP;
         (usable / card_with_gap).max(1) as usize
@@ -92,7 +111,7 @@ impl StatefulWidget for CardGrid<'_> {
             return;
         }
 
-        let cols = self.calc_columns(area.width);
+        let cols = Self::columns(area.width);
         let total_items = self.profiles.len() + if self.show_new_card { 1 } else { 0 };
 
         if state.selected >= total_items && total_items > 0 {
@@ -131,10 +150,12 @@ impl StatefulWidget for CardGrid<'_> {
                     ProfileCard::new(profile)
                         .selected(profile.is_active)
                         .focused(is_selected)
+                        .theme(self.theme)
                         .render(card_area, buf);
                 } else {
                     NewProfileCard::new()
                         .focused(is_selected)
+                        .theme(self.theme)
                         .render(card_area, buf);
                 }
             }
@@ -1,23 +1,24 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
     text::Line,
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use super::EmptyState;
+use super::{EmptyState, render_vertical_scrollbar};
 use crate::config::ProfileInfo;
+use crate::display::Theme;
 
-pub fn render_profile_details(profile: &ProfileInfo) -> Vec<Line<'static>> {
+pub fn render_profile_details(profile: &ProfileInfo, theme: &Theme) -> Vec<Line<'static>> {
     let nodes = crate::display::profile_to_nodes(profile);
-    crate::display::nodes_to_lines(&nodes)
+    crate::display::nodes_to_lines(&nodes, theme)
 }
 
 pub struct DetailPane<'a> {
     profile: Option<&'a ProfileInfo>,
     is_focused: bool,
     scroll_offset: u16,
+    theme: Theme,
 }
 
 impl<'a> DetailPane<'a> {
@@ -26,6 +27,7 @@ impl<'a> DetailPane<'a> {
             profile,
             is_focused: false,
             scroll_offset: 0,
+            theme: Theme::default(),
         }
     }
 
@@ -38,6 +40,11 @@ impl<'a> DetailPane<'a> {
         self.scroll_offset = offset;
         self
     }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 impl Widget for DetailPane<'_> {
@@ -49,27 +56,27 @@ impl Widget for DetailPane<'_> {
                     String::new(),
                     "Tab to focus Profiles".to_string(),
                 ];
-                let widget = EmptyState::new("Details", lines).focused(self.is_focused);
+                let widget = EmptyState::new("Details", lines)
+                    .focused(self.is_focused)
+                    .theme(self.theme);
                 widget.render(area, buf);
             }
             Some(profile) => {
-                let border_style = if self.is_focused {
-                    Style::default().fg(Color::Cyan)
-                } else {
-                    Style::default().fg(Color::DarkGray)
-                };
+                let border_style = self.theme.border(self.is_focused);
 
                 let block = Block::default()
                     .title(" Details ")
                     .borders(Borders::ALL)
                     .border_style(border_style);
 
-                let content = render_profile_details(profile);
+                let content = render_profile_details(profile, &self.theme);
+                let content_len = content.len();
 
                 Paragraph::new(content)
                     .block(block)
                     .scroll((self.scroll_offset, 0))
                     .render(area, buf);
+                render_vertical_scrollbar(area, buf, content_len, self.scroll_offset as usize);
             }
         }
     }
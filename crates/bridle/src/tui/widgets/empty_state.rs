@@ -1,15 +1,18 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Line,
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
+use crate::display::Theme;
+
 pub struct EmptyState<'a> {
     title: &'a str,
     lines: Vec<String>,
     is_focused: bool,
+    theme: Theme,
 }
 
 impl<'a> EmptyState<'a> {
@@ -18,6 +21,7 @@ impl<'a> EmptyState<'a> {
             title,
             lines,
             is_focused: false,
+            theme: Theme::default(),
         }
     }
 
@@ -26,6 +30,11 @@ impl<'a> EmptyState<'a> {
         self
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     fn count_wrapped_lines(&self, text: &str, width: usize) -> usize {
         if width == 0 {
             return 0;
@@ -36,11 +45,7 @@ impl<'a> EmptyState<'a> {
 
 impl Widget for EmptyState<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.is_focused {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_style = self.theme.border(self.is_focused);
 
         let block = Block::default()
             .title(format!(" {} ", self.title))
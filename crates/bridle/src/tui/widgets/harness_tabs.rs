@@ -1,5 +1,5 @@
+use crate::display::Theme;
 use crate::harness::HarnessConfig;
-use crate::tui::theme::Theme;
 use harness_locate::{Harness, HarnessKind, InstallationStatus};
 use ratatui::{
     buffer::Buffer,
@@ -27,9 +27,9 @@ impl HarnessStatus {
         }
     }
 
-    pub fn style(self) -> Style {
+    pub fn style(self, theme: &Theme) -> Style {
         match self {
-            Self::Active => Style::default().fg(Color::Green),
+            Self::Active => Style::default().fg(theme.profile_active),
             Self::Installed => Theme::harness_installed(),
             Self::BinaryOnly | Self::NotInstalled => Theme::harness_not_installed(),
         }
@@ -40,20 +40,23 @@ pub struct HarnessTabs<'a> {
     harnesses: &'a [HarnessKind],
     selected: usize,
     statuses: Vec<HarnessStatus>,
+    theme: Theme,
 }
 
 impl<'a> HarnessTabs<'a> {
-    pub fn new(harnesses: &'a [HarnessKind], selected: usize) -> Self {
+    /// `status_of` supplies each harness's installation status, `None` if unknown.
+    pub fn new(
+        harnesses: &'a [HarnessKind],
+        selected: usize,
+        status_of: impl Fn(HarnessKind) -> Option<&'a InstallationStatus>,
+    ) -> Self {
         let statuses = harnesses
             .iter()
-            .map(|kind| {
-                let harness = Harness::new(*kind);
-                match harness.installation_status() {
-                    Ok(InstallationStatus::FullyInstalled { .. })
-                    | Ok(InstallationStatus::ConfigOnly { .. }) => HarnessStatus::Installed,
-                    Ok(InstallationStatus::BinaryOnly { .. }) => HarnessStatus::BinaryOnly,
-                    _ => HarnessStatus::NotInstalled,
-                }
+            .map(|kind| match status_of(*kind) {
+                Some(InstallationStatus::FullyInstalled { .. })
+                | Some(InstallationStatus::ConfigOnly { .. }) => HarnessStatus::Installed,
+                Some(InstallationStatus::BinaryOnly { .. }) => HarnessStatus::BinaryOnly,
+                _ => HarnessStatus::NotInstalled,
             })
             .collect();
 
@@ -61,6 +64,7 @@ impl<'a> HarnessTabs<'a> {
             harnesses,
             selected,
             statuses,
+            theme: Theme::default(),
         }
     }
 
@@ -73,6 +77,11 @@ impl<'a> HarnessTabs<'a> {
         }
         self
     }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 impl Widget for HarnessTabs<'_> {
@@ -84,7 +93,7 @@ impl Widget for HarnessTabs<'_> {
             .map(|(kind, status)| {
                 let harness = Harness::new(*kind);
                 let name = harness.kind().to_string();
-                let style = status.style();
+                let style = status.style(&self.theme);
                 Line::from(vec![
                     Span::styled(format!("{} ", status.indicator()), style),
                     Span::styled(name, style),
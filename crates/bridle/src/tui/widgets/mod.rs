@@ -2,6 +2,7 @@ mod detail_pane;
 mod empty_state;
 mod harness_tabs;
 mod profile_table;
+mod scrollbar;
 mod status_bar;
 
 #[cfg(feature = "tui-cards")]
@@ -13,9 +14,8 @@ pub use detail_pane::{DetailPane, render_profile_details};
 pub use empty_state::EmptyState;
 pub use harness_tabs::HarnessTabs;
 pub use profile_table::ProfileTable;
+pub use scrollbar::render_vertical_scrollbar;
 pub use status_bar::StatusBar;
 
 #[cfg(feature = "tui-cards")]
 pub use card_grid::{CardGrid, CardGridState};
-#[cfg(feature = "tui-cards")]
-pub use profile_card::{NewProfileCard, ProfileCard};
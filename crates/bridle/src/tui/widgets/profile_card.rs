@@ -3,18 +3,19 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
 use crate::config::ProfileInfo;
-use crate::tui::theme::Theme;
+use crate::display::Theme;
 
 pub struct ProfileCard<'a> {
     profile: &'a ProfileInfo,
     selected: bool,
     focused: bool,
+    theme: Theme,
 }
 
 impl<'a> ProfileCard<'a> {
@@ -23,6 +24,7 @@ impl<'a> ProfileCard<'a> {
             profile,
             selected: false,
             focused: false,
+            theme: Theme::default(),
         }
     }
 
@@ -35,14 +37,19 @@ impl<'a> ProfileCard<'a> {
         self.focused = focused;
         self
     }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 impl Widget for ProfileCard<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let border_style = if self.focused {
-            Theme::border_active()
+            self.theme.border(true)
         } else if self.selected {
-            Theme::profile_active()
+            self.theme.profile_active()
         } else {
             Style::default().fg(Color::DarkGray)
         };
@@ -50,7 +57,7 @@ impl Widget for ProfileCard<'_> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
-            .title(self.profile.name.clone());
+            .title(crate::display::profile_label(self.profile));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -64,17 +71,15 @@ impl Widget for ProfileCard<'_> {
         if self.profile.is_active {
             lines.push(Line::from(Span::styled(
                 "● Active",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+                self.theme.profile_active(),
             )));
         }
 
-        if let Some(model) = &self.profile.model {
+        if let Some(model) = self.profile.model.as_deref() {
             let display = if model.len() > inner.width as usize - 2 {
                 format!("{}…", &model[..inner.width as usize - 3])
             } else {
-                model.clone()
+                model.to_string()
             };
             lines.push(Line::from(Span::styled(
                 display,
@@ -90,7 +95,7 @@ impl Widget for ProfileCard<'_> {
             )));
         }
 
-        if let Some(theme) = &self.profile.theme {
+        if let Some(theme) = self.profile.theme.as_deref() {
             lines.push(Line::from(Span::styled(
                 format!("Theme: {}", theme),
                 Style::default().fg(Color::Gray),
@@ -104,17 +109,26 @@ impl Widget for ProfileCard<'_> {
 
 pub struct NewProfileCard {
     focused: bool,
+    theme: Theme,
 }
 
 impl NewProfileCard {
     pub fn new() -> Self {
-        Self { focused: false }
+        Self {
+            focused: false,
+            theme: Theme::default(),
+        }
     }
 
     pub fn focused(mut self, focused: bool) -> Self {
         self.focused = focused;
         self
     }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 impl Default for NewProfileCard {
@@ -125,11 +139,7 @@ impl Default for NewProfileCard {
 
 impl Widget for NewProfileCard {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.focused {
-            Theme::border_active()
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_style = self.theme.border(self.focused);
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -7,30 +7,30 @@ use ratatui::{
 
 use super::EmptyState;
 use crate::config::ProfileInfo;
+use crate::display::Theme;
 
 pub struct ProfileTable<'a> {
     profiles: &'a [ProfileInfo],
-    block: Option<Block<'a>>,
     focused: bool,
+    theme: Theme,
 }
 
 impl<'a> ProfileTable<'a> {
     pub fn new(profiles: &'a [ProfileInfo]) -> Self {
         Self {
             profiles,
-            block: None,
             focused: false,
+            theme: Theme::default(),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn block(mut self, block: Block<'a>) -> Self {
-        self.block = Some(block);
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
         self
     }
 
-    pub fn focused(mut self, focused: bool) -> Self {
-        self.focused = focused;
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
         self
     }
 
@@ -63,7 +63,9 @@ impl StatefulWidget for ProfileTable<'_> {
                 String::new(),
                 "Press 'n' to create a profile".to_string(),
             ];
-            let widget = EmptyState::new("Profiles", lines).focused(self.focused);
+            let widget = EmptyState::new("Profiles", lines)
+                .focused(self.focused)
+                .theme(self.theme);
             widget.render(area, buf);
             return;
         }
@@ -78,7 +80,7 @@ impl StatefulWidget for ProfileTable<'_> {
         let rows = self.profiles.iter().map(|profile| {
             let active = if profile.is_active { "●" } else { " " };
             let active_style = if profile.is_active {
-                Style::default().fg(Color::Green)
+                Style::default().fg(self.theme.profile_active)
             } else {
                 Style::default()
             };
@@ -98,7 +100,7 @@ impl StatefulWidget for ProfileTable<'_> {
 
             Row::new(vec![
                 Cell::from(active).style(active_style),
-                Cell::from(profile.name.as_str()),
+                Cell::from(crate::display::profile_label(profile)),
                 Cell::from(model).style(Style::default().add_modifier(Modifier::DIM)),
                 Cell::from(mcp).style(Style::default().add_modifier(Modifier::DIM)),
             ])
@@ -111,11 +113,7 @@ impl StatefulWidget for ProfileTable<'_> {
             Constraint::Length(4),
         ];
 
-        let border_style = if self.focused {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_style = self.theme.border(self.focused);
 
         let table = Table::new(rows, widths)
             .header(header)
@@ -132,12 +130,6 @@ impl StatefulWidget for ProfileTable<'_> {
                     .title(" Profiles "),
             );
 
-        let table = if let Some(block) = self.block {
-            table.block(block)
-        } else {
-            table
-        };
-
         StatefulWidget::render(table, area, buf, state);
     }
 }
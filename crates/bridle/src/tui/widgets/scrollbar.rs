@@ -0,0 +1,32 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget},
+};
+
+/// Draws a vertical scrollbar on the right border of a bordered `area`, but
+/// only when `content_len` rows don't fit inside it.
+pub fn render_vertical_scrollbar(
+    area: Rect,
+    buf: &mut Buffer,
+    content_len: usize,
+    position: usize,
+) {
+    let track = area.inner(Margin {
+        vertical: 1,
+        horizontal: 0,
+    });
+    if content_len <= track.height as usize {
+        return;
+    }
+    let mut state = ScrollbarState::new(content_len)
+        .viewport_content_length(track.height as usize)
+        .position(position);
+    Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(Style::default().fg(Color::Cyan))
+        .track_style(Style::default().fg(Color::DarkGray))
+        .render(track, buf, &mut state);
+}
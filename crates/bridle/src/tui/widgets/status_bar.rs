@@ -1,4 +1,4 @@
-use crate::tui::theme::Theme;
+use crate::display::Theme;
 use crate::tui::views::ViewMode;
 use harness_locate::InstallationStatus;
 use ratatui::{
@@ -12,6 +12,7 @@ pub struct StatusBar<'a> {
     view_mode: ViewMode,
     message: Option<&'a str>,
     harness_status: Option<&'a str>,
+    theme: Theme,
 }
 
 impl<'a> StatusBar<'a> {
@@ -20,6 +21,7 @@ impl<'a> StatusBar<'a> {
             view_mode,
             message: None,
             harness_status: None,
+            theme: Theme::default(),
         }
     }
 
@@ -33,6 +35,11 @@ impl<'a> StatusBar<'a> {
         self
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     pub fn installation_status_text(status: &InstallationStatus) -> &'static str {
         match status {
             InstallationStatus::FullyInstalled { .. } => "Installed",
@@ -46,14 +53,14 @@ impl<'a> StatusBar<'a> {
     fn keybindings(&self) -> &'static str {
         match self.view_mode {
             ViewMode::Dashboard => {
-                "q:quit  ←/→:harness  ↑/↓:profile  Tab:focus  Enter:switch  n:new  d:del  e:edit  r:refresh  ?:help"
+                "q:quit  ←/→:harness  ↑/↓:profile  Tab:focus  Enter:switch  n:new  d:del  e:edit  o:open  r:refresh  ?:help"
             }
             ViewMode::Legacy => {
-                "q:quit  Tab:pane  ↑/↓:nav  Enter:switch  n:new  d:del  e:edit  r:refresh  ?:help"
+                "q:quit  Tab:pane  ↑/↓:nav  Enter:switch  n:new  d:del  e:edit  o:open  r:refresh  ?:help"
             }
             #[cfg(feature = "tui-cards")]
             ViewMode::Cards => {
-                "q:quit  ←/→:harness  ↑/↓:profile  Enter:switch  n:new  d:del  e:edit  r:refresh  ?:help"
+                "q:quit  ←/→:harness  ↑/↓:profile  Enter:switch  n:new  d:del  e:edit  o:open  r:refresh  ?:help"
             }
         }
     }
@@ -84,7 +91,7 @@ impl Widget for StatusBar<'_> {
 
         spans.push(Span::styled(self.keybindings(), Theme::text_muted()));
         spans.push(Span::raw("  "));
-        spans.push(Span::styled(msg, Theme::text_warning()));
+        spans.push(Span::styled(msg, self.theme.warning()));
 
         let paragraph = Paragraph::new(Line::from(spans));
         paragraph.render(area, buf);
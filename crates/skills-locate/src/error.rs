@@ -38,6 +38,13 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// The server refused the request because of rate limiting (HTTP 403/429).
+    #[error("rate limited{}", retry_after.map(|s| format!(" (retry after {s}s)")).unwrap_or_default())]
+    RateLimited {
+        /// Seconds until the limit resets, from the `Retry-After` header.
+        retry_after: Option<u64>,
+    },
+
     /// Download size limit exceeded.
     #[error("size limit exceeded: {size} bytes > {limit} bytes")]
     SizeLimit {
@@ -100,6 +107,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_display_rate_limited() {
+        let err = Error::RateLimited {
+            retry_after: Some(60),
+        };
+        assert_eq!(err.to_string(), "rate limited (retry after 60s)");
+        let err = Error::RateLimited { retry_after: None };
+        assert_eq!(err.to_string(), "rate limited");
+    }
+
     #[test]
     fn error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
@@ -11,11 +11,25 @@ const MAX_RETRIES: u32 = 3;
 const RETRY_DELAYS_MS: [u64; 3] = [100, 500, 2000];
 const SIZE_LIMIT: u64 = 200 * 1024 * 1024;
 
+/// Options controlling a single fetch.
+#[derive(Debug, Clone, Default)]
+pub struct FetchOptions {
+    /// Overall deadline for each request attempt. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    /// Bearer token sent in the `Authorization` header.
+    pub auth_token: Option<String>,
+}
+
 pub fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
+    fetch_bytes_with(url, &FetchOptions::default())
+}
+
+/// Like [`fetch_bytes`], with a request timeout and optional authentication.
+pub fn fetch_bytes_with(url: &str, options: &FetchOptions) -> Result<Vec<u8>> {
     let mut last_error = None;
 
     for attempt in 0..MAX_RETRIES {
-        match try_fetch(url) {
+        match try_fetch(url, options) {
             Ok(bytes) => return Ok(bytes),
             Err(e) => {
                 if is_retryable(&e) && attempt < MAX_RETRIES - 1 {
@@ -31,13 +45,37 @@ pub fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
     Err(last_error.unwrap_or_else(|| Error::Http("max retries exceeded".into())))
 }
 
-fn try_fetch(url: &str) -> Result<Vec<u8>> {
-    let mut response = ureq::get(url).call().map_err(|e| match e {
-        ureq::Error::StatusCode(code) => Error::Http(format!("HTTP {code} for {url}")),
+fn try_fetch(url: &str, options: &FetchOptions) -> Result<Vec<u8>> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(options.timeout)
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let mut request = agent.get(url);
+    if let Some(token) = &options.auth_token {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+
+    let mut response = request.call().map_err(|e| match e {
+        ureq::Error::Timeout(t) => Error::Http(format!("timeout: {t} for {url}")),
         ureq::Error::Io(io_err) => Error::Http(format!("transport error: {io_err}")),
         _ => Error::Http(format!("request failed: {e}")),
     })?;
 
+    let code = response.status().as_u16();
+    if code == 403 || code == 429 {
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        return Err(Error::RateLimited { retry_after });
+    }
+    if code >= 400 {
+        return Err(Error::Http(format!("HTTP {code} for {url}")));
+    }
+
     // Check content-length header before reading body
     if let Some(len) = response.headers().get("content-length")
         && let Ok(size) = len.to_str().unwrap_or("").parse::<u64>()
@@ -126,7 +164,33 @@ pub fn list_files(zip_bytes: &[u8], suffix: &str) -> Result<Vec<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    /// Serves one canned HTTP response and reports the request headers it saw.
+    fn serve_once(response: &'static str) -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/archive.zip", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                headers.push(line.trim_end().to_string());
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            tx.send(headers).unwrap();
+        });
+
+        (url, rx)
+    }
 
     fn create_test_zip(files: &[(&str, &str)]) -> Vec<u8> {
         let mut buf = Cursor::new(Vec::new());
@@ -176,4 +240,61 @@ mod tests {
         let matches = list_files(&zip, ".json").unwrap();
         assert!(matches.is_empty());
     }
+
+    #[test]
+    fn fetch_maps_429_to_rate_limited() {
+        let (url, _rx) = serve_once(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 42\r\nContent-Length: 0\r\n\r\n",
+        );
+        let result = fetch_bytes(&url);
+        assert!(matches!(
+            result,
+            Err(Error::RateLimited {
+                retry_after: Some(42)
+            })
+        ));
+    }
+
+    #[test]
+    fn fetch_maps_403_without_retry_after() {
+        let (url, _rx) = serve_once("HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n");
+        let result = fetch_bytes(&url);
+        assert!(matches!(
+            result,
+            Err(Error::RateLimited { retry_after: None })
+        ));
+    }
+
+    #[test]
+    fn fetch_sends_auth_token() {
+        let (url, rx) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok");
+        let options = FetchOptions {
+            timeout: Some(Duration::from_secs(5)),
+            auth_token: Some("secret-token".to_string()),
+        };
+
+        let bytes = fetch_bytes_with(&url, &options).unwrap();
+        assert_eq!(bytes, b"ok");
+
+        let headers = rx.recv().unwrap();
+        assert!(
+            headers
+                .iter()
+                .any(|h| h.eq_ignore_ascii_case("authorization: Bearer secret-token")),
+            "missing auth header in {headers:?}"
+        );
+    }
+
+    #[test]
+    fn fetch_omits_auth_header_without_token() {
+        let (url, rx) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+        fetch_bytes(&url).unwrap();
+
+        let headers = rx.recv().unwrap();
+        assert!(
+            !headers
+                .iter()
+                .any(|h| h.to_ascii_lowercase().starts_with("authorization:"))
+        );
+    }
 }
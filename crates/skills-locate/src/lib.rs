@@ -18,7 +18,9 @@ pub use component::{
 pub use detect::{DetectedMcp, DetectionConfidence, DetectionSource, detect_mcp_from_files};
 pub use discovery::{discover_all, discover_from_source, discover_plugins};
 pub use error::{Error, Result};
-pub use fetch::{extract_file, fetch_bytes, fetch_json, list_files};
+pub use fetch::{
+    FetchOptions, extract_file, fetch_bytes, fetch_bytes_with, fetch_json, list_files,
+};
 pub use github::GitHubRef;
 pub use marketplace::{Marketplace, MarketplaceEntry};
 pub use registry::{PackageEntry, RegistryClient, RemoteEntry, ServerEntry};